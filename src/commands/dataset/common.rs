@@ -0,0 +1,342 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::{BufRead, BufReader, Write},
+    path::Path,
+};
+
+use clap::ValueEnum;
+use cliclack as cli;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::{error::Error, output::csv_cell, result::Result};
+
+/// Dataset file format.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum DatasetFormat {
+    /// JSON Lines (one sample per line)
+    Jsonl,
+
+    /// Comma separated values (metadata stored in columns)
+    Csv,
+}
+
+impl DatasetFormat {
+    /// Returns the format for a path based on its extension.
+    ///
+    /// Defaults to JSONL for unknown extensions.
+    pub fn for_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("csv") => Self::Csv,
+            _ => Self::Jsonl,
+        }
+    }
+
+    pub fn ext(&self) -> &'static str {
+        match self {
+            Self::Jsonl => "jsonl",
+            Self::Csv => "csv",
+        }
+    }
+}
+
+/// Dataset sample record.
+///
+/// Field names match those used by Inspect's default sample field
+/// spec so that records can be loaded with `json_dataset` and
+/// `csv_dataset` without additional configuration.
+#[derive(Serialize, Debug, Default)]
+pub struct DatasetRecord {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<Value>,
+
+    pub input: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub choices: Option<Vec<String>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<Value>,
+
+    #[serde(skip_serializing_if = "Map::is_empty")]
+    pub metadata: Map<String, Value>,
+}
+
+const CSV_FIELDS: [&str; 4] = ["id", "input", "choices", "target"];
+
+/// Writes records to a dataset file.
+///
+/// When `append` is true and the file exists, records are added to the
+/// end of the file. CSV records are appended using the existing header.
+pub fn write_dataset(
+    path: &Path,
+    format: DatasetFormat,
+    records: &[DatasetRecord],
+    append: bool,
+) -> Result<()> {
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        fs::create_dir_all(parent)?;
+    }
+    match format {
+        DatasetFormat::Jsonl => write_jsonl(path, records, append),
+        DatasetFormat::Csv => write_csv(path, records, append),
+    }
+}
+
+/// Confirms that samples should be added to an existing file.
+///
+/// Returns false if the file doesn't exist.
+pub fn confirm_append(path: &Path, yes: bool) -> Result<bool> {
+    if !path.exists() {
+        return Ok(false);
+    }
+    if yes {
+        return Err(Error::general(format!(
+            "{} exists\n\
+            \n\
+            Use --append to add samples to this file or specify a \
+            different file with --output.",
+            path.to_string_lossy()
+        )));
+    }
+    if cli::confirm(format!(
+        "{} exists. Add samples to this file?",
+        path.to_string_lossy()
+    ))
+    .initial_value(true)
+    .interact()?
+    {
+        Ok(true)
+    } else {
+        Err(Error::Canceled)
+    }
+}
+
+/// Returns the highest numeric sample ID in a dataset file.
+///
+/// IDs that aren't integers are ignored. Returns 0 if the file doesn't
+/// exist or doesn't have numeric IDs.
+pub fn max_numeric_id(path: &Path, format: DatasetFormat) -> Result<u64> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(e.into()),
+    };
+    let ids = match format {
+        DatasetFormat::Jsonl => contents
+            .lines()
+            .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+            .filter_map(|record| match &record["id"] {
+                Value::Number(n) => n.as_u64(),
+                Value::String(s) => s.parse().ok(),
+                _ => None,
+            })
+            .collect::<Vec<_>>(),
+        DatasetFormat::Csv => {
+            let mut r = csv::Reader::from_reader(contents.as_bytes());
+            let Some(id_col) = r
+                .headers()
+                .map_err(csv_error)?
+                .iter()
+                .position(|name| name == "id")
+            else {
+                return Ok(0);
+            };
+            r.records()
+                .filter_map(|record| record.ok()?.get(id_col)?.parse().ok())
+                .collect()
+        }
+    };
+    Ok(ids.into_iter().max().unwrap_or(0))
+}
+
+fn write_jsonl(path: &Path, records: &[DatasetRecord], append: bool) -> Result<()> {
+    let mut f = OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(append)
+        .truncate(!append)
+        .open(path)?;
+    // Guard against a missing LF at the end of an existing file
+    if append && !ends_with_newline(path)? {
+        writeln!(f)?;
+    }
+    for record in records {
+        let line = serde_json::to_string(record).map_err(|e| Error::general(e.to_string()))?;
+        writeln!(f, "{line}")?;
+    }
+    Ok(())
+}
+
+fn ends_with_newline(path: &Path) -> Result<bool> {
+    let contents = fs::read(path)?;
+    Ok(contents.last().map(|b| *b == b'\n').unwrap_or(true))
+}
+
+fn write_csv(path: &Path, records: &[DatasetRecord], append: bool) -> Result<()> {
+    let existing_header = if append { read_csv_header(path)? } else { None };
+    let header = match &existing_header {
+        Some(header) => {
+            check_csv_header(path, header, records)?;
+            header.clone()
+        }
+        None => csv_header(records),
+    };
+    let f = OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(append)
+        .truncate(!append)
+        .open(path)?;
+    let mut w = csv::Writer::from_writer(f);
+    if existing_header.is_none() {
+        w.write_record(&header).map_err(csv_error)?;
+    }
+    for record in records {
+        w.write_record(header.iter().map(|name| csv_value(record, name)))
+            .map_err(csv_error)?;
+    }
+    w.flush()?;
+    Ok(())
+}
+
+fn read_csv_header(path: &Path) -> Result<Option<Vec<String>>> {
+    let f = match fs::File::open(path) {
+        Ok(f) => f,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    let mut line = String::new();
+    BufReader::new(f).read_line(&mut line)?;
+    if line.trim().is_empty() {
+        return Ok(None);
+    }
+    let mut r = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .from_reader(line.as_bytes());
+    match r.records().next() {
+        Some(header) => Ok(Some(
+            header
+                .map_err(csv_error)?
+                .iter()
+                .map(String::from)
+                .collect(),
+        )),
+        None => Ok(None),
+    }
+}
+
+/// Returns CSV column names for records.
+///
+/// Standard fields are included only when at least one record uses
+/// them. Metadata keys follow as additional columns in the order they
+/// first appear.
+fn csv_header(records: &[DatasetRecord]) -> Vec<String> {
+    let mut header: Vec<String> = CSV_FIELDS
+        .iter()
+        .filter(|name| **name == "input" || records.iter().any(|r| !csv_value(r, name).is_empty()))
+        .map(|name| name.to_string())
+        .collect();
+    for record in records {
+        for key in record.metadata.keys() {
+            if !header.contains(key) {
+                header.push(key.clone());
+            }
+        }
+    }
+    header
+}
+
+fn check_csv_header(path: &Path, header: &[String], records: &[DatasetRecord]) -> Result<()> {
+    for name in csv_header(records) {
+        if !header.contains(&name) {
+            return Err(Error::general(format!(
+                "Cannot append to {}: missing column '{name}'",
+                path.to_string_lossy()
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn csv_value(record: &DatasetRecord, name: &str) -> String {
+    match name {
+        "id" => record.id.as_ref().map(csv_cell).unwrap_or_default(),
+        "input" => record.input.clone(),
+        "choices" => record
+            .choices
+            .as_ref()
+            .map(|choices| serde_json::to_string(choices).unwrap())
+            .unwrap_or_default(),
+        "target" => record.target.as_ref().map(csv_cell).unwrap_or_default(),
+        _ => record.metadata.get(name).map(csv_cell).unwrap_or_default(),
+    }
+}
+
+fn csv_error(e: csv::Error) -> Error {
+    Error::general(e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{Map, Value, json};
+
+    use crate::util::TempDir;
+
+    use super::{DatasetFormat, DatasetRecord, csv_header, csv_value, max_numeric_id};
+
+    #[test]
+    fn test_csv_header() {
+        let mut metadata = Map::new();
+        metadata.insert("lang".into(), json!("en"));
+        let records = [
+            DatasetRecord {
+                input: "Alice".into(),
+                ..Default::default()
+            },
+            DatasetRecord {
+                id: Some(json!(2)),
+                input: "Bob".into(),
+                target: Some(json!("Bob")),
+                metadata,
+                ..Default::default()
+            },
+        ];
+        assert_eq!(csv_header(&records), ["id", "input", "target", "lang"]);
+        assert_eq!(csv_value(&records[1], "id"), "2");
+        assert_eq!(csv_value(&records[1], "lang"), "en");
+        assert_eq!(csv_value(&records[0], "lang"), "");
+        assert_eq!(
+            csv_value(
+                &DatasetRecord {
+                    target: Some(Value::Array(vec![json!("a"), json!("b")])),
+                    ..Default::default()
+                },
+                "target"
+            ),
+            r#"["a","b"]"#
+        );
+    }
+
+    #[test]
+    fn test_max_numeric_id() {
+        let tmp = TempDir::create("gage-dataset-test").unwrap();
+        let jsonl = tmp.path().join("samples.jsonl");
+        assert_eq!(max_numeric_id(&jsonl, DatasetFormat::Jsonl).unwrap(), 0);
+        std::fs::write(
+            &jsonl,
+            "{\"id\": 3, \"input\": \"a\"}\n{\"id\": \"7\", \"input\": \"b\"}\n\
+             {\"id\": \"x\", \"input\": \"c\"}\n{\"input\": \"d\"}\n",
+        )
+        .unwrap();
+        assert_eq!(max_numeric_id(&jsonl, DatasetFormat::Jsonl).unwrap(), 7);
+
+        let csv = tmp.path().join("samples.csv");
+        std::fs::write(&csv, "id,input\n2,a\n10,b\nx,c\n").unwrap();
+        assert_eq!(max_numeric_id(&csv, DatasetFormat::Csv).unwrap(), 10);
+        std::fs::write(&csv, "input\na\n").unwrap();
+        assert_eq!(max_numeric_id(&csv, DatasetFormat::Csv).unwrap(), 0);
+    }
+}
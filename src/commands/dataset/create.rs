@@ -1,7 +1,27 @@
+use std::path::PathBuf;
+
 use clap::Args as ArgsTrait;
+use cliclack as cli;
+use console::style;
 use pyo3::Python;
+use serde_json::{Map, Value};
 
-use crate::{commands::task::select_task_dialog, py, result::Result};
+use crate::{
+    commands::{
+        dataset::common::{
+            DatasetFormat, DatasetRecord, confirm_append, max_numeric_id, write_dataset,
+        },
+        task::{
+            run::{input_placeholder, target_placeholder, task_summary},
+            select_task_dialog,
+        },
+    },
+    dialog::{DialogResult, handle_dialog_result},
+    inspect::task::get_task_doc,
+    plural,
+    py::{self, Docstring, DocstringParam},
+    result::Result,
+};
 
 #[derive(ArgsTrait, Debug)]
 pub struct Args {
@@ -13,16 +33,213 @@ pub struct Args {
     #[arg(short, long)]
     path: Option<String>,
 
-    /// Don't prompt when creating dataset
+    /// Dataset file to write (defaults to TASK.jsonl)
+    #[arg(short, long, value_name = "FILE")]
+    output: Option<PathBuf>,
+
+    /// Dataset format (defaults to format implied by file extension)
+    #[arg(short, long, value_enum)]
+    format: Option<DatasetFormat>,
+
+    /// Add samples to an existing dataset file
+    #[arg(long)]
+    append: bool,
+
+    /// Prompt for sample IDs (Inspect assigns IDs when not specified)
+    #[arg(long)]
+    ids: bool,
+
+    /// Prompt for sample metadata
+    #[arg(long)]
+    metadata: bool,
+
+    /// Don't prompt for task or dataset file (use defaults)
     #[arg(short, long)]
     yes: bool,
 }
 
 pub fn main(args: Args) -> Result<()> {
+    handle_dialog_result(create_dialog(args))
+}
+
+fn create_dialog(args: Args) -> Result<DialogResult> {
+    cli::intro(style("Create dataset").bold())?;
     py::init();
     Python::attach(|py| {
         let task = select_task_dialog(py, args.task.as_deref(), args.path.as_deref(), args.yes)?;
-        println!("TODO something with {task:?}");
-        Ok(())
+        let task_doc = get_task_doc(py, &task)?;
+
+        // Task summary
+        if let Some(summary) = task_summary(&task, task_doc.as_ref()) {
+            cli::log::remark(summary)?;
+        }
+
+        // Dataset file and format
+        let default_format = args.format.unwrap_or(DatasetFormat::Jsonl);
+        let path = match args.output {
+            Some(path) => {
+                cli::log::step(format!(
+                    "Dataset file:\n{}",
+                    style(path.to_string_lossy()).dim()
+                ))?;
+                path
+            }
+            None => {
+                let default = format!("{}.{}", task.name, default_format.ext());
+                if args.yes {
+                    cli::log::step(format!("Dataset file:\n{}", style(&default).dim()))?;
+                    PathBuf::from(default)
+                } else {
+                    cli::Input::new("Dataset file:")
+                        .default_input(&default)
+                        .interact::<String>()?
+                        .into()
+                }
+            }
+        };
+        let format = args
+            .format
+            .unwrap_or_else(|| DatasetFormat::for_path(&path));
+        let append = args.append || confirm_append(&path, args.yes)?;
+
+        // Samples
+        let params = input_params(task_doc.as_ref());
+        let input_help = input_placeholder(task_doc.as_ref());
+        let target_help = target_placeholder(task_doc.as_ref());
+        // Default IDs follow those in the file when appending
+        let first_id = if append && args.ids {
+            max_numeric_id(&path, format)? + 1
+        } else {
+            1
+        };
+        let mut records = Vec::new();
+        loop {
+            cli::log::remark(style(format!("Sample {}", records.len() + 1)).bold())?;
+            records.push(prompt_record(
+                &params,
+                &input_help,
+                &target_help,
+                first_id + records.len() as u64,
+                args.ids,
+                args.metadata,
+            )?);
+            if !cli::confirm("Add another sample?")
+                .initial_value(true)
+                .interact()?
+            {
+                break;
+            }
+        }
+
+        write_dataset(&path, format, &records, append)?;
+
+        Ok(DialogResult::Message(format!(
+            "{} {} {} to {}",
+            if append { "Added" } else { "Wrote" },
+            records.len(),
+            plural!("sample", records.len()),
+            path.to_string_lossy()
+        )))
     })
 }
+
+/// Returns the task params that make up sample input.
+///
+/// Tasks with a single (or no) documented param use a single input
+/// value. Tasks with multiple params prompt for each param and combine
+/// the values as 'NAME: VALUE' lines, which is the format used by
+/// 'gage run' for these tasks.
+fn input_params(task_doc: Option<&Docstring>) -> Vec<&DocstringParam> {
+    match task_doc {
+        Some(doc) if doc.params.len() > 1 => doc.params.iter().collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn prompt_record(
+    params: &[&DocstringParam],
+    input_help: &str,
+    target_help: &str,
+    default_id: u64,
+    ids: bool,
+    metadata: bool,
+) -> Result<DatasetRecord> {
+    // Id
+    let id = if ids {
+        let id: String = cli::Input::new("Id:")
+            .default_input(&default_id.to_string())
+            .interact()?;
+        Some(parse_value(&id))
+    } else {
+        None
+    };
+
+    // Input
+    let input = if params.is_empty() {
+        cli::Input::new("Input:")
+            .multiline()
+            .placeholder(input_help)
+            .interact()?
+    } else {
+        let mut lines = Vec::new();
+        for param in params {
+            let val: String = cli::Input::new(format!("{}:", param.arg_name))
+                .placeholder(param.description.as_deref().unwrap_or_default())
+                .interact()?;
+            lines.push(format!("{}: {val}", param.arg_name));
+        }
+        lines.join("\n")
+    };
+
+    // Target
+    let target: String = cli::Input::new("Target (optional):")
+        .placeholder(target_help)
+        .required(false)
+        .interact()?;
+    let target = (!target.is_empty()).then_some(Value::String(target));
+
+    // Metadata
+    let metadata = if metadata {
+        let val: String = cli::Input::new("Metadata (optional):")
+            .placeholder("NAME=VALUE, ...")
+            .required(false)
+            .validate(|val: &String| parse_metadata(val).map(|_| ()))
+            .interact()?;
+        parse_metadata(&val).expect("validated")
+    } else {
+        Map::new()
+    };
+
+    Ok(DatasetRecord {
+        id,
+        input,
+        target,
+        metadata,
+        ..Default::default()
+    })
+}
+
+/// Parses comma separated NAME=VALUE pairs.
+fn parse_metadata(s: &str) -> std::result::Result<Map<String, Value>, String> {
+    let mut metadata = Map::new();
+    for part in s.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+        let Some((name, val)) = part.split_once('=') else {
+            return Err(format!("Expected NAME=VALUE but got '{part}'"));
+        };
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(format!("Missing name in '{part}'"));
+        }
+        metadata.insert(name.into(), parse_value(val.trim()));
+    }
+    Ok(metadata)
+}
+
+/// Parses a value as a JSON number or boolean, falling back to a
+/// string.
+fn parse_value(s: &str) -> Value {
+    match serde_json::from_str::<Value>(s) {
+        Ok(val @ (Value::Number(_) | Value::Bool(_))) => val,
+        _ => Value::String(s.into()),
+    }
+}
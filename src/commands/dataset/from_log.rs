@@ -0,0 +1,175 @@
+use std::{cell::RefCell, path::PathBuf};
+
+use clap::Args as ArgsTrait;
+use pyo3::Python;
+use serde_json::{Map, Value};
+
+use crate::{
+    commands::{
+        dataset::common::{DatasetFormat, DatasetRecord, confirm_append, write_dataset},
+        log::common::{LogOpDialog, LogSelect, SelectedLog},
+    },
+    dialog::{DialogResult, handle_dialog_result},
+    error::Error,
+    inspect::log::{EvalSample, read_log, resolve_log_dir},
+    plural,
+    result::Result,
+};
+
+#[derive(ArgsTrait, Debug)]
+pub struct Args {
+    /// Logs to read samples from
+    ///
+    /// LOG may be specified using its # or Id. To use a range, use one
+    /// of 'START:', ':END', or 'START:END'. Use a filter expression such
+    /// as 'task=summarize' to use logs matching the filter (see 'gage
+    /// log list --help').
+    #[arg(value_name = "LOG", required = true)]
+    specs: Vec<String>,
+
+    /// Dataset file to write
+    #[arg(short, long, value_name = "FILE")]
+    output: PathBuf,
+
+    /// Dataset format (defaults to format implied by file extension)
+    #[arg(short, long, value_enum)]
+    format: Option<DatasetFormat>,
+
+    /// Add samples to an existing dataset file
+    #[arg(long)]
+    append: bool,
+
+    /// Use model output as the sample target
+    #[arg(long)]
+    output_as_target: bool,
+
+    /// Only include samples scored as correct
+    #[arg(long, conflicts_with = "incorrect")]
+    correct: bool,
+
+    /// Only include samples scored as incorrect
+    #[arg(long)]
+    incorrect: bool,
+
+    /// Only include sample ID (may use more than once)
+    #[arg(short, long = "sample", value_name = "ID")]
+    samples: Vec<String>,
+
+    /// Log directory
+    #[arg(long)]
+    log_dir: Option<PathBuf>,
+
+    /// Don't prompt for dialog
+    #[arg(short, long)]
+    yes: bool,
+}
+
+pub fn main(args: Args) -> Result<()> {
+    let log_specs = LogSelect::parse_specs(&args.specs)?;
+    let format = args
+        .format
+        .unwrap_or_else(|| DatasetFormat::for_path(&args.output));
+    let append = args.append || confirm_append(&args.output, args.yes)?;
+    let records = RefCell::new(Vec::new());
+    let result = LogOpDialog::new("Create dataset from logs")
+        .log_dir(resolve_log_dir(args.log_dir.as_ref()))
+        .log_specs(log_specs)
+        .show_prompt(!args.yes)
+        .confirm_prompt(|selected| {
+            format!(
+                "You are about to create a dataset from {} {}. Continue?",
+                selected.len(),
+                plural!("log", selected.len())
+            )
+        })
+        .run(|log| {
+            let mut records = records.borrow_mut();
+            for mut record in log_records(&log, &args)? {
+                // Qualify IDs that are already used by samples from other logs
+                if records.iter().any(|r: &DatasetRecord| r.id == record.id)
+                    && let Some(id) = record.id.as_ref()
+                {
+                    record.id = Some(Value::String(format!(
+                        "{}-{}",
+                        log.log().short_log_id(),
+                        id.as_str().map(String::from).unwrap_or(id.to_string())
+                    )));
+                }
+                records.push(record);
+            }
+            Ok(())
+        });
+    handle_dialog_result(result.and_then(|result| {
+        let records = records.into_inner();
+        if records.is_empty() {
+            return Err(Error::general("No samples matched"));
+        }
+        write_dataset(&args.output, format, &records, append)?;
+        let logs = result.count();
+        Ok(DialogResult::Message(format!(
+            "{} {} {} from {logs} {} to {}",
+            if append { "Added" } else { "Wrote" },
+            records.len(),
+            plural!("sample", records.len()),
+            plural!("log", logs),
+            args.output.to_string_lossy()
+        )))
+    }))
+}
+
+fn log_records(log: &SelectedLog, args: &Args) -> Result<Vec<DatasetRecord>> {
+    let eval_log = Python::attach(|py| read_log(py, &log.log().name))?;
+    let mut records = Vec::new();
+    let mut seen = Vec::new();
+    for sample in eval_log.samples.unwrap_or_default() {
+        let id = sample.id.to_string();
+        // Use the first matching epoch for a sample
+        if seen.contains(&id) || !sample_selected(&sample, &id, args) {
+            continue;
+        }
+        let target = if args.output_as_target {
+            let output = sample.output.completion.clone();
+            if output.is_empty() {
+                log::warn!(
+                    "Skipping sample {id} in {}: no model output",
+                    log.log().log_id
+                );
+                continue;
+            }
+            Some(Value::String(output))
+        } else {
+            match sample.target.to_vec().as_slice() {
+                [] => None,
+                [s] if s.is_empty() => None,
+                [s] => Some(s.as_str().into()),
+                l => Some(l.into()),
+            }
+        };
+        records.push(DatasetRecord {
+            id: Some(Value::from(&sample.id)),
+            input: sample.input_text(),
+            target,
+            metadata: sample
+                .metadata
+                .iter()
+                .map(|(k, v)| (k.clone(), Value::from(v)))
+                .collect::<Map<_, _>>(),
+            ..Default::default()
+        });
+        seen.push(id);
+    }
+    Ok(records)
+}
+
+fn sample_selected(sample: &EvalSample, id: &str, args: &Args) -> bool {
+    if !args.samples.is_empty() && !args.samples.iter().any(|s| s == id) {
+        return false;
+    }
+    if args.correct && sample.is_correct() != Some(true) {
+        return false;
+    }
+    if args.incorrect && sample.is_correct() != Some(false) {
+        return false;
+    }
+    true
+}
@@ -1,5 +1,6 @@
 use clap::Args as ArgsTrait;
 use pyo3::Python;
+use serde::Serialize;
 use tabled::{
     builder::Builder,
     settings::{
@@ -10,7 +11,13 @@ use tabled::{
 };
 
 use crate::{
-    commands::dataset::list_datasets_dialog, py, result::Result, theme::Colors, util::TableExt,
+    commands::dataset::list_datasets_dialog,
+    inspect::dataset::list_datasets,
+    output::{FormatArgs, print_records},
+    py,
+    result::Result,
+    theme::Colors,
+    util::{TableExt, split_path_or_env},
 };
 
 #[derive(ArgsTrait, Debug)]
@@ -18,11 +25,39 @@ pub struct Args {
     /// Path to find datasets
     #[arg(short, long)]
     path: Option<String>,
+
+    #[command(flatten)]
+    format: FormatArgs,
+}
+
+/// Dataset record used for JSON and CSV output.
+#[derive(Serialize, Debug)]
+pub struct DatasetRecord {
+    /// Dataset name.
+    pub name: String,
+
+    /// Dataset description.
+    pub description: Option<String>,
+
+    /// Dataset source file.
+    pub source: String,
 }
 
 pub fn main(args: Args) -> Result<()> {
     py::init();
     Python::attach(|py| {
+        if !args.format.is_table() {
+            let path = split_path_or_env(args.path.as_deref(), "TASKPATH");
+            let records = list_datasets(py, &path)?
+                .iter()
+                .map(|dataset| DatasetRecord {
+                    name: dataset.name.clone(),
+                    description: dataset.get_description(),
+                    source: dataset.file.clone(),
+                })
+                .collect::<Vec<_>>();
+            return print_records(args.format.format(), &records);
+        }
         let datasets = list_datasets_dialog(py, args.path.as_deref())?;
         if datasets.is_empty() {
             println!("No datasets found");
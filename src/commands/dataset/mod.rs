@@ -8,8 +8,11 @@ use crate::{
     util::split_path_or_env,
 };
 
+pub mod common;
 mod create;
+mod from_log;
 mod list;
+mod show;
 
 #[derive(ArgsTrait, Debug)]
 pub struct Args {
@@ -20,17 +23,24 @@ pub struct Args {
 #[derive(Subcommand, Debug)]
 pub enum Cmd {
     /// Create a new dataset
-    #[command(hide = true)] // TODO
     Create(create::Args),
 
+    /// Create a dataset from log samples
+    FromLog(from_log::Args),
+
     /// List datasets
     List(list::Args),
+
+    /// Show dataset samples
+    Show(show::Args),
 }
 
 pub fn main(args: Args) -> Result<()> {
     match args.cmd {
         Cmd::Create(args) => create::main(args),
+        Cmd::FromLog(args) => from_log::main(args),
         Cmd::List(args) => list::main(args),
+        Cmd::Show(args) => show::main(args),
     }
 }
 
@@ -0,0 +1,375 @@
+use clap::Args as ArgsTrait;
+use cliclack as cli;
+use console::style;
+use itertools::Itertools;
+use pyo3::Python;
+use serde_json::{Map, Value, json};
+use tabled::{
+    builder::Builder,
+    settings::{
+        Color,
+        object::{Columns, Object, Rows},
+        themes::Colorization,
+    },
+};
+
+use crate::{
+    commands::dataset::list_datasets_dialog,
+    error::Error,
+    inspect::{
+        dataset::{DatasetInfo, Sample, list_datasets, load_dataset},
+        log::SampleInput,
+    },
+    output::{Format, print_record},
+    py,
+    result::Result,
+    util::{TableExt, first_line, split_path_or_env, term_height, term_width, wrap},
+};
+
+#[derive(ArgsTrait, Debug)]
+pub struct Args {
+    /// Dataset to show
+    #[arg(value_name = "DATASET")]
+    name: String,
+
+    /// Path to find datasets
+    #[arg(short, long)]
+    path: Option<String>,
+
+    /// Show a sample
+    #[arg(short, long, value_name = "ID")]
+    sample: Option<String>,
+
+    /// Show more samples
+    #[arg(short, long, action = clap::ArgAction::Count)]
+    more: u8,
+
+    /// Limit samples shown to N
+    #[arg(short, long, value_name = "N")]
+    limit: Option<usize>,
+
+    /// Show all samples
+    #[arg(short, long)]
+    all: bool,
+
+    /// Show dataset as JSON
+    #[arg(long)]
+    json: bool,
+}
+
+pub fn main(args: Args) -> Result<()> {
+    // Check incompatible options
+    if args.more > 0 && args.limit.is_some() {
+        return Err(Error::general("--more and --limit cannot both be used"));
+    }
+    if args.more > 0 && args.all {
+        return Err(Error::general("--more and --all cannot both be used"));
+    }
+    if args.all && args.limit.is_some() {
+        return Err(Error::general("--all and --limit cannot both be used"));
+    }
+
+    py::init();
+    Python::attach(|py| {
+        let datasets = if args.json {
+            list_datasets(py, &split_path_or_env(args.path.as_deref(), "TASKPATH"))?
+        } else {
+            list_datasets_dialog(py, args.path.as_deref())?
+        };
+        let dataset = datasets
+            .into_iter()
+            .find(|dataset| dataset.name == args.name)
+            .ok_or_else(|| {
+                Error::general(format!(
+                    "No dataset named '{}'\n\
+                    \n\
+                    Try 'gage dataset list' for a list of datasets.",
+                    args.name
+                ))
+            })?;
+
+        let samples = if args.json {
+            load_dataset(py, &dataset)?
+        } else {
+            let pb = cli::spinner();
+            pb.start("Loading dataset");
+            let samples = load_dataset(py, &dataset);
+            pb.clear();
+            samples?
+        };
+
+        // Single sample
+        if let Some(id) = args.sample.as_deref() {
+            let sample = samples
+                .iter()
+                .enumerate()
+                .find(|(i, sample)| sample_id(sample, *i) == id)
+                .map(|(_, sample)| sample)
+                .ok_or_else(|| {
+                    Error::general(format!(
+                        "No sample with ID '{id}' in dataset '{}'",
+                        dataset.name
+                    ))
+                })?;
+            if args.json {
+                print_record(Format::Json, &sample_json(sample))?;
+            } else {
+                print_sample(sample, id);
+            }
+            return Ok(());
+        }
+
+        if args.json {
+            return print_record(Format::Json, &dataset_json(&dataset, &samples));
+        }
+
+        print_summary(&dataset, &samples);
+
+        // Calc number of samples to show based on options
+        let count = std::cmp::min(
+            if args.all {
+                samples.len()
+            } else {
+                args.limit.unwrap_or_else(|| {
+                    let page_size = term_height().saturating_sub(16).max(5);
+                    page_size * (args.more as usize + 1)
+                })
+            },
+            samples.len(),
+        );
+        if !samples.is_empty() {
+            print_samples(&samples[..count]);
+        }
+
+        // If table truncated show what happened
+        if count < samples.len() {
+            println!(
+                "{}",
+                style(format!(
+                    "Showing {} of {} (-m for more)",
+                    count,
+                    samples.len()
+                ))
+                .dim()
+                .italic()
+            );
+        }
+        Ok(())
+    })
+}
+
+/// Returns the ID used to identify a sample.
+///
+/// Samples without an explicit ID are identified by their 1-based
+/// position, which is the ID Inspect assigns when running the dataset.
+fn sample_id(sample: &Sample, index: usize) -> String {
+    sample
+        .id
+        .as_ref()
+        .map(|id| id.to_string())
+        .unwrap_or_else(|| (index + 1).to_string())
+}
+
+fn print_summary(dataset: &DatasetInfo, samples: &[Sample]) {
+    let mut table = Builder::new();
+    table.push_record(["Dataset", &dataset.name]);
+    table.push_record(["Source", &dataset.file]);
+    table.push_record(["Samples", &samples.len().to_string()]);
+    table.push_record(["Fields", &field_summary(samples)]);
+    println!(
+        "{}",
+        table
+            .build()
+            .with_term_fit()
+            .with_row_labels()
+            .with_rounded_no_header()
+            .with(Colorization::exact(
+                [Color::FG_BRIGHT_YELLOW],
+                Columns::one(1).intersect(Rows::one(0))
+            ))
+    );
+}
+
+/// Returns a summary of sample fields and the number of samples that
+/// define each.
+fn field_summary(samples: &[Sample]) -> String {
+    let count = |f: &dyn Fn(&Sample) -> bool| samples.iter().filter(|s| f(s)).count();
+    let mut fields = vec![
+        ("id".to_string(), count(&|s| s.id.is_some())),
+        ("input".to_string(), samples.len()),
+        ("target".to_string(), count(&|s| !s.target.is_empty())),
+        (
+            "choices".to_string(),
+            count(&|s| s.choices.as_ref().is_some_and(|c| !c.is_empty())),
+        ),
+    ];
+    let metadata_keys = samples
+        .iter()
+        .filter_map(|s| s.metadata.as_ref())
+        .flat_map(|m| m.keys())
+        .unique()
+        .sorted()
+        .collect_vec();
+    for key in metadata_keys {
+        fields.push((
+            format!("metadata.{key}"),
+            count(&|s| s.metadata.as_ref().is_some_and(|m| m.contains_key(key))),
+        ));
+    }
+    let chat_inputs = count(&|s| matches!(s.input, SampleInput::ChatMessageList(_)));
+    fields
+        .into_iter()
+        .filter(|(_, n)| *n > 0)
+        .map(|(name, n)| {
+            if name == "input" && chat_inputs > 0 {
+                format!("{name} ({n}, {chat_inputs} chat)")
+            } else {
+                format!("{name} ({n})")
+            }
+        })
+        .join("\n")
+}
+
+fn print_samples(samples: &[Sample]) {
+    let has_choices = samples
+        .iter()
+        .any(|s| s.choices.as_ref().is_some_and(|c| !c.is_empty()));
+    let has_metadata = samples
+        .iter()
+        .any(|s| s.metadata.as_ref().is_some_and(|m| !m.is_empty()));
+
+    let mut header = vec!["Id", "Input", "Target"];
+    if has_choices {
+        header.push("Choices");
+    }
+    if has_metadata {
+        header.push("Metadata");
+    }
+    let mut table = Builder::new();
+    table.push_record(header);
+    for (i, sample) in samples.iter().enumerate() {
+        let mut row = vec![
+            sample_id(sample, i),
+            cell_line(&sample.input.text()),
+            cell_line(&sample.target.to_vec().join(", ")),
+        ];
+        if has_choices {
+            row.push(cell_line(
+                &sample.choices.as_deref().unwrap_or_default().join(", "),
+            ));
+        }
+        if has_metadata {
+            row.push(cell_line(&metadata_str(sample, ", ")));
+        }
+        table.push_record(row);
+    }
+    println!(
+        "{}",
+        table
+            .build()
+            .with_term_fit()
+            .with_rounded()
+            .with_col_labels()
+            .with(Colorization::exact(
+                [Color::FG_BRIGHT_CYAN],
+                Columns::one(0).intersect(Rows::new(1..))
+            ))
+    );
+}
+
+/// Returns the first line of a value, using '…' to indicate truncated
+/// lines.
+fn cell_line(s: &str) -> String {
+    match first_line(s) {
+        (line, true) => format!("{line} …"),
+        (line, false) => line.into(),
+    }
+}
+
+fn metadata_str(sample: &Sample, sep: &str) -> String {
+    sample
+        .metadata
+        .as_ref()
+        .map(|m| {
+            m.iter()
+                .sorted_by_key(|(k, _)| *k)
+                .map(|(k, v)| format!("{k}={v}"))
+                .join(sep)
+        })
+        .unwrap_or_default()
+}
+
+fn print_sample(sample: &Sample, id: &str) {
+    let width = term_width().saturating_sub(16);
+    let mut table = Builder::new();
+    table.push_record(["Id", id]);
+    table.push_record(["Input", &wrap(&sample.input.text(), width)]);
+    table.push_record(["Target", &wrap(&sample.target.to_vec().join("\n"), width)]);
+    if let Some(choices) = sample.choices.as_ref()
+        && !choices.is_empty()
+    {
+        table.push_record(["Choices", &wrap(&choices.join("\n"), width)]);
+    }
+    if sample.metadata.as_ref().is_some_and(|m| !m.is_empty()) {
+        table.push_record(["Metadata", &metadata_str(sample, "\n")]);
+    }
+    println!(
+        "{}",
+        table
+            .build()
+            .with_term_fit()
+            .with_row_labels()
+            .with_rounded_no_header()
+            .with(Colorization::exact(
+                [Color::FG_BRIGHT_CYAN],
+                Columns::one(1).intersect(Rows::one(0))
+            ))
+    );
+}
+
+fn dataset_json(dataset: &DatasetInfo, samples: &[Sample]) -> Value {
+    json!({
+        "name": dataset.name,
+        "source": dataset.file,
+        "count": samples.len(),
+        "samples": samples.iter().map(sample_json).collect_vec(),
+    })
+}
+
+fn sample_json(sample: &Sample) -> Value {
+    let mut val = Map::new();
+    if let Some(id) = sample.id.as_ref() {
+        val.insert("id".into(), id.into());
+    }
+    val.insert(
+        "input".into(),
+        match &sample.input {
+            SampleInput::String(s) => s.as_str().into(),
+            SampleInput::ChatMessageList(l) => l
+                .iter()
+                .map(|msg| json!({"role": msg.role(), "content": msg.base().content.text()}))
+                .collect(),
+        },
+    );
+    if let Some(choices) = sample.choices.as_ref() {
+        val.insert("choices".into(), choices.clone().into());
+    }
+    val.insert(
+        "target".into(),
+        match sample.target.to_vec().as_slice() {
+            [s] => s.as_str().into(),
+            l => l.into(),
+        },
+    );
+    if let Some(metadata) = sample.metadata.as_ref() {
+        val.insert(
+            "metadata".into(),
+            metadata
+                .iter()
+                .map(|(k, v)| (k.clone(), Value::from(v)))
+                .collect::<Map<_, _>>()
+                .into(),
+        );
+    }
+    val.into()
+}
@@ -0,0 +1,248 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{config::Config, error::Error, py::EpochMillis, result::Result};
+
+/// State of a running endpoint.
+///
+/// Endpoints write their state to `.gage/endpoints/NAME.json` in the
+/// project directory when they start and remove it when they stop.
+/// Output for detached endpoints is written to `NAME.log` in the same
+/// directory.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct EndpointState {
+    /// Endpoint name.
+    pub name: String,
+
+    /// Endpoint process ID.
+    pub pid: u32,
+
+    /// Address the endpoint listens on.
+    pub host: String,
+
+    /// Port the endpoint listens on.
+    pub port: u16,
+
+    /// Names of tasks served by the endpoint.
+    pub tasks: Vec<String>,
+
+    /// Time the endpoint started (RFC 3339).
+    pub started: String,
+
+    /// Number of requests handled by the endpoint.
+    pub requests: usize,
+
+    /// Start time of the endpoint process as reported by the OS.
+    ///
+    /// Used to check that `pid` is still the endpoint process, as PIDs
+    /// are reused after a process exits. None if not supported on the
+    /// platform.
+    #[serde(default)]
+    pub process_start: Option<u64>,
+}
+
+impl EndpointState {
+    pub fn new(name: &str, host: &str, port: u16, tasks: Vec<String>) -> Self {
+        Self {
+            name: name.into(),
+            pid: std::process::id(),
+            host: host.into(),
+            port,
+            tasks,
+            started: Utc::now().to_rfc3339(),
+            requests: 0,
+            process_start: process_start_time(std::process::id()),
+        }
+    }
+
+    pub fn url(&self) -> String {
+        format!("http://{}:{}", self.host, self.port)
+    }
+
+    pub fn uptime(&self) -> String {
+        DateTime::parse_from_rfc3339(&self.started)
+            .ok()
+            .map(|started| {
+                EpochMillis::from_epoch_millis(started.timestamp_millis())
+                    .to_human_since(&Utc::now())
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns true if the endpoint process is running.
+    ///
+    /// A process with the endpoint PID is only the endpoint if it
+    /// started when the endpoint did. If start times aren't available,
+    /// the endpoint port is probed instead.
+    pub fn is_running(&self) -> bool {
+        if !process_exists(self.pid) {
+            return false;
+        }
+        match (self.process_start, process_start_time(self.pid)) {
+            (Some(recorded), Some(current)) => recorded == current,
+            _ => self.probe(),
+        }
+    }
+
+    /// Returns true if the endpoint responds on its port.
+    fn probe(&self) -> bool {
+        ureq::AgentBuilder::new()
+            .timeout(PROBE_TIMEOUT)
+            .build()
+            .get(&format!("{}/tasks", self.url()))
+            .call()
+            .is_ok()
+    }
+
+    pub fn matches(&self, spec: &str) -> bool {
+        self.name == spec || self.port.to_string() == spec
+    }
+
+    pub fn write(&self, dir: &Path) -> Result<()> {
+        fs::create_dir_all(dir)?;
+        let path = state_path(dir, &self.name);
+        let tmp = path.with_extension("json.tmp");
+        fs::write(
+            &tmp,
+            serde_json::to_string_pretty(self).map_err(|e| Error::general(e.to_string()))?,
+        )?;
+        // Rename to avoid readers seeing a partially written file
+        fs::rename(tmp, path)?;
+        Ok(())
+    }
+
+    pub fn remove(&self, dir: &Path) -> Result<()> {
+        match fs::remove_file(state_path(dir, &self.name)) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+/// Time to wait for an endpoint to respond when checking that it's
+/// running.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Returns the directory used to store endpoint state.
+pub fn endpoints_dir(config: &Config) -> PathBuf {
+    let project_dir = config
+        .path
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .map(PathBuf::from)
+        .unwrap_or_else(|| std::env::current_dir().unwrap());
+    project_dir.join(".gage").join("endpoints")
+}
+
+pub fn state_path(dir: &Path, name: &str) -> PathBuf {
+    dir.join(format!("{name}.json"))
+}
+
+pub fn output_path(dir: &Path, name: &str) -> PathBuf {
+    dir.join(format!("{name}.log"))
+}
+
+pub fn read_state(path: &Path) -> Result<EndpointState> {
+    let contents = fs::read_to_string(path)?;
+    serde_json::from_str(&contents).map_err(|e| {
+        Error::general(format!(
+            "Error reading endpoint state {}: {e}",
+            path.to_string_lossy()
+        ))
+    })
+}
+
+/// Returns endpoint states sorted by name.
+///
+/// State files that can't be read are logged and skipped.
+pub fn list_endpoints(dir: &Path) -> Result<Vec<EndpointState>> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    let mut endpoints = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+            continue;
+        }
+        match read_state(&path) {
+            Ok(state) => endpoints.push(state),
+            Err(e) => log::warn!("{e}"),
+        }
+    }
+    endpoints.sort_by(|lhs, rhs| lhs.name.cmp(&rhs.name));
+    Ok(endpoints)
+}
+
+pub fn process_exists(pid: u32) -> bool {
+    // Signal 0 checks for process existence without sending a signal
+    let ret = unsafe { libc::kill(pid as libc::pid_t, 0) };
+    ret == 0 || io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+}
+
+/// Returns the start time of a process.
+///
+/// The value is only used for comparison - units are platform
+/// specific (clock ticks since boot on Linux, seconds since the epoch
+/// on macOS).
+#[cfg(target_os = "linux")]
+pub fn process_start_time(pid: u32) -> Option<u64> {
+    let stat = fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+    // Command name may contain spaces - fields follow the last ')'.
+    // Start time is field 22, the 20th field after the command name.
+    let (_, fields) = stat.rsplit_once(')')?;
+    fields.split_whitespace().nth(19)?.parse().ok()
+}
+
+#[cfg(target_os = "macos")]
+pub fn process_start_time(pid: u32) -> Option<u64> {
+    let mut info = std::mem::MaybeUninit::<libc::proc_bsdinfo>::zeroed();
+    let size = std::mem::size_of::<libc::proc_bsdinfo>() as libc::c_int;
+    let ret = unsafe {
+        libc::proc_pidinfo(
+            pid as libc::c_int,
+            libc::PROC_PIDTBSDINFO,
+            0,
+            info.as_mut_ptr() as *mut libc::c_void,
+            size,
+        )
+    };
+    if ret != size {
+        return None;
+    }
+    Some(unsafe { info.assume_init() }.pbi_start_tvsec)
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+pub fn process_start_time(_pid: u32) -> Option<u64> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EndpointState;
+
+    #[test]
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    fn test_is_running() {
+        // Port 1 isn't an endpoint so the probe fallback fails
+        let mut state = EndpointState::new("test", "127.0.0.1", 1, Vec::new());
+        assert!(state.is_running());
+
+        // Same PID with a different start time is another process
+        state.process_start = state.process_start.map(|start| start + 1);
+        assert!(!state.is_running());
+
+        state.pid = u32::MAX >> 2;
+        assert!(!state.is_running());
+    }
+}
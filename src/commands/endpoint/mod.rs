@@ -1,10 +1,11 @@
+mod common;
 pub mod start;
 mod status;
 mod stop;
 
 use clap::{Args as ArgsTrait, Subcommand};
 
-use crate::result::Result;
+use crate::{config::Config, result::Result};
 
 #[derive(ArgsTrait, Debug)]
 pub struct Args {
@@ -24,10 +25,10 @@ pub enum Cmd {
     Stop(stop::Args),
 }
 
-pub fn main(args: Args) -> Result<()> {
+pub fn main(args: Args, config: &Config) -> Result<()> {
     match args.cmd {
-        Cmd::Start(args) => start::main(args),
-        Cmd::Status(args) => status::main(args),
-        Cmd::Stop(args) => stop::main(args),
+        Cmd::Start(args) => start::main(args, config),
+        Cmd::Status(args) => status::main(args, config),
+        Cmd::Stop(args) => stop::main(args, config),
     }
 }
@@ -1,11 +1,477 @@
+use std::{
+    collections::HashMap,
+    env,
+    fs::{self, File},
+    os::unix::process::CommandExt,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    sync::atomic::{AtomicBool, Ordering},
+    thread,
+    time::{Duration, Instant},
+};
+
 use clap::Args as ArgsTrait;
+use cliclack as cli;
+use console::style;
+use pyo3::Python;
+use serde::{Deserialize, Serialize};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use crate::{
+    commands::{
+        endpoint::common::{EndpointState, endpoints_dir, output_path, read_state, state_path},
+        task::list_tasks,
+    },
+    config::Config,
+    error::Error,
+    inspect::{
+        log::{EvalStatus, resolve_log_dir},
+        scorer::Value,
+        task::{TaskInfo, TaskResult, run_task},
+    },
+    plural, py,
+    result::Result,
+    util::PathExt,
+};
 
-use crate::result::Result;
+/// Time to wait for a detached endpoint to start serving.
+const START_TIMEOUT: Duration = Duration::from_secs(60);
 
 #[derive(ArgsTrait, Debug)]
-pub struct Args {}
+pub struct Args {
+    /// Tasks to serve (defaults to all available tasks)
+    #[arg(name = "TASK")]
+    tasks: Vec<String>,
+
+    /// Endpoint name
+    #[arg(short, long, default_value = "default")]
+    name: String,
+
+    /// Address to listen on
+    #[arg(long, default_value = "127.0.0.1")]
+    host: String,
+
+    /// Port to listen on
+    #[arg(long, default_value_t = 8000)]
+    port: u16,
+
+    /// Default task model
+    ///
+    /// Used when a request doesn't specify a model. If not specified,
+    /// environent variables GAGE_MODEL and INSPECT_EVAL_MODEL are used.
+    #[arg(short, long)]
+    model: Option<String>,
+
+    /// Path to find tasks
+    #[arg(short, long)]
+    path: Option<String>,
+
+    /// Inspect log dir
+    #[arg(long)]
+    log_dir: Option<PathBuf>,
+
+    /// Run the endpoint in the foreground
+    #[arg(long)]
+    foreground: bool,
+}
+
+pub fn main(args: Args, config: &Config) -> Result<()> {
+    let state_dir = endpoints_dir(config);
+    check_not_running(&state_dir, &args.name)?;
+    if args.foreground {
+        serve(args, &state_dir)
+    } else {
+        start_detached(&args, &state_dir)
+    }
+}
+
+/// Entry point for `serve`, which always runs in the foreground.
+pub fn serve_main(args: Args, config: &Config) -> Result<()> {
+    main(
+        Args {
+            foreground: true,
+            ..args
+        },
+        config,
+    )
+}
+
+fn check_not_running(state_dir: &Path, name: &str) -> Result<()> {
+    let path = state_path(state_dir, name);
+    if !path.exists() {
+        return Ok(());
+    }
+    let state = read_state(&path)?;
+    if state.is_running() {
+        return Err(Error::general(format!(
+            "Endpoint '{name}' is already running at {} (pid {})\n\
+            \n\
+            Use --name to start another endpoint or 'gage endpoint stop {name}' \
+            to stop it.",
+            state.url(),
+            state.pid
+        )));
+    }
+    log::debug!("Removing stale endpoint state {path:?}");
+    state.remove(state_dir)
+}
+
+fn start_detached(args: &Args, state_dir: &Path) -> Result<()> {
+    fs::create_dir_all(state_dir)?;
+    let output_path = output_path(state_dir, &args.name);
+    let output = File::create(&output_path)?;
+
+    // Re-run the current command in the foreground as a new process
+    // group so it isn't affected by signals sent to this terminal
+    let mut child = Command::new(env::current_exe()?)
+        .args(env::args_os().skip(1))
+        .arg("--foreground")
+        .stdin(Stdio::null())
+        .stdout(output.try_clone()?)
+        .stderr(output)
+        .process_group(0)
+        .spawn()?;
+
+    let pb = cli::spinner();
+    pb.start(format!("Starting endpoint '{}'", args.name));
+    let state_path = state_path(state_dir, &args.name);
+    let start = Instant::now();
+    let state = loop {
+        if let Some(status) = child.try_wait()? {
+            pb.clear();
+            let output = fs::read_to_string(&output_path).unwrap_or_default();
+            return Err(Error::general(format!(
+                "Endpoint failed to start ({status})\n\n{}",
+                output.trim_end()
+            )));
+        }
+        if let Ok(state) = read_state(&state_path)
+            && state.pid == child.id()
+        {
+            break state;
+        }
+        if start.elapsed() > START_TIMEOUT {
+            pb.clear();
+            let _ = child.kill();
+            return Err(Error::general(format!(
+                "Timed out waiting for endpoint to start\n\n\
+                See {} for details.",
+                output_path.to_string_lossy()
+            )));
+        }
+        thread::sleep(Duration::from_millis(100));
+    };
+    pb.clear();
+
+    println!(
+        "Started endpoint '{}' at {} (pid {})",
+        state.name,
+        style(state.url()).cyan().bright(),
+        state.pid
+    );
+    println!(
+        "{}",
+        style(format!(
+            "Use 'gage endpoint status' to show status or 'gage endpoint stop {}' to stop it.",
+            state.name
+        ))
+        .dim()
+    );
+    Ok(())
+}
+
+static STOP_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+extern "C" fn on_stop_signal(_: libc::c_int) {
+    STOP_REQUESTED.store(true, Ordering::SeqCst);
+}
+
+fn handle_stop_signals() {
+    let handler = on_stop_signal as extern "C" fn(libc::c_int) as libc::sighandler_t;
+    unsafe {
+        libc::signal(libc::SIGTERM, handler);
+        libc::signal(libc::SIGINT, handler);
+    }
+}
+
+fn serve(args: Args, state_dir: &Path) -> Result<()> {
+    py::init();
+    let tasks = Python::attach(|py| list_tasks(py, args.path.as_deref()))?;
+    let tasks = filter_tasks(tasks, &args.tasks)?;
+
+    let addr = format!("{}:{}", args.host, args.port);
+    let server = Server::http(&addr)
+        .map_err(|e| Error::general(format!("Cannot start endpoint on {addr}: {e}")))?;
+    let port = server
+        .server_addr()
+        .to_ip()
+        .map(|addr| addr.port())
+        .unwrap_or(args.port);
+
+    let endpoint = Endpoint {
+        tasks,
+        default_model: args
+            .model
+            .or_else(|| env::var("GAGE_MODEL").ok())
+            .or_else(|| env::var("INSPECT_EVAL_MODEL").ok()),
+        log_dir: resolve_log_dir(args.log_dir.as_ref()).expect_string(),
+    };
+
+    let mut state = EndpointState::new(
+        &args.name,
+        &args.host,
+        port,
+        endpoint.tasks.iter().map(|task| task.name.clone()).collect(),
+    );
+    state.write(state_dir)?;
+    handle_stop_signals();
+    print_endpoint_summary(&endpoint, &state.url());
+
+    while !STOP_REQUESTED.load(Ordering::SeqCst) {
+        match server.recv_timeout(Duration::from_millis(250)) {
+            Ok(Some(request)) => {
+                endpoint.handle(request);
+                state.requests += 1;
+                if let Err(e) = state.write(state_dir) {
+                    log::warn!("Error writing endpoint state: {e}");
+                }
+            }
+            Ok(None) => {}
+            Err(e) => {
+                log::error!("Error receiving request: {e}");
+                break;
+            }
+        }
+    }
 
-pub fn main(args: Args) -> Result<()> {
-    println!("TODO: serve tasks {args:?}");
+    state.remove(state_dir)?;
+    println!("Endpoint '{}' stopped", state.name);
     Ok(())
 }
+
+fn filter_tasks(tasks: Vec<TaskInfo>, tasks_arg: &[String]) -> Result<Vec<TaskInfo>> {
+    if tasks.is_empty() {
+        return Err(Error::no_tasks());
+    }
+    if tasks_arg.is_empty() {
+        return Ok(tasks);
+    }
+    let matching = tasks
+        .into_iter()
+        .filter(|task| tasks_arg.contains(&task.name))
+        .collect::<Vec<_>>();
+    if matching.is_empty() {
+        return Err(Error::no_matching_tasks(tasks_arg));
+    }
+    Ok(matching)
+}
+
+fn print_endpoint_summary(endpoint: &Endpoint, url: &str) {
+    println!(
+        "Serving {} {} at {}",
+        endpoint.tasks.len(),
+        plural!("task", endpoint.tasks.len()),
+        style(url).cyan().bright()
+    );
+    println!();
+    println!("  {}", style("GET  /tasks").dim());
+    for task in &endpoint.tasks {
+        println!(
+            "  {} {}",
+            style("POST").dim(),
+            style(format!("/tasks/{}", task.name)).yellow()
+        );
+    }
+    println!();
+    println!("{}", style("Press Ctrl-C to stop").dim().italic());
+    println!();
+}
+
+struct Endpoint {
+    tasks: Vec<TaskInfo>,
+    default_model: Option<String>,
+    log_dir: String,
+}
+
+/// Request body for running a task.
+#[derive(Deserialize, Debug)]
+struct RunRequest {
+    /// Task input.
+    input: String,
+
+    /// Model used for the run (defaults to endpoint model).
+    model: Option<String>,
+
+    /// Expected output - when specified the output is scored.
+    target: Option<String>,
+
+    /// Task arguments.
+    #[serde(default)]
+    task_args: HashMap<String, serde_json::Value>,
+
+    /// Tags applied to the run log.
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct TaskListing<'a> {
+    name: &'a str,
+    description: Option<String>,
+    file: &'a str,
+    url: String,
+}
+
+#[derive(Serialize)]
+struct RunResponse<'a> {
+    task: &'a str,
+    status: String,
+    output: Option<&'a str>,
+    scores: HashMap<&'a str, ScoreResponse<'a>>,
+    error: Option<&'a str>,
+    log: &'a str,
+}
+
+#[derive(Serialize)]
+struct ScoreResponse<'a> {
+    value: &'a Value,
+    answer: Option<&'a str>,
+    explanation: Option<&'a str>,
+}
+
+#[derive(Serialize)]
+struct ErrorResponse {
+    error: String,
+}
+
+type JsonResponse = (u16, String);
+
+impl Endpoint {
+    fn handle(&self, mut request: Request) {
+        let method = request.method().clone();
+        let url = request.url().to_string();
+        // Route on the path only - query params aren't used
+        let path = url.split('?').next().unwrap_or_default();
+        let (status, body) = match (&method, path.trim_end_matches('/')) {
+            (Method::Get, "" | "/tasks") => self.list_tasks(),
+            (Method::Post, path) if path.starts_with("/tasks/") => {
+                let name = &path["/tasks/".len()..];
+                let mut body = String::new();
+                match request.as_reader().read_to_string(&mut body) {
+                    Ok(_) => self.run_task(name, &body),
+                    Err(e) => error_response(400, format!("Error reading request: {e}")),
+                }
+            }
+            (_, path) if path == "/tasks" || path.starts_with("/tasks/") => {
+                error_response(405, format!("Method {method} not allowed for {url}"))
+            }
+            _ => error_response(404, format!("Not found: {url}")),
+        };
+        log::info!("{method} {url} {status}");
+        let response = Response::from_string(body)
+            .with_status_code(status)
+            .with_header(
+                Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..])
+                    .expect("valid header"),
+            );
+        if let Err(e) = request.respond(response) {
+            log::error!("Error sending response for {url}: {e}");
+        }
+    }
+
+    fn list_tasks(&self) -> JsonResponse {
+        let listing = self
+            .tasks
+            .iter()
+            .map(|task| TaskListing {
+                name: &task.name,
+                description: task.get_description(),
+                file: &task.file,
+                url: format!("/tasks/{}", task.name),
+            })
+            .collect::<Vec<_>>();
+        (200, to_json(&listing))
+    }
+
+    fn run_task(&self, name: &str, body: &str) -> JsonResponse {
+        let Some(task) = self.tasks.iter().find(|task| task.name == name) else {
+            return error_response(404, format!("Cannot find task '{name}'"));
+        };
+        let request: RunRequest = match serde_json::from_str(body) {
+            Ok(request) => request,
+            Err(e) => return error_response(400, format!("Invalid request: {e}")),
+        };
+        let task_args = request
+            .task_args
+            .iter()
+            .map(|(name, val)| match val {
+                serde_json::Value::String(s) => format!("{name}={s}"),
+                other => format!("{name}={other}"),
+            })
+            .collect::<Vec<_>>();
+        let result = Python::attach(|py| {
+            run_task(
+                py,
+                task.get_full_name(),
+                request.input,
+                task_args,
+                request.model.or_else(|| self.default_model.clone()),
+                request.target,
+                Some(self.log_dir.clone()),
+                request.tags,
+            )
+        });
+        match result {
+            Ok(result) => run_response(&task.name, &result),
+            Err(Error::Py(e)) => error_response(500, e.to_string()),
+            Err(e) => error_response(400, e.to_string()),
+        }
+    }
+}
+
+fn run_response(task: &str, result: &TaskResult) -> JsonResponse {
+    let log = result.log();
+    let (output, scores) = match log.status {
+        EvalStatus::Success => {
+            let sample = result.sample();
+            let scores = sample
+                .scores
+                .iter()
+                .flatten()
+                .map(|(name, score)| {
+                    (
+                        name.as_str(),
+                        ScoreResponse {
+                            value: &score.value,
+                            answer: score.answer.as_deref(),
+                            explanation: score.explanation.as_deref(),
+                        },
+                    )
+                })
+                .collect();
+            (Some(sample.output.completion.as_str()), scores)
+        }
+        _ => (None, HashMap::new()),
+    };
+    let response = RunResponse {
+        task,
+        status: log.status.to_string(),
+        output,
+        scores,
+        error: log.error.as_ref().map(|e| e.message.as_str()),
+        log: &log.location,
+    };
+    let status = match log.status {
+        EvalStatus::Success => 200,
+        _ => 500,
+    };
+    (status, to_json(&response))
+}
+
+fn error_response(status: u16, error: String) -> JsonResponse {
+    (status, to_json(&ErrorResponse { error }))
+}
+
+fn to_json<T: Serialize>(val: &T) -> String {
+    serde_json::to_string(val).expect("serializable response")
+}
@@ -1,11 +1,67 @@
 use clap::Args as ArgsTrait;
+use tabled::{
+    builder::Builder,
+    settings::{
+        Color,
+        object::{Columns, Object, Rows},
+        themes::Colorization,
+    },
+};
 
-use crate::result::Result;
+use crate::{
+    commands::endpoint::common::{endpoints_dir, list_endpoints},
+    config::Config,
+    result::Result,
+    theme::Colors,
+    util::TableExt,
+};
 
 #[derive(ArgsTrait, Debug)]
 pub struct Args {}
 
-pub fn main(args: Args) -> Result<()> {
-    println!("TODO: show endpoint status {args:?}");
+pub fn main(_args: Args, config: &Config) -> Result<()> {
+    let state_dir = endpoints_dir(config);
+    let mut table = Builder::default();
+    table.push_record(["Name", "URL", "Pid", "Tasks", "Requests", "Uptime"]);
+    for state in list_endpoints(&state_dir)? {
+        if !state.is_running() {
+            // Process is gone - state is stale
+            log::debug!("Removing stale state for endpoint '{}'", state.name);
+            state.remove(&state_dir)?;
+            continue;
+        }
+        table.push_record([
+            state.name.clone(),
+            state.url(),
+            state.pid.to_string(),
+            state.tasks.join(", "),
+            state.requests.to_string(),
+            state.uptime(),
+        ]);
+    }
+    if table.count_records() == 1 {
+        println!("No endpoints running");
+        return Ok(());
+    }
+    println!(
+        "{}",
+        table
+            .build()
+            .with_rounded()
+            .with_col_labels()
+            .with_term_fit()
+            .with(Colorization::exact(
+                [Color::FG_BRIGHT_YELLOW],
+                Columns::one(0).intersect(Rows::new(1..))
+            ))
+            .with(Colorization::exact(
+                [Color::FG_BRIGHT_CYAN],
+                Columns::one(1).intersect(Rows::new(1..))
+            ))
+            .with(Colorization::exact(
+                [Colors::dim()],
+                Columns::one(2).intersect(Rows::new(1..))
+            ))
+    );
     Ok(())
 }
@@ -1,11 +1,96 @@
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
 use clap::Args as ArgsTrait;
 
-use crate::result::Result;
+use crate::{
+    commands::endpoint::common::{
+        EndpointState, endpoints_dir, list_endpoints, process_exists,
+    },
+    config::Config,
+    error::Error,
+    result::Result,
+};
+
+/// Time to wait for an endpoint to stop before killing it.
+const STOP_TIMEOUT: Duration = Duration::from_secs(10);
 
 #[derive(ArgsTrait, Debug)]
-pub struct Args {}
+pub struct Args {
+    /// Endpoints to stop
+    ///
+    /// ENDPOINT may be specified using its name or port.
+    #[arg(value_name = "ENDPOINT")]
+    specs: Vec<String>,
+
+    /// Stop all endpoints
+    #[arg(short, long)]
+    all: bool,
+}
+
+pub fn main(args: Args, config: &Config) -> Result<()> {
+    if args.specs.is_empty() && !args.all {
+        return Err(Error::general("Specify an endpoint name or --all"));
+    }
+    let state_dir = endpoints_dir(config);
+    let endpoints = list_endpoints(&state_dir)?;
+
+    // Resolve specs before stopping anything
+    for spec in &args.specs {
+        if !endpoints.iter().any(|state| state.matches(spec)) {
+            return Err(Error::general(format!(
+                "No endpoint matching '{spec}'\n\
+                \n\
+                Try 'gage endpoint status' for a list of endpoints."
+            )));
+        }
+    }
+    let selected = endpoints
+        .into_iter()
+        .filter(|state| args.all || args.specs.iter().any(|spec| state.matches(spec)))
+        .collect::<Vec<_>>();
+    if selected.is_empty() {
+        println!("No endpoints running");
+        return Ok(());
+    }
+
+    for state in selected {
+        if state.is_running() {
+            stop_process(&state)?;
+            println!("Stopped endpoint '{}' (port {})", state.name, state.port);
+        } else {
+            println!("Endpoint '{}' is not running", state.name);
+        }
+        // Endpoints remove their state on a clean exit - ensure it's
+        // removed if the process was killed or exited unexpectedly
+        state.remove(&state_dir)?;
+    }
+    Ok(())
+}
 
-pub fn main(args: Args) -> Result<()> {
-    println!("TODO: stop endpoint {args:?}");
+fn stop_process(state: &EndpointState) -> Result<()> {
+    let pid = state.pid as libc::pid_t;
+    if unsafe { libc::kill(pid, libc::SIGTERM) } != 0 {
+        return Err(Error::general(format!(
+            "Error stopping endpoint '{}': {}",
+            state.name,
+            std::io::Error::last_os_error()
+        )));
+    }
+    let start = Instant::now();
+    while process_exists(state.pid) {
+        if start.elapsed() > STOP_TIMEOUT {
+            log::warn!(
+                "Endpoint '{}' did not stop after {}s - killing it",
+                state.name,
+                STOP_TIMEOUT.as_secs()
+            );
+            unsafe { libc::kill(pid, libc::SIGKILL) };
+            break;
+        }
+        thread::sleep(Duration::from_millis(100));
+    }
     Ok(())
 }
@@ -1,6 +1,299 @@
-use crate::result::Result;
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
 
-pub fn main() -> Result<()> {
-    println!("TODO init a project");
+use clap::Args as ArgsTrait;
+use cliclack as cli;
+use console::style;
+
+use crate::{
+    commands::profile::use_::write_dotenv_profile,
+    dialog::{DialogResult, handle_dialog_result},
+    error::Error,
+    result::Result,
+};
+
+/// Model used when a model isn't otherwise specified.
+///
+/// Inspect's mock model doesn't need credentials, which lets a new
+/// project run its sample task right away.
+const DEFAULT_MODEL: &str = "mockllm/model";
+
+const SAMPLE_TASK_FILE: &str = "hello.py";
+
+const SAMPLE_DATASET_FILE: &str = "hello.jsonl";
+
+const SAMPLE_TASK: &str = r#""""Sample Gage task.
+
+Run this task with 'gage run hello' or evaluate it with 'gage eval hello'.
+"""
+
+from inspect_ai import Task, task
+from inspect_ai.dataset import json_dataset
+from inspect_ai.scorer import includes
+from inspect_ai.solver import generate, prompt_template
+
+
+@task
+def hello():
+    """Greet someone by name.
+
+    Args:
+        name: Name of the person to greet
+
+    Returns:
+        A greeting that includes the person's name
+    """
+    return Task(
+        dataset=json_dataset("hello.jsonl"),
+        solver=[
+            prompt_template("Write a short, friendly greeting for {prompt}."),
+            generate(),
+        ],
+        scorer=includes(),
+    )
+"#;
+
+const SAMPLE_DATASET: &str = r#"{"id": 1, "input": "Alice", "target": "Alice"}
+{"id": 2, "input": "Bob", "target": "Bob"}
+{"id": 3, "input": "Carol", "target": "Carol"}
+"#;
+
+#[derive(ArgsTrait, Debug)]
+pub struct Args {
+    /// Project directory (defaults to current directory)
+    #[arg(value_name = "DIR")]
+    dir: Option<PathBuf>,
+
+    /// Name of the starter profile
+    #[arg(long, value_name = "NAME")]
+    profile: Option<String>,
+
+    /// Default model for the starter profile
+    #[arg(short, long)]
+    model: Option<String>,
+
+    /// Log directory for the starter profile
+    #[arg(long, value_name = "DIR")]
+    log_dir: Option<String>,
+
+    /// Don't create a sample task and dataset
+    #[arg(long)]
+    no_sample: bool,
+
+    /// Don't prompt for input (use defaults)
+    #[arg(short, long)]
+    yes: bool,
+}
+
+pub fn main(args: Args) -> Result<()> {
+    handle_dialog_result(init_dialog(args))
+}
+
+struct InitPlan {
+    dir: PathBuf,
+    profile: String,
+    model: String,
+    log_dir: String,
+    sample: bool,
+}
+
+fn init_dialog(args: Args) -> Result<DialogResult> {
+    cli::intro(style("Initialize project").bold())?;
+
+    let dir = args.dir.clone().unwrap_or_else(|| PathBuf::from("."));
+    let config_path = dir.join("gage.toml");
+    if config_path.exists() {
+        return Err(Error::general(format!(
+            "{} already exists\n\
+            \n\
+            Use 'gage profile list' to show the profiles it defines.",
+            config_path
+                .strip_prefix(".")
+                .unwrap_or(&config_path)
+                .to_string_lossy()
+        )));
+    }
+
+    let yes = args.yes;
+    let plan = if yes {
+        plan_from_args(dir, args)
+    } else {
+        prompt_plan(dir, args)?
+    };
+
+    // Confirm before writing unless --yes
+    let files = plan_files(&plan);
+    cli::log::step(format!(
+        "Files:\n{}",
+        files
+            .iter()
+            .map(|(path, note)| {
+                let path = path.strip_prefix(".").unwrap_or(path).to_string_lossy();
+                match note {
+                    Some(note) => style(format!("{path} ({note})")).dim().italic(),
+                    None => style(path.to_string()).dim(),
+                }
+                .to_string()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    ))?;
+    if !yes
+        && !cli::confirm("Create project files?")
+            .initial_value(true)
+            .interact()?
+    {
+        return Err(Error::Canceled);
+    }
+
+    write_project(&plan)?;
+
+    cli::note(
+        "Next steps",
+        if plan.sample {
+            format!(
+                "{}\n{}\n{}",
+                style("gage run hello").cyan(),
+                style("gage eval hello").cyan(),
+                style("gage review").cyan(),
+            )
+        } else {
+            format!(
+                "{}\n{}",
+                style("gage task list").cyan(),
+                style("gage profile status").cyan()
+            )
+        },
+    )?;
+    Ok(DialogResult::Message("Project initialized".into()))
+}
+
+fn plan_from_args(dir: PathBuf, args: Args) -> InitPlan {
+    InitPlan {
+        dir,
+        profile: args.profile.unwrap_or_else(|| "dev".into()),
+        model: args.model.unwrap_or_else(default_model),
+        log_dir: args.log_dir.unwrap_or_else(|| "logs".into()),
+        sample: !args.no_sample,
+    }
+}
+
+fn prompt_plan(dir: PathBuf, args: Args) -> Result<InitPlan> {
+    let profile = match args.profile {
+        Some(profile) => {
+            cli::log::step(format!("Profile:\n{}", style(&profile).dim()))?;
+            profile
+        }
+        None => cli::Input::new("Profile:")
+            .default_input("dev")
+            .validate(|val: &String| {
+                if val.trim().is_empty() || val.contains(char::is_whitespace) {
+                    Err("Profile name cannot be empty or contain spaces")
+                } else {
+                    Ok(())
+                }
+            })
+            .interact()?,
+    };
+    let model = match args.model {
+        Some(model) => {
+            cli::log::step(format!("Model:\n{}", style(&model).dim()))?;
+            model
+        }
+        None => cli::Input::new("Model:")
+            .default_input(&default_model())
+            .interact()?,
+    };
+    let log_dir = match args.log_dir {
+        Some(log_dir) => {
+            cli::log::step(format!("Log dir:\n{}", style(&log_dir).dim()))?;
+            log_dir
+        }
+        None => cli::Input::new("Log dir:").default_input("logs").interact()?,
+    };
+    let sample = if args.no_sample {
+        false
+    } else {
+        cli::confirm("Create a sample task and dataset?")
+            .initial_value(true)
+            .interact()?
+    };
+    Ok(InitPlan {
+        dir,
+        profile,
+        model,
+        log_dir,
+        sample,
+    })
+}
+
+fn default_model() -> String {
+    env::var("GAGE_MODEL")
+        .or_else(|_| env::var("INSPECT_EVAL_MODEL"))
+        .unwrap_or_else(|_| DEFAULT_MODEL.into())
+}
+
+/// Returns the files for a plan along with a note for files that
+/// already exist.
+fn plan_files(plan: &InitPlan) -> Vec<(PathBuf, Option<&'static str>)> {
+    let mut files = vec![
+        (plan.dir.join("gage.toml"), "exists"),
+        (plan.dir.join(".env"), "updated"),
+        (plan.dir.join(&plan.log_dir), "exists"),
+    ];
+    if plan.sample {
+        files.push((plan.dir.join(SAMPLE_TASK_FILE), "exists - skipped"));
+        files.push((plan.dir.join(SAMPLE_DATASET_FILE), "exists - skipped"));
+    }
+    files
+        .into_iter()
+        .map(|(path, note)| {
+            let note = path.exists().then_some(note);
+            (path, note)
+        })
+        .collect()
+}
+
+fn write_project(plan: &InitPlan) -> Result<()> {
+    fs::create_dir_all(&plan.dir)?;
+
+    // gage.toml
+    fs::write(plan.dir.join("gage.toml"), config_toml(plan))?;
+
+    // .env - preserves existing lines
+    write_dotenv_profile(&plan.dir.join(".env"), &plan.profile)?;
+
+    // Log dir
+    fs::create_dir_all(plan.dir.join(&plan.log_dir))?;
+
+    // Sample task and dataset
+    if plan.sample {
+        write_new(&plan.dir.join(SAMPLE_TASK_FILE), SAMPLE_TASK)?;
+        write_new(&plan.dir.join(SAMPLE_DATASET_FILE), SAMPLE_DATASET)?;
+    }
     Ok(())
 }
+
+fn config_toml(plan: &InitPlan) -> String {
+    let mut table = toml::Table::new();
+    table.insert("help".into(), "Starter profile created by gage init".into());
+    table.insert("log_dir".into(), plan.log_dir.clone().into());
+    let mut env = toml::Table::new();
+    env.insert("GAGE_MODEL".into(), plan.model.clone().into());
+    table.insert("env".into(), env.into());
+    let mut profiles = toml::Table::new();
+    profiles.insert(plan.profile.clone(), table.into());
+    let mut config = toml::Table::new();
+    config.insert("profiles".into(), profiles.into());
+    toml::to_string(&config).expect("valid toml")
+}
+
+fn write_new(path: &Path, contents: &str) -> Result<()> {
+    if path.exists() {
+        log::debug!("Skipping {path:?} (exists)");
+        return Ok(());
+    }
+    Ok(fs::write(path, contents)?)
+}
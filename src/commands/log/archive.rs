@@ -0,0 +1,202 @@
+use std::{
+    cell::RefCell,
+    collections::BTreeMap,
+    fs::{self, File},
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use clap::Args as ArgsTrait;
+use flate2::{Compression, write::GzEncoder};
+use pyo3::Python;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    commands::log::common::{LogOpDialog, LogOpSuccessMap, LogSelect},
+    dialog::handle_dialog_result,
+    error::Error,
+    inspect::{
+        log::{EvalLogInfo, EvalStatus, read_log_header, resolve_log_dir},
+        log_file,
+    },
+    plural,
+    result::Result,
+};
+
+/// Bundle manifest path.
+pub const MANIFEST_PATH: &str = "manifest.json";
+
+/// Bundle directory containing log files.
+pub const LOGS_DIR: &str = "logs/";
+
+const MANIFEST_VERSION: u32 = 1;
+
+#[derive(ArgsTrait, Debug)]
+pub struct Args {
+    /// The target logs to archive
+    ///
+    /// LOGS may be specified using their #, Id, range, or a filter
+    /// expression (see 'gage log list --help').
+    #[arg(value_name = "LOG")]
+    specs: Vec<String>,
+
+    /// Bundle to write (.tar.gz)
+    #[arg(short, long, value_name = "FILE", required = true)]
+    output: PathBuf,
+
+    /// Log directory
+    #[arg(long)]
+    log_dir: Option<PathBuf>,
+
+    /// Archive all logs
+    #[arg(short, long)]
+    all: bool,
+}
+
+/// Log bundle manifest.
+///
+/// Describes the logs in a bundle. Written to `manifest.json` in the
+/// bundle.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Manifest {
+    /// Manifest format version.
+    pub version: u32,
+
+    /// Time the bundle was created (RFC 3339).
+    pub created: String,
+
+    /// Version of Gage used to create the bundle.
+    pub gage_version: String,
+
+    /// Logs in the bundle.
+    pub logs: Vec<ManifestLog>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ManifestLog {
+    /// Log ID.
+    pub id: String,
+
+    /// Log file path in the bundle.
+    pub file: String,
+
+    /// Task name.
+    pub task: String,
+
+    /// Model used for the eval.
+    pub model: String,
+
+    /// Eval status.
+    pub status: EvalStatus,
+
+    /// Time the eval was created (RFC 3339).
+    pub created: String,
+
+    /// Package versions used for the eval (e.g. 'inspect_ai').
+    #[serde(default)]
+    pub packages: BTreeMap<String, String>,
+}
+
+pub fn main(args: Args) -> Result<()> {
+    validate_args(&args)?;
+    let log_specs = LogSelect::parse_specs(&args.specs)?;
+    let log_dir = resolve_log_dir(args.log_dir.as_ref());
+    // Write to a temp file to avoid leaving partial bundles
+    let tmp_path = args.output.with_extension("tmp");
+    let bundle = RefCell::new(BundleWriter::create(&tmp_path)?);
+    let result = LogOpDialog::new("Archive logs")
+        .log_dir(log_dir.clone())
+        .log_specs(log_specs)
+        .show_prompt(false)
+        .run(|log| bundle.borrow_mut().add(&log_dir, log.log()))
+        .and_then(|result| {
+            bundle.into_inner().finish()?;
+            fs::rename(&tmp_path, &args.output)?;
+            Ok(result)
+        });
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp_path);
+    }
+    handle_dialog_result(result.on_success(|count| {
+        format!(
+            "{count} {} archived to {}",
+            plural!("log", count),
+            args.output.to_string_lossy()
+        )
+    }))
+}
+
+fn validate_args(args: &Args) -> Result<()> {
+    if args.specs.is_empty() && !args.all {
+        return Err(Error::general(
+            "--all flag must be provided if there is no target",
+        ));
+    }
+    Ok(())
+}
+
+pub(super) struct BundleWriter {
+    tar: tar::Builder<GzEncoder<BufWriter<File>>>,
+    manifest: Manifest,
+}
+
+impl BundleWriter {
+    pub(super) fn create(path: &Path) -> Result<Self> {
+        let f = File::create(path)
+            .map_err(|e| Error::general(format!("Cannot write {}: {e}", path.to_string_lossy())))?;
+        Ok(Self {
+            tar: tar::Builder::new(GzEncoder::new(BufWriter::new(f), Compression::default())),
+            manifest: Manifest {
+                version: MANIFEST_VERSION,
+                created: chrono::Utc::now().to_rfc3339(),
+                gage_version: env!("CARGO_PKG_VERSION").into(),
+                logs: Vec::new(),
+            },
+        })
+    }
+
+    pub(super) fn add(&mut self, log_dir: &Path, log: &EvalLogInfo) -> Result<()> {
+        let header = Python::attach(|py| read_log_header(py, &log.name))?;
+        let bytes = log_file::read_log_bytes(&log.name)?;
+        let file = format!("{LOGS_DIR}{}", log_file::relative_name(log_dir, &log.name));
+        let mtime = log
+            .mtime
+            .as_ref()
+            .map(|mtime| mtime.as_datetime().timestamp() as u64)
+            .unwrap_or_default();
+        self.append(&file, &bytes, mtime)?;
+        for suffix in log_file::SIDECAR_SUFFIXES {
+            if let Some(bytes) = log_file::read_sidecar(&log.name, suffix)? {
+                self.append(&format!("{file}{suffix}"), &bytes, mtime)?;
+            }
+        }
+        self.manifest.logs.push(ManifestLog {
+            id: log.log_id.clone(),
+            file,
+            task: header.eval.task.clone(),
+            model: header.eval.model.clone(),
+            status: header.status,
+            created: header.eval.created.to_rfc3339(),
+            packages: header.eval.packages.into_iter().collect(),
+        });
+        Ok(())
+    }
+
+    pub(super) fn finish(mut self) -> Result<()> {
+        let manifest =
+            serde_json::to_vec_pretty(&self.manifest).map_err(|e| Error::general(e.to_string()))?;
+        let now = chrono::Utc::now().timestamp() as u64;
+        self.append(MANIFEST_PATH, &manifest, now)?;
+        self.tar.into_inner()?.finish()?.flush()?;
+        Ok(())
+    }
+
+    fn append(&mut self, path: &str, bytes: &[u8], mtime: u64) -> Result<()> {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(bytes.len() as u64);
+        header.set_mode(0o644);
+        header.set_mtime(mtime);
+        self.tar.append_data(&mut header, path, bytes)?;
+        Ok(())
+    }
+}
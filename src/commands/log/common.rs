@@ -9,7 +9,9 @@ use std::{
 
 use cliclack as cli;
 use console::style;
+use itertools::Itertools;
 use pyo3::Python;
+use serde::Serialize;
 use tabled::{
     Table,
     builder::Builder,
@@ -21,9 +23,17 @@ use tabled::{
 };
 
 use crate::{
+    commands::log::query::LogQuery,
     dialog::DialogResult,
     error::Error,
-    inspect::log::{EvalLogInfo, LogFilter, list_logs_filter, read_log_header},
+    inspect::{
+        log::{EvalLogInfo, LogFilter, list_logs_filter},
+        log_file,
+        log_grades::grade_summary,
+        log_index::{LogIndex, LogSummary},
+        s3::{S3Client, S3Url},
+    },
+    output::{Format, print_records},
     py,
     result::Result,
     theme::Colors,
@@ -35,14 +45,25 @@ pub struct LogOpDialog {
     log_dir: PathBuf,
     filter: LogFilter,
     log_specs: Vec<LogSpec>,
+    select: Option<Box<SelectFn>>,
+    allow_empty: bool,
+    dry_run: bool,
     show_prompt: bool,
     confirm_prompt: Option<Box<ConfirmPromptFn>>,
 }
 
+type SelectFn = dyn Fn(Python<'_>, &mut LogIndex, &[EvalLogInfo]) -> Result<SelectedLogs>;
+
 type ConfirmPromptFn = dyn Fn(&[SelectedLog]) -> String;
 
 pub struct LogOpResult(usize);
 
+impl LogOpResult {
+    pub fn count(&self) -> usize {
+        self.0
+    }
+}
+
 pub trait LogOpSuccessMap {
     fn on_success<F>(self, f: F) -> Result<DialogResult>
     where
@@ -65,6 +86,9 @@ impl LogOpDialog {
             log_dir: "logs".into(),
             filter: LogFilter::None,
             log_specs: Vec::new(),
+            select: None,
+            allow_empty: false,
+            dry_run: false,
             show_prompt: true,
             confirm_prompt: None,
         }
@@ -85,6 +109,29 @@ impl LogOpDialog {
         self
     }
 
+    /// Selects logs using a function rather than log specs.
+    pub fn select<F>(mut self, f: F) -> Self
+    where
+        F: Fn(Python<'_>, &mut LogIndex, &[EvalLogInfo]) -> Result<SelectedLogs> + 'static,
+    {
+        self.select = Some(Box::new(f));
+        self
+    }
+
+    /// Don't treat an empty selection as an error.
+    ///
+    /// The op succeeds with a count of 0 when no logs are selected.
+    pub fn allow_empty(mut self, flag: bool) -> Self {
+        self.allow_empty = flag;
+        self
+    }
+
+    /// Show selected logs without prompting or performing the op.
+    pub fn dry_run(mut self, flag: bool) -> Self {
+        self.dry_run = flag;
+        self
+    }
+
     pub fn show_prompt(mut self, flag: bool) -> Self {
         self.show_prompt = flag;
         self
@@ -110,21 +157,40 @@ impl LogOpDialog {
             let pb = cli::spinner();
             pb.start("Loading log information");
             let logs = list_logs_filter(py, &self.log_dir, self.filter)?;
+            let mut index = LogIndex::open(&self.log_dir);
+            let selected = match &self.select {
+                Some(select) => select(py, &mut index, &logs),
+                None => LogSelect::select_logs(py, &mut index, &logs, &self.log_specs),
+            };
+            index.save();
             pb.clear();
 
             // Select logs for op
-            let selected = LogSelect::select_logs(&logs, &self.log_specs)?
-                .into_iter()
-                .collect::<Vec<_>>();
+            let selected = selected?.into_iter().collect::<Vec<_>>();
             if selected.is_empty() {
+                if self.allow_empty {
+                    return Ok(LogOpResult(0));
+                }
                 return Err(Error::general("Log(s) not found"));
             }
 
-            // Prompt user
-            if self.show_prompt {
-                let (table, _errors) = logs_table(py, selected.iter().map(|s| &s.inner), false);
+            // Show selected logs
+            if self.show_prompt || self.dry_run {
+                let (table, _errors) = logs_table(
+                    py,
+                    &mut index,
+                    selected.iter().map(|s| (s.table_pos, &s.inner)),
+                    false,
+                );
                 // Ignore log table errors as op can proceed regardless
                 cli::log::remark(table.to_string())?;
+            }
+            if self.dry_run {
+                return Ok(LogOpResult(selected.len()));
+            }
+
+            // Prompt user
+            if self.show_prompt {
                 let msg = if let Some(confirm) = self.confirm_prompt {
                     confirm(&selected)
                 } else {
@@ -149,11 +215,11 @@ impl LogOpDialog {
     }
 }
 
-pub fn print_log_table<'a, Logs>(py: Python<'_>, logs: Logs)
+pub fn print_log_table<'a, Logs>(py: Python<'_>, index: &mut LogIndex, logs: Logs)
 where
-    Logs: Iterator<Item = &'a EvalLogInfo>,
+    Logs: Iterator<Item = (usize, &'a EvalLogInfo)>,
 {
-    let (table, errors) = logs_table(py, logs, true);
+    let (table, errors) = logs_table(py, index, logs, true);
     if table.count_rows() == 1 {
         println!("No logs found");
     } else {
@@ -167,40 +233,214 @@ where
     }
 }
 
-type ReadErrorLogIds = Vec<String>;
+/// Log record used for JSON and CSV output.
+///
+/// Fields are documented in README.md ("JSON and CSV output"). New
+/// fields may be added but existing fields are not renamed or removed.
+/// Fields that can't be read from the log header are null. Keep the
+/// README table in sync when changing fields.
+#[derive(Serialize, Debug)]
+pub struct LogRecord {
+    /// Log ID.
+    pub id: String,
+
+    /// Log file path.
+    pub file: String,
+
+    /// Task name.
+    pub task: String,
+
+    /// Run type from 'type:' tag (e.g. "eval" or "run").
+    #[serde(rename = "type")]
+    pub run_type: Option<String>,
+
+    /// Eval status ("started", "success", "cancelled", or "error").
+    pub status: Option<String>,
+
+    /// Model used for the eval.
+    pub model: Option<String>,
+
+    /// Dataset name.
+    pub dataset: Option<String>,
+
+    /// Number of samples evaluated.
+    pub samples: Option<usize>,
+
+    /// Accuracy of the first scorer, if available.
+    pub accuracy: Option<f64>,
+
+    /// Standard error of the first scorer accuracy, if available.
+    pub stderr: Option<f64>,
+
+    /// Number of samples graded by reviewers.
+    pub graded: Option<usize>,
+
+    /// Accuracy of reviewer grades, if any samples are graded correct
+    /// or incorrect.
+    pub human_accuracy: Option<f64>,
+
+    /// Portion of reviewer grades that agree with the scorer, if
+    /// available.
+    pub agreement: Option<f64>,
 
-pub fn logs_table<'a, Logs>(py: Python<'_>, logs: Logs, index: bool) -> (Table, ReadErrorLogIds)
+    /// Eval tags.
+    pub tags: Vec<String>,
+
+    /// Time the eval was created (RFC 3339).
+    pub created: Option<String>,
+
+    /// Time the log was last modified (RFC 3339).
+    pub modified: Option<String>,
+
+    /// Eval ID.
+    pub eval_id: Option<String>,
+
+    /// Run ID.
+    pub run_id: Option<String>,
+
+    /// Error message for failed evals or logs that can't be read.
+    pub error: Option<String>,
+}
+
+impl LogRecord {
+    pub fn new(log: &EvalLogInfo, summary: &LogSummary) -> Self {
+        Self {
+            run_type: summary.run_type.clone(),
+            status: Some(summary.status.to_string()),
+            model: Some(summary.model.clone()),
+            dataset: summary.dataset.clone(),
+            samples: summary.samples,
+            accuracy: summary.accuracy,
+            stderr: summary.stderr,
+            graded: Some(summary.grades.graded()),
+            human_accuracy: summary.grades.human_accuracy(),
+            agreement: summary.grades.agreement(),
+            tags: summary.tags.clone(),
+            created: Some(summary.created.to_rfc3339()),
+            eval_id: Some(summary.eval_id.clone()),
+            run_id: Some(summary.run_id.clone()),
+            error: summary.error.clone(),
+            ..Self::from_info(log)
+        }
+    }
+
+    /// Returns a record for a log that can't be read.
+    pub fn read_error(log: &EvalLogInfo, err: &Error) -> Self {
+        Self {
+            error: Some(match err {
+                Error::Py(e) => e.to_string(),
+                _ => err.to_string(),
+            }),
+            ..Self::from_info(log)
+        }
+    }
+
+    fn from_info(log: &EvalLogInfo) -> Self {
+        Self {
+            id: log.log_id.clone(),
+            file: log
+                .name
+                .strip_prefix("file://")
+                .unwrap_or(&log.name)
+                .to_string(),
+            task: log.task.clone(),
+            run_type: None,
+            status: None,
+            model: None,
+            dataset: None,
+            samples: None,
+            accuracy: None,
+            stderr: None,
+            graded: None,
+            human_accuracy: None,
+            agreement: None,
+            tags: Vec::new(),
+            created: None,
+            modified: log.mtime.as_ref().map(|mtime| mtime.to_rfc3339()),
+            eval_id: None,
+            run_id: None,
+            error: None,
+        }
+    }
+}
+
+/// Prints logs as JSON or CSV using `LogRecord`.
+pub fn print_log_records<'a, Logs>(
+    py: Python<'_>,
+    index: &mut LogIndex,
+    logs: Logs,
+    format: Format,
+) -> Result<()>
 where
     Logs: Iterator<Item = &'a EvalLogInfo>,
+{
+    let records = logs
+        .map(|log| match index.summary(py, log) {
+            Ok(summary) => LogRecord::new(
+                log,
+                &LogSummary {
+                    grades: grade_summary(&log.name),
+                    ..summary
+                },
+            ),
+            Err(err) => {
+                log::error!("Error reading {}: {}", log.log_id, err);
+                LogRecord::read_error(log, &err)
+            }
+        })
+        .collect::<Vec<_>>();
+    print_records(format, &records)
+}
+
+type ReadErrorLogIds = Vec<String>;
+
+/// Returns a table of logs.
+///
+/// `logs` is an iterator of log table positions and logs. If `index`
+/// is true, the table includes log positions.
+pub fn logs_table<'a, Logs>(
+    py: Python<'_>,
+    log_index: &mut LogIndex,
+    logs: Logs,
+    index: bool,
+) -> (Table, ReadErrorLogIds)
+where
+    Logs: Iterator<Item = (usize, &'a EvalLogInfo)>,
 {
     let now = chrono::Utc::now();
     let index_offset = if index { 0 } else { 1 };
     let mut colored_cells: Vec<(usize, usize, Color)> = Vec::new();
     let mut table = Builder::new();
     table.push_record([
-        "#", "Id", "Task", "Type", "Status", "Model", "Dataset", "Modified",
+        "#", "Id", "Task", "Type", "Status", "Model", "Dataset", "Tags", "Modified",
     ]);
     let mut errors = Vec::new();
-    for (i, log) in logs.enumerate() {
-        match read_log_header(py, &log.name) {
-            Ok(header) => {
+    for (i, (table_pos, log)) in logs.enumerate() {
+        match log_index.summary(py, log) {
+            Ok(summary) => {
                 let time = log
                     .mtime
                     .as_ref()
                     .map(|mtime| mtime.to_human_since(&now))
                     .unwrap_or_default();
                 // status color
-                if let Some(color) = status_color(&header.status.to_string()) {
+                if let Some(color) = status_color(&summary.status.to_string()) {
                     colored_cells.push((i + 1, 4 - index_offset, color));
                 }
                 table.push_record([
-                    (i + 1).to_string(),
+                    table_pos.to_string(),
                     log.short_log_id().into(),
                     log.task.clone(),
-                    header.eval.run_type().unwrap_or_default(),
-                    header.status.to_string(),
-                    fit_path_name(&header.eval.model, 20),
-                    fit_path_name(&header.eval.dataset.name.unwrap_or_default(), 20),
+                    summary.run_type.unwrap_or_default(),
+                    match summary.progress {
+                        Some((completed, total)) => {
+                            format!("{} {completed}/{total}", summary.status)
+                        }
+                        None => summary.status.to_string(),
+                    },
+                    fit_path_name(&summary.model, 20),
+                    fit_path_name(&summary.dataset.unwrap_or_default(), 20),
+                    fmt_tags(&summary.tags),
                     time,
                 ]);
             }
@@ -208,7 +448,7 @@ where
                 errors.push(log.log_id.clone());
                 log::error!("Error reading {}: {}", log.log_id, err);
                 table.push_record([
-                    (i + 1).to_string(),
+                    table_pos.to_string(),
                     log.short_log_id().into(),
                     log.task.clone(),
                     "?".into(),
@@ -216,6 +456,7 @@ where
                     "?".into(),
                     "?".into(),
                     "?".into(),
+                    "?".into(),
                 ]);
             }
         }
@@ -256,7 +497,7 @@ where
     // Date is dim
     table.with(Colorization::exact(
         [Colors::dim()],
-        Columns::one(7 - index_offset).intersect(Rows::new(1..)),
+        Columns::one(8 - index_offset).intersect(Rows::new(1..)),
     ));
 
     // Apply colored cells (e.g. status)
@@ -267,6 +508,15 @@ where
     (table, errors)
 }
 
+/// Returns tags for display in a table.
+///
+/// 'type:' tags are omitted as they're shown as the run type.
+fn fmt_tags(tags: &[String]) -> String {
+    tags.iter()
+        .filter(|tag| !tag.starts_with("type:"))
+        .join(", ")
+}
+
 fn status_color(status: &str) -> Option<Color> {
     match status {
         "success" => None,
@@ -285,19 +535,82 @@ impl LogSelect {
             .collect::<Result<Vec<_>>>()
     }
 
-    pub fn select_logs(from: &[EvalLogInfo], specs: &[LogSpec]) -> Result<SelectedLogs> {
+    /// Selects logs using specs.
+    ///
+    /// Logs selected by #, range, or Id are combined. Filter
+    /// expressions are then applied to the combined logs (or to all
+    /// logs if there are no other specs). Filters are evaluated using
+    /// log headers from `index`.
+    pub fn select_logs(
+        py: Python<'_>,
+        index: &mut LogIndex,
+        from: &[EvalLogInfo],
+        specs: &[LogSpec],
+    ) -> Result<SelectedLogs> {
+        let (queries, specs): (Vec<_>, Vec<_>) = specs
+            .iter()
+            .partition(|spec| matches!(spec, LogSpec::Query(_)));
         let mut selected = BTreeSet::default();
         // If no specs, assume all
         let specs = if specs.is_empty() {
-            &[LogSpec::TablePosRangeFrom(1..)]
+            vec![&LogSpec::TablePosRangeFrom(1..)]
         } else {
             specs
         };
         for spec in specs {
             selected.extend(spec.select_logs(from)?)
         }
+        if !queries.is_empty() {
+            selected.retain(|log| match index.summary(py, &log.inner) {
+                Ok(summary) => queries.iter().all(|spec| match spec {
+                    LogSpec::Query(query) => query.matches(&summary),
+                    _ => true,
+                }),
+                Err(err) => {
+                    log::error!("Error reading {}: {}", log.inner.log_id, err);
+                    false
+                }
+            });
+        }
         Ok(selected)
     }
+
+    /// Selects a single log using a log #, ID, or filter.
+    pub fn select_one(
+        py: Python<'_>,
+        index: &mut LogIndex,
+        from: &[EvalLogInfo],
+        spec: &str,
+    ) -> Result<SelectedLog> {
+        let log_spec = LogSpec::from_str(spec)?;
+        if matches!(
+            log_spec,
+            LogSpec::TablePosRange(_) | LogSpec::TablePosRangeFrom(_)
+        ) {
+            return Err(Error::general(format!(
+                "Invalid log '{spec}' - expected a log #, ID, or filter"
+            )));
+        }
+        let mut selected = Self::select_logs(py, index, from, &[log_spec])?;
+        if selected.len() > 1 {
+            return Err(Error::general(format!(
+                "Log spec '{spec}' matches more than one log: {}\n\
+                \n\
+                Use a log # or ID instead.",
+                selected
+                    .iter()
+                    .map(|log| log.inner.log_id.as_str())
+                    .join(", ")
+            )));
+        }
+        selected.pop_first().ok_or_else(|| {
+            Error::general(format!(
+                "No logs matching '{spec}'\n\
+                \n\
+                Try 'gage log list' for a list of logs."
+            ))
+        })
+    }
 }
 
 pub struct SelectedLog {
@@ -335,19 +648,35 @@ impl SelectedLog {
 }
 
 impl SelectedLog {
+    pub fn log(&self) -> &EvalLogInfo {
+        &self.inner
+    }
+
     pub fn file_path(&self) -> Result<PathBuf> {
         resolve_file_name(&self.inner.name)
     }
 
     pub fn delete(self, permanent: bool) -> Result<()> {
+        if let Some(url) = S3Url::parse(&self.inner.name) {
+            return if permanent {
+                S3Client::from_env().delete(&url)?;
+                log_file::remove_sidecars(&self.inner.name)
+            } else {
+                s3_recoverable_deletion(&url)
+            };
+        }
         if permanent {
-            permanent_deletion(self.file_path()?)
+            permanent_deletion(self.file_path()?)?;
+            log_file::remove_sidecars(&self.inner.name)
         } else {
             recoverable_deletion(self.file_path()?)
         }
     }
 
     pub fn restore(self) -> Result<()> {
+        if let Some(url) = S3Url::parse(&self.inner.name) {
+            return s3_restore(&url);
+        }
         restore(self.file_path()?)
     }
 }
@@ -358,6 +687,7 @@ pub enum LogSpec {
     IdPrefix(String),
     TablePosRange(Range<usize>), // NOTE: Range is NOT inclusive
     TablePosRangeFrom(RangeFrom<usize>),
+    Query(LogQuery),
 }
 
 pub type SelectedLogs = BTreeSet<SelectedLog>;
@@ -388,6 +718,8 @@ impl LogSpec {
                     selected.insert(SelectedLog::new(table_pos, log.clone()));
                 }
             }
+            // Queries are applied by `LogSelect::select_logs`
+            LogSpec::Query(_) => {}
             LogSpec::IdPrefix(prefix) => {
                 let matches = logs_table
                     .iter_table()
@@ -415,8 +747,12 @@ impl LogSpec {
     }
 
     pub fn from_str(target: &str) -> Result<Self> {
+        // Attempt to parse a filter expression (may contain ':' in times)
+        if LogQuery::is_query(target) {
+            Ok(Self::Query(LogQuery::parse(target)?))
+
         // Attempt to parse a numeric table position
-        if let Ok(pos) = target.parse::<usize>() {
+        } else if let Ok(pos) = target.parse::<usize>() {
             Ok(Self::TablePos(pos))
 
         // Attempt to parse range in format START:END
@@ -511,6 +847,40 @@ fn permanent_deletion(file_path: PathBuf) -> Result<()> {
     Ok(fs::remove_file(file_path)?)
 }
 
+/// Deletes an S3 log by moving it to a key with a `.deleted` suffix.
+fn s3_recoverable_deletion(url: &S3Url) -> Result<()> {
+    if url.key.ends_with(".deleted") {
+        return Ok(());
+    }
+    let client = S3Client::from_env();
+    let deleted_key = format!("{}.deleted", url.key);
+    if client.exists(&url.with_key(&deleted_key))? {
+        return Err(Error::Custom(format!(
+            "Deleted file already exists for {:?}",
+            file_name(&url.key)
+        )));
+    }
+    client.rename(url, &deleted_key)
+}
+
+fn s3_restore(url: &S3Url) -> Result<()> {
+    let Some(restored_key) = url.key.strip_suffix(".deleted") else {
+        return Ok(());
+    };
+    let client = S3Client::from_env();
+    if client.exists(&url.with_key(restored_key))? {
+        return Err(Error::Custom(format!(
+            "Restored file already exists for {:?}",
+            file_name(&url.key)
+        )));
+    }
+    client.rename(url, restored_key)
+}
+
+fn file_name(key: &str) -> &str {
+    key.rsplit('/').next().unwrap_or(key)
+}
+
 #[macro_export]
 macro_rules! plural {
     ($item:literal, $count:expr) => {{
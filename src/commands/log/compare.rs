@@ -0,0 +1,659 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use clap::Args as ArgsTrait;
+use cliclack as cli;
+use console::style;
+use itertools::Itertools;
+use pyo3::Python;
+use serde::Serialize;
+use serde_json::Value;
+use tabled::{
+    builder::Builder,
+    settings::{
+        Color,
+        object::{Cell, Columns, Object, Rows},
+        themes::Colorization,
+    },
+};
+
+use crate::{
+    commands::log::common::LogSelect,
+    inspect::{
+        log::{EvalLog, EvalLogInfo, EvalSample, EvalScore, list_logs, read_log, resolve_log_dir},
+        log_index::LogIndex,
+        scorer,
+    },
+    output::{Format, print_record},
+    plural, py,
+    result::Result,
+    theme::Colors,
+    util::TableExt,
+};
+
+#[derive(ArgsTrait, Debug)]
+pub struct Args {
+    /// Baseline log
+    ///
+    /// Logs may be specified using their # or Id.
+    #[arg(value_name = "A")]
+    a: String,
+
+    /// Log to compare with the baseline
+    #[arg(value_name = "B")]
+    b: String,
+
+    /// Show samples with changed output or scores (not only flipped
+    /// samples)
+    #[arg(short, long)]
+    changed: bool,
+
+    /// Log directory
+    #[arg(long)]
+    log_dir: Option<PathBuf>,
+
+    /// Show comparison as JSON
+    ///
+    /// JSON output includes all changed samples.
+    #[arg(long)]
+    json: bool,
+}
+
+/// Log comparison.
+///
+/// Used to print comparison tables and as the schema for JSON output.
+#[derive(Serialize, Debug)]
+struct Comparison {
+    a: LogSummary,
+    b: LogSummary,
+    metrics: Vec<MetricDelta>,
+    samples: SampleCounts,
+    changed: Vec<SampleDiff>,
+}
+
+#[derive(Serialize, Debug)]
+struct LogSummary {
+    id: String,
+    task: String,
+    model: String,
+    status: String,
+    created: String,
+    samples: usize,
+}
+
+#[derive(Serialize, Debug)]
+struct MetricDelta {
+    score: String,
+    metric: String,
+    a: Option<f64>,
+    b: Option<f64>,
+    delta: Option<f64>,
+}
+
+#[derive(Serialize, Debug, Default)]
+struct SampleCounts {
+    /// Samples in both logs (aligned by id and epoch).
+    compared: usize,
+
+    /// Samples that changed from incorrect to correct.
+    fixed: usize,
+
+    /// Samples that changed from correct to incorrect.
+    regressed: usize,
+
+    /// Samples with changed output or score that didn't flip.
+    changed: usize,
+
+    /// Samples only in A as 'ID' or 'ID:EPOCH'.
+    only_a: Vec<String>,
+
+    /// Samples only in B as 'ID' or 'ID:EPOCH'.
+    only_b: Vec<String>,
+}
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum Change {
+    Fixed,
+    Regressed,
+    Changed,
+}
+
+#[derive(Serialize, Debug)]
+struct SampleDiff {
+    id: Value,
+    epoch: i64,
+    change: Change,
+    input: String,
+    a: SampleResult,
+    b: SampleResult,
+}
+
+#[derive(Serialize, Debug)]
+struct SampleResult {
+    output: String,
+    score: Option<scorer::Value>,
+    correct: Option<bool>,
+}
+
+pub fn main(args: Args) -> Result<()> {
+    py::init();
+    Python::attach(|py| {
+        let log_dir = resolve_log_dir(args.log_dir.as_ref());
+        let logs = list_logs(py, &log_dir)?;
+        let mut index = LogIndex::open(&log_dir);
+        let a = LogSelect::select_one(py, &mut index, &logs, &args.a);
+        let b = LogSelect::select_one(py, &mut index, &logs, &args.b);
+        index.save();
+        let (a, b) = (a?, b?);
+
+        let (log_a, log_b) = if args.json {
+            (read_log(py, &a.log().name)?, read_log(py, &b.log().name)?)
+        } else {
+            let pb = cli::spinner();
+            pb.start("Reading logs");
+            let logs = read_log(py, &a.log().name)
+                .and_then(|log_a| Ok((log_a, read_log(py, &b.log().name)?)));
+            pb.clear();
+            logs?
+        };
+
+        let comparison = compare(a.log(), &log_a, b.log(), &log_b);
+        if args.json {
+            return print_record(Format::Json, &comparison);
+        }
+        print_logs(&comparison);
+        print_metrics(&comparison.metrics);
+        print_counts(&comparison.samples);
+        print_samples(&comparison.changed, args.changed);
+        Ok(())
+    })
+}
+
+fn compare(a: &EvalLogInfo, log_a: &EvalLog, b: &EvalLogInfo, log_b: &EvalLog) -> Comparison {
+    let samples_a = log_a.samples.as_deref().unwrap_or_default();
+    let samples_b = log_b.samples.as_deref().unwrap_or_default();
+    let multi_epoch = samples_a.iter().chain(samples_b).any(|s| s.epoch > 1);
+
+    // Align samples by id and epoch, using A sample order
+    let b_index: HashMap<_, _> = samples_b.iter().map(|s| (sample_key(s), s)).collect();
+    let a_keys = samples_a.iter().map(sample_key).collect_vec();
+    let mut counts = SampleCounts::default();
+    let mut changed = Vec::new();
+    for sample_a in samples_a {
+        let Some(sample_b) = b_index.get(&sample_key(sample_a)) else {
+            counts.only_a.push(sample_label(sample_a, multi_epoch));
+            continue;
+        };
+        counts.compared += 1;
+        if let Some(diff) = sample_diff(sample_a, sample_b) {
+            match diff.change {
+                Change::Fixed => counts.fixed += 1,
+                Change::Regressed => counts.regressed += 1,
+                Change::Changed => counts.changed += 1,
+            }
+            changed.push(diff);
+        }
+    }
+    counts.only_b = samples_b
+        .iter()
+        .filter(|s| !a_keys.contains(&sample_key(s)))
+        .map(|s| sample_label(s, multi_epoch))
+        .collect();
+
+    Comparison {
+        a: log_summary(a, log_a),
+        b: log_summary(b, log_b),
+        metrics: metric_deltas(log_a, log_b),
+        samples: counts,
+        changed,
+    }
+}
+
+fn log_summary(log: &EvalLogInfo, eval_log: &EvalLog) -> LogSummary {
+    LogSummary {
+        id: log.log_id.clone(),
+        task: eval_log.eval.task.clone(),
+        model: eval_log.eval.model.clone(),
+        status: eval_log.status.to_string(),
+        created: eval_log.eval.created.to_rfc3339(),
+        samples: eval_log.samples.as_ref().map(|s| s.len()).unwrap_or(0),
+    }
+}
+
+/// Returns metric deltas for scores in either log.
+///
+/// Scores and metrics are listed in A order followed by those only in
+/// B. Metrics within a score are sorted by name.
+fn metric_deltas(log_a: &EvalLog, log_b: &EvalLog) -> Vec<MetricDelta> {
+    let (scores_a, scores_b) = (log_scores(log_a), log_scores(log_b));
+    let score_names = scores_a
+        .iter()
+        .chain(scores_b.iter())
+        .map(|s| s.name.as_str())
+        .unique()
+        .collect_vec();
+    let mut deltas = Vec::new();
+    for name in score_names {
+        let score_a = scores_a.iter().find(|s| s.name == name);
+        let score_b = scores_b.iter().find(|s| s.name == name);
+        let metric_names = score_a
+            .iter()
+            .chain(score_b.iter())
+            .flat_map(|s| s.metrics.keys())
+            .unique()
+            .sorted();
+        for metric in metric_names {
+            let a = score_a.and_then(|s| s.metrics.get(metric).map(|m| m.value.as_f64()));
+            let b = score_b.and_then(|s| s.metrics.get(metric).map(|m| m.value.as_f64()));
+            deltas.push(MetricDelta {
+                score: name.into(),
+                metric: metric.clone(),
+                a,
+                b,
+                delta: a.zip(b).map(|(a, b)| b - a),
+            });
+        }
+    }
+    deltas
+}
+
+fn log_scores(log: &EvalLog) -> &[EvalScore] {
+    log.results
+        .as_ref()
+        .map(|r| r.scores.as_slice())
+        .unwrap_or_default()
+}
+
+fn sample_key(sample: &EvalSample) -> (String, i64) {
+    (sample.id.to_string(), sample.epoch)
+}
+
+fn sample_label(sample: &EvalSample, multi_epoch: bool) -> String {
+    if multi_epoch {
+        format!("{}:{}", sample.id, sample.epoch)
+    } else {
+        sample.id.to_string()
+    }
+}
+
+/// Returns a diff for samples that flipped or have changed output or
+/// score. Returns None if the samples are the same.
+fn sample_diff(a: &EvalSample, b: &EvalSample) -> Option<SampleDiff> {
+    let (result_a, result_b) = (sample_result(a), sample_result(b));
+    let change = match (result_a.correct, result_b.correct) {
+        (Some(false), Some(true)) => Change::Fixed,
+        (Some(true), Some(false)) => Change::Regressed,
+        _ if result_a.output != result_b.output
+            || score_str(result_a.score.as_ref()) != score_str(result_b.score.as_ref()) =>
+        {
+            Change::Changed
+        }
+        _ => return None,
+    };
+    Some(SampleDiff {
+        id: Value::from(&a.id),
+        epoch: a.epoch,
+        change,
+        input: a.input.text(),
+        a: result_a,
+        b: result_b,
+    })
+}
+
+fn sample_result(sample: &EvalSample) -> SampleResult {
+    SampleResult {
+        output: sample.output.completion.clone(),
+        score: sample_score(sample).cloned(),
+        correct: sample.is_correct(),
+    }
+}
+
+/// Returns the sample default score or the first score by name.
+fn sample_score(sample: &EvalSample) -> Option<&scorer::Value> {
+    if let Some((_, score)) = sample.default_score() {
+        return Some(&score.value);
+    }
+    let scores = sample.scores.as_ref()?;
+    let name = scores.keys().sorted().next()?;
+    Some(&scores[name].value)
+}
+
+fn score_str(score: Option<&scorer::Value>) -> String {
+    score.map(|s| s.to_string()).unwrap_or_default()
+}
+
+fn print_logs(c: &Comparison) {
+    let mut table = Builder::new();
+    table.push_record(["", "A", "B"]);
+    table.push_record(["Log", &c.a.id, &c.b.id]);
+    table.push_record(["Task", &c.a.task, &c.b.task]);
+    table.push_record(["Model", &c.a.model, &c.b.model]);
+    table.push_record(["Status", &c.a.status, &c.b.status]);
+    table.push_record([
+        "Samples",
+        &c.a.samples.to_string(),
+        &c.b.samples.to_string(),
+    ]);
+    println!(
+        "{}",
+        table
+            .build()
+            .with_term_fit()
+            .with_rounded()
+            .with_col_labels()
+            .with_row_labels()
+            .with(Colorization::exact(
+                [Color::FG_BRIGHT_CYAN],
+                Columns::new(1..).intersect(Rows::one(1))
+            ))
+            .with(Colorization::exact(
+                [Color::FG_BRIGHT_YELLOW],
+                Columns::new(1..).intersect(Rows::one(2))
+            ))
+    );
+}
+
+fn print_metrics(metrics: &[MetricDelta]) {
+    if metrics.is_empty() {
+        println!("{}", style("No metrics to compare").dim());
+        return;
+    }
+    let fmt = |val: Option<f64>| val.map(|v| format!("{v:.3}")).unwrap_or("-".into());
+    let mut table = Builder::new();
+    table.push_record(["Score", "Metric", "A", "B", "Delta"]);
+    for metric in metrics {
+        table.push_record([
+            metric.score.clone(),
+            metric.metric.clone(),
+            fmt(metric.a),
+            fmt(metric.b),
+            metric
+                .delta
+                .map(|d| format!("{d:+.3}"))
+                .unwrap_or("-".into()),
+        ]);
+    }
+    println!(
+        "{}",
+        table
+            .build()
+            .with_term_fit()
+            .with_rounded()
+            .with_col_labels()
+            .with(Colorization::exact(
+                [Color::FG_BRIGHT_YELLOW],
+                Columns::one(0).intersect(Rows::new(1..))
+            ))
+    );
+}
+
+fn print_counts(counts: &SampleCounts) {
+    println!(
+        "Compared {} {}: {} fixed, {} regressed, {} changed",
+        counts.compared,
+        plural!("sample", counts.compared),
+        counts.fixed,
+        counts.regressed,
+        counts.changed
+    );
+    for (label, ids) in [("A", &counts.only_a), ("B", &counts.only_b)] {
+        if !ids.is_empty() {
+            println!(
+                "{}",
+                style(format!(
+                    "{} {} only in {label}: {}",
+                    ids.len(),
+                    plural!("sample", ids.len()),
+                    ids.join(", ")
+                ))
+                .dim()
+            );
+        }
+    }
+}
+
+fn print_samples(diffs: &[SampleDiff], show_changed: bool) {
+    let diffs = diffs
+        .iter()
+        .filter(|d| show_changed || d.change != Change::Changed)
+        .collect_vec();
+    if diffs.is_empty() {
+        return;
+    }
+    let multi_epoch = diffs.iter().any(|d| d.epoch > 1);
+    let mut header = vec!["Id"];
+    if multi_epoch {
+        header.push("Epoch");
+    }
+    header.extend(["Score", "Input", "Output A", "Output B"]);
+    let score_col = header.len() - 4;
+    let mut colored_cells = Vec::new();
+    let mut table = Builder::new();
+    table.push_record(header);
+    for (i, diff) in diffs.iter().enumerate() {
+        let mut row = vec![crate::output::csv_cell(&diff.id)];
+        if multi_epoch {
+            row.push(diff.epoch.to_string());
+        }
+        row.extend([
+            format!(
+                "{} → {}",
+                score_str(diff.a.score.as_ref()),
+                score_str(diff.b.score.as_ref())
+            ),
+            snippet(&diff.input),
+            snippet(&diff.a.output),
+            snippet(&diff.b.output),
+        ]);
+        match diff.change {
+            Change::Fixed => colored_cells.push((i + 1, Color::FG_GREEN)),
+            Change::Regressed => colored_cells.push((i + 1, Color::FG_RED)),
+            Change::Changed => colored_cells.push((i + 1, Colors::dim())),
+        }
+        table.push_record(row);
+    }
+    let mut table = table.build();
+    table
+        .with_term_fit()
+        .with_rounded()
+        .with_col_labels()
+        .with(Colorization::exact(
+            [Color::FG_BRIGHT_CYAN],
+            Columns::one(0).intersect(Rows::new(1..)),
+        ));
+    for (row, color) in colored_cells {
+        table.with(Colorization::exact([color], Cell::new(row, score_col)));
+    }
+    println!("{table}");
+}
+
+/// Returns the first line of a value, truncated to fit a table cell.
+fn snippet(s: &str) -> String {
+    const MAX_CHARS: usize = 40;
+    let mut lines = s.trim().lines();
+    let line = lines.next().unwrap_or_default();
+    if line.chars().count() > MAX_CHARS {
+        format!("{}…", line.chars().take(MAX_CHARS - 1).collect::<String>())
+    } else if lines.next().is_some() {
+        format!("{line} …")
+    } else {
+        line.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{Value, json};
+
+    use crate::inspect::log::{EvalLog, EvalLogInfo};
+
+    use super::{Change, compare, metric_deltas, sample_diff};
+
+    fn eval_log(scores: Value, samples: &[(i64, i64, &str, &str)]) -> EvalLog {
+        let samples = samples
+            .iter()
+            .map(|(id, epoch, output, value)| {
+                json!({
+                    "id": id, "epoch": epoch, "input": format!("input {id}"),
+                    "output": {"choices": [], "completion": output},
+                    "scores": {"match": {"value": value}}
+                })
+            })
+            .collect::<Vec<_>>();
+        serde_json::from_value(json!({
+            "version": 2,
+            "status": "success",
+            "eval": {
+                "eval_id": "E1", "run_id": "R1",
+                "created": "2025-01-01T10:00:00+00:00",
+                "task": "hello", "task_id": "T1", "dataset": {},
+                "model": "mockllm/model"
+            },
+            "plan": {"name": "plan", "steps": []},
+            "results": {"total_samples": samples.len(), "completed_samples": samples.len(), "scores": scores},
+            "samples": samples
+        }))
+        .unwrap()
+    }
+
+    fn log_info(log_id: &str) -> EvalLogInfo {
+        EvalLogInfo {
+            name: format!("2025-01-01T10-00-00+00-00_hello_{log_id}.eval"),
+            mtime: None,
+            task: "hello".into(),
+            log_id: log_id.into(),
+            size: None,
+            etag: None,
+        }
+    }
+
+    fn score(name: &str, metrics: &[(&str, f64)]) -> Value {
+        let metrics = metrics
+            .iter()
+            .map(|(metric, value)| (metric.to_string(), json!({"name": metric, "value": value})))
+            .collect::<serde_json::Map<_, _>>();
+        json!({"name": name, "scorer": name, "metrics": metrics})
+    }
+
+    #[test]
+    fn test_compare() {
+        let log_a = eval_log(
+            json!([]),
+            &[
+                (1, 1, "a", "C"),
+                (2, 1, "b", "I"),
+                (3, 1, "c", "C"),
+                (4, 1, "d", "C"),
+                (5, 1, "e", "C"),
+            ],
+        );
+        let log_b = eval_log(
+            json!([]),
+            &[
+                (1, 1, "a", "I"),
+                (2, 1, "b", "C"),
+                (3, 1, "c2", "C"),
+                (4, 1, "d", "C"),
+                (6, 1, "f", "C"),
+            ],
+        );
+        let c = compare(&log_info("A"), &log_a, &log_info("B"), &log_b);
+        assert_eq!((c.a.id.as_str(), c.b.id.as_str()), ("A", "B"));
+        assert_eq!((c.a.samples, c.b.samples), (5, 5));
+        assert_eq!(c.samples.compared, 4);
+        assert_eq!(
+            (c.samples.fixed, c.samples.regressed, c.samples.changed),
+            (1, 1, 1)
+        );
+        assert_eq!(c.samples.only_a, ["5"]);
+        assert_eq!(c.samples.only_b, ["6"]);
+        assert_eq!(
+            c.changed
+                .iter()
+                .map(|d| (d.id.to_string(), d.change))
+                .collect::<Vec<_>>(),
+            [
+                ("1".into(), Change::Regressed),
+                ("2".into(), Change::Fixed),
+                ("3".into(), Change::Changed),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_compare_epochs() {
+        let log_a = eval_log(json!([]), &[(1, 1, "a", "C"), (1, 2, "a", "C")]);
+        let log_b = eval_log(json!([]), &[(1, 1, "a", "I"), (2, 1, "b", "C")]);
+        let c = compare(&log_info("A"), &log_a, &log_info("B"), &log_b);
+        assert_eq!(c.samples.compared, 1);
+        assert_eq!(c.samples.regressed, 1);
+        assert_eq!(c.changed[0].epoch, 1);
+        assert_eq!(c.samples.only_a, ["1:2"]);
+        assert_eq!(c.samples.only_b, ["2:1"]);
+    }
+
+    #[test]
+    fn test_sample_diff() {
+        let samples = |log: EvalLog| log.samples.unwrap();
+        let a = samples(eval_log(
+            json!([]),
+            &[
+                (1, 1, "a", "I"),
+                (2, 1, "b", "C"),
+                (3, 1, "c", "1"),
+                (4, 1, "d", "C"),
+            ],
+        ));
+        let b = samples(eval_log(
+            json!([]),
+            &[
+                (1, 1, "a", "C"),
+                (2, 1, "b", "I"),
+                (3, 1, "c", "0.5"),
+                (4, 1, "d", "C"),
+            ],
+        ));
+        let change = |i: usize| sample_diff(&a[i], &b[i]).map(|d| d.change);
+        assert_eq!(change(0), Some(Change::Fixed));
+        assert_eq!(change(1), Some(Change::Regressed));
+        assert_eq!(change(2), Some(Change::Changed));
+        assert_eq!(change(3), None);
+
+        let diff = sample_diff(&a[0], &b[0]).unwrap();
+        assert_eq!(diff.input, "input 1");
+        assert_eq!((diff.a.correct, diff.b.correct), (Some(false), Some(true)));
+    }
+
+    #[test]
+    fn test_metric_deltas() {
+        let log_a = eval_log(
+            json!([score("match", &[("stderr", 0.1), ("accuracy", 0.5)])]),
+            &[],
+        );
+        let log_b = eval_log(
+            json!([
+                score("other", &[("mean", 2.0)]),
+                score("match", &[("accuracy", 0.75)]),
+            ]),
+            &[],
+        );
+        let deltas = metric_deltas(&log_a, &log_b)
+            .into_iter()
+            .map(|d| (d.score, d.metric, d.a, d.b, d.delta))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            deltas,
+            [
+                (
+                    "match".into(),
+                    "accuracy".into(),
+                    Some(0.5),
+                    Some(0.75),
+                    Some(0.25)
+                ),
+                ("match".into(), "stderr".into(), Some(0.1), None, None),
+                ("other".into(), "mean".into(), None, Some(2.0), None),
+            ]
+        );
+    }
+}
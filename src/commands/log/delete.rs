@@ -15,7 +15,9 @@ pub struct Args {
     /// The target logs to delete
     ///
     /// LOGS may be specified using their # or Id. To delete a range, use
-    /// one of 'START:', ':END', or 'START:END'.
+    /// one of 'START:', ':END', or 'START:END'. Use a filter expression
+    /// such as 'status=error' to delete logs matching the filter (see
+    /// 'gage log list --help').
     #[arg(value_name = "LOG")]
     specs: Vec<String>,
 
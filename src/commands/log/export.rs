@@ -0,0 +1,403 @@
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use clap::{Args as ArgsTrait, ValueEnum};
+use cliclack as cli;
+use itertools::Itertools;
+use pyo3::Python;
+use serde_json::{Map, Value};
+
+use crate::{
+    commands::log::common::LogSelect,
+    inspect::{
+        log::{EvalLog, EvalLogInfo, EvalSample, list_logs, read_log, resolve_log_dir},
+        log_index::LogIndex,
+    },
+    output::{csv_cell, ignore_broken_pipe},
+    plural, py,
+    result::Result,
+};
+
+#[derive(ArgsTrait, Debug)]
+pub struct Args {
+    /// Log to export
+    ///
+    /// LOG may be specified using its #, Id, or a filter expression
+    /// that matches one log (see 'gage log list --help').
+    #[arg(value_name = "LOG")]
+    log: String,
+
+    /// Export format (defaults to format implied by --output or 'csv')
+    #[arg(short, long, value_enum)]
+    format: Option<ExportFormat>,
+
+    /// File to write (defaults to standard output)
+    #[arg(short, long, value_name = "FILE")]
+    output: Option<PathBuf>,
+
+    /// Log directory
+    #[arg(long)]
+    log_dir: Option<PathBuf>,
+}
+
+/// Log export format.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum ExportFormat {
+    Csv,
+    Jsonl,
+    Md,
+    Html,
+}
+
+impl ExportFormat {
+    /// Returns the format for a path based on its extension.
+    fn for_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_lowercase().as_str() {
+            "csv" => Some(Self::Csv),
+            "jsonl" | "json" => Some(Self::Jsonl),
+            "md" | "markdown" => Some(Self::Md),
+            "html" | "htm" => Some(Self::Html),
+            _ => None,
+        }
+    }
+}
+
+/// Log samples flattened into columns.
+///
+/// Each sample is a row. Score columns are added for each score name
+/// used in the log: 'score.NAME', 'answer.NAME' and
+/// 'explanation.NAME'.
+struct Export {
+    title: String,
+    summary: Vec<(&'static str, String)>,
+    columns: Vec<String>,
+    rows: Vec<Vec<Value>>,
+}
+
+pub fn main(args: Args) -> Result<()> {
+    let format = args
+        .format
+        .or_else(|| args.output.as_deref().and_then(ExportFormat::for_path))
+        .unwrap_or(ExportFormat::Csv);
+    py::init();
+    Python::attach(|py| {
+        let log_dir = resolve_log_dir(args.log_dir.as_ref());
+        let logs = list_logs(py, &log_dir)?;
+        let mut index = LogIndex::open(&log_dir);
+        let log = LogSelect::select_one(py, &mut index, &logs, &args.log);
+        index.save();
+        let log = log?;
+        let export = match &args.output {
+            Some(_) => {
+                let pb = cli::spinner();
+                pb.start("Reading log");
+                let eval_log = read_log(py, &log.log().name);
+                pb.clear();
+                export_data(log.log(), &eval_log?)
+            }
+            None => export_data(log.log(), &read_log(py, &log.log().name)?),
+        };
+
+        match &args.output {
+            Some(path) => {
+                let mut f = BufWriter::new(File::create(path)?);
+                write_export(&mut f, &export, format)?;
+                f.flush()?;
+                println!(
+                    "Exported {} {} to {}",
+                    export.rows.len(),
+                    plural!("sample", export.rows.len()),
+                    path.to_string_lossy()
+                );
+                Ok(())
+            }
+            None => ignore_broken_pipe(write_export(&mut io::stdout().lock(), &export, format)),
+        }
+    })
+}
+
+fn export_data(log: &EvalLogInfo, eval_log: &EvalLog) -> Export {
+    let samples = eval_log.samples.as_deref().unwrap_or_default();
+    let score_names = samples
+        .iter()
+        .filter_map(|s| s.scores.as_ref())
+        .flat_map(|scores| scores.keys())
+        .unique()
+        .sorted()
+        .collect_vec();
+
+    let mut columns = ["id", "epoch", "input", "target", "output"]
+        .map(String::from)
+        .to_vec();
+    for name in &score_names {
+        columns.push(format!("score.{name}"));
+        columns.push(format!("answer.{name}"));
+        columns.push(format!("explanation.{name}"));
+    }
+    columns.extend(["input_tokens", "output_tokens", "total_tokens", "error"].map(String::from));
+
+    let rows = samples
+        .iter()
+        .map(|sample| sample_row(sample, &score_names))
+        .collect();
+
+    let mut summary = vec![
+        ("Log", log.log_id.clone()),
+        ("Task", eval_log.eval.task.clone()),
+        ("Model", eval_log.eval.model.clone()),
+        ("Created", eval_log.eval.created.to_rfc3339()),
+        ("Status", eval_log.status.to_string()),
+    ];
+    if let Some(results) = eval_log.results.as_ref()
+        && let Some(accuracy) = results.first_accuracy()
+    {
+        summary.push((
+            "Accuracy",
+            match results.first_stderr() {
+                Some(stderr) => format!("{accuracy:.3} ± {stderr:.3}"),
+                None => format!("{accuracy:.3}"),
+            },
+        ));
+    }
+
+    Export {
+        title: eval_log.eval.task.clone(),
+        summary,
+        columns,
+        rows,
+    }
+}
+
+fn sample_row(sample: &EvalSample, score_names: &[&String]) -> Vec<Value> {
+    let target = sample.target.to_vec();
+    let mut row = vec![
+        Value::from(&sample.id),
+        sample.epoch.into(),
+        sample.input_text().into(),
+        match target.as_slice() {
+            [s] => s.as_str().into(),
+            l => l.into(),
+        },
+        sample.output.completion.as_str().into(),
+    ];
+    for name in score_names {
+        let score = sample.scores.as_ref().and_then(|scores| scores.get(*name));
+        row.push(
+            score
+                .and_then(|s| serde_json::to_value(&s.value).ok())
+                .unwrap_or_default(),
+        );
+        row.push(score.and_then(|s| s.answer.as_deref()).into());
+        row.push(score.and_then(|s| s.explanation.as_deref()).into());
+    }
+    // Token usage is summed across models used by the sample (e.g.
+    // model graded scorers), not only the model output
+    let usage = (!sample.model_usage.is_empty()).then(|| {
+        sample.model_usage.values().fold((0, 0, 0), |(i, o, t), u| {
+            (i + u.input_tokens, o + u.output_tokens, t + u.total_tokens)
+        })
+    });
+    row.push(usage.map(|(i, _, _)| i).into());
+    row.push(usage.map(|(_, o, _)| o).into());
+    row.push(usage.map(|(_, _, t)| t).into());
+    let errors = sample
+        .errors()
+        .into_iter()
+        .map(|e| e.message.as_str())
+        .chain(sample.output.error.as_deref())
+        .join("\n");
+    row.push(if errors.is_empty() {
+        Value::Null
+    } else {
+        errors.into()
+    });
+    row
+}
+
+fn write_export(w: &mut dyn Write, export: &Export, format: ExportFormat) -> io::Result<()> {
+    match format {
+        ExportFormat::Csv => write_csv(w, export),
+        ExportFormat::Jsonl => write_jsonl(w, export),
+        ExportFormat::Md => write_md(w, export),
+        ExportFormat::Html => write_html(w, export),
+    }
+}
+
+fn write_csv(w: &mut dyn Write, export: &Export) -> io::Result<()> {
+    let mut csv = csv::Writer::from_writer(w);
+    csv.write_record(&export.columns)
+        .map_err(io::Error::other)?;
+    for row in &export.rows {
+        csv.write_record(row.iter().map(csv_cell))
+            .map_err(io::Error::other)?;
+    }
+    csv.flush()
+}
+
+fn write_jsonl(w: &mut dyn Write, export: &Export) -> io::Result<()> {
+    for row in &export.rows {
+        let record = export
+            .columns
+            .iter()
+            .cloned()
+            .zip(row.iter().cloned())
+            .collect::<Map<_, _>>();
+        writeln!(w, "{}", Value::Object(record))?;
+    }
+    Ok(())
+}
+
+fn write_md(w: &mut dyn Write, export: &Export) -> io::Result<()> {
+    writeln!(w, "# {}\n", md_cell(&export.title))?;
+    for (label, val) in &export.summary {
+        writeln!(w, "- **{label}:** {}", md_cell(val))?;
+    }
+    writeln!(w, "\n## Samples\n")?;
+    writeln!(
+        w,
+        "| {} |",
+        export.columns.iter().map(|c| md_cell(c)).join(" | ")
+    )?;
+    writeln!(w, "|{}", "---|".repeat(export.columns.len()))?;
+    for row in &export.rows {
+        writeln!(
+            w,
+            "| {} |",
+            row.iter().map(|val| md_cell(&csv_cell(val))).join(" | ")
+        )?;
+    }
+    Ok(())
+}
+
+/// Escapes a value for use in a Markdown table cell.
+fn md_cell(s: &str) -> String {
+    s.trim()
+        .replace('\\', "\\\\")
+        .replace('|', "\\|")
+        .replace("\r\n", "<br>")
+        .replace('\n', "<br>")
+}
+
+const HTML_STYLE: &str = "\
+body { font-family: system-ui, sans-serif; margin: 2em; color: #222; }
+table { border-collapse: collapse; margin-bottom: 2em; }
+th, td { border: 1px solid #ccc; padding: 4px 8px; text-align: left; vertical-align: top; }
+th { background: #f4f4f4; }
+td { white-space: pre-wrap; max-width: 40em; }
+.summary th { background: none; font-weight: normal; color: #666; }";
+
+fn write_html(w: &mut dyn Write, export: &Export) -> io::Result<()> {
+    let title = html_escape(&export.title);
+    writeln!(w, "<!DOCTYPE html>")?;
+    writeln!(w, "<html>\n<head>")?;
+    writeln!(w, "<meta charset=\"utf-8\">")?;
+    writeln!(w, "<title>{title}</title>")?;
+    writeln!(w, "<style>\n{HTML_STYLE}\n</style>")?;
+    writeln!(w, "</head>\n<body>")?;
+    writeln!(w, "<h1>{title}</h1>")?;
+    writeln!(w, "<table class=\"summary\">")?;
+    for (label, val) in &export.summary {
+        writeln!(w, "<tr><th>{label}</th><td>{}</td></tr>", html_escape(val))?;
+    }
+    writeln!(w, "</table>")?;
+    writeln!(w, "<h2>Samples</h2>")?;
+    writeln!(w, "<table class=\"samples\">")?;
+    writeln!(
+        w,
+        "<tr>{}</tr>",
+        export
+            .columns
+            .iter()
+            .map(|c| format!("<th>{}</th>", html_escape(c)))
+            .join("")
+    )?;
+    for row in &export.rows {
+        writeln!(
+            w,
+            "<tr>{}</tr>",
+            row.iter()
+                .map(|val| format!("<td>{}</td>", html_escape(&csv_cell(val))))
+                .join("")
+        )?;
+    }
+    writeln!(w, "</table>")?;
+    writeln!(w, "</body>\n</html>")
+}
+
+fn html_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{Value, json};
+
+    use crate::inspect::log::EvalSample;
+
+    use super::{Export, html_escape, md_cell, sample_row, write_csv};
+
+    #[test]
+    fn test_sample_row_tokens() {
+        let sample: EvalSample = serde_json::from_value(json!({
+            "id": 1, "epoch": 1, "input": "hi",
+            "output": {
+                "choices": [], "completion": "hello",
+                "usage": {"input_tokens": 1, "output_tokens": 1, "total_tokens": 2}
+            },
+            "model_usage": {
+                "openai/gpt-4o": {"input_tokens": 10, "output_tokens": 5, "total_tokens": 15},
+                "openai/gpt-4o-mini": {"input_tokens": 3, "output_tokens": 2, "total_tokens": 5}
+            }
+        }))
+        .unwrap();
+        let row = sample_row(&sample, &[]);
+        assert_eq!(row[5..8], [json!(13), json!(7), json!(20)]);
+        assert_eq!(row[8], Value::Null);
+    }
+
+    #[test]
+    fn test_md_cell() {
+        assert_eq!(md_cell("  a | b  "), "a \\| b");
+        assert_eq!(md_cell("a\\b"), "a\\\\b");
+        assert_eq!(
+            md_cell("line 1\r\nline 2\nline 3"),
+            "line 1<br>line 2<br>line 3"
+        );
+    }
+
+    #[test]
+    fn test_html_escape() {
+        assert_eq!(
+            html_escape("<a href=\"x\">Q&A</a>"),
+            "&lt;a href=&quot;x&quot;&gt;Q&amp;A&lt;/a&gt;"
+        );
+        assert_eq!(html_escape("&lt;"), "&amp;lt;");
+    }
+
+    #[test]
+    fn test_write_csv() {
+        let export = Export {
+            title: "hello".into(),
+            summary: vec![],
+            columns: ["id", "output", "score", "tags"].map(String::from).to_vec(),
+            rows: vec![
+                vec![json!(1), json!("a, \"b\"\nc"), json!(0.5), json!(["x"])],
+                vec![json!("two"), Value::Null, json!("C"), Value::Null],
+            ],
+        };
+        let mut buf = Vec::new();
+        write_csv(&mut buf, &export).unwrap();
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            "id,output,score,tags\n\
+             1,\"a, \"\"b\"\"\nc\",0.5,\"[\"\"x\"\"]\"\n\
+             two,,C,\n"
+        );
+    }
+}
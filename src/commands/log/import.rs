@@ -0,0 +1,436 @@
+use std::{
+    collections::HashMap,
+    fs::{self, File},
+    io::{self, BufReader, Read},
+    path::{Component, Path, PathBuf},
+};
+
+use clap::Args as ArgsTrait;
+use cliclack as cli;
+use console::style;
+use flate2::read::GzDecoder;
+use pyo3::Python;
+use tabled::{
+    builder::Builder,
+    settings::{
+        Color,
+        object::{Columns, Object, Rows},
+        themes::Colorization,
+    },
+};
+
+use crate::{
+    commands::log::archive::{LOGS_DIR, MANIFEST_PATH, Manifest, ManifestLog},
+    dialog::{DialogResult, handle_dialog_result},
+    error::Error,
+    inspect::{
+        log::{EvalLogInfo, list_logs, resolve_log_dir},
+        log_file,
+    },
+    plural, py,
+    result::Result,
+    theme::Colors,
+    util::{TableExt, TempDir, fit_path_name, relpath, term_width, wrap},
+};
+
+#[derive(ArgsTrait, Debug)]
+pub struct Args {
+    /// Log bundle to import (created with 'gage log archive')
+    #[arg(value_name = "BUNDLE")]
+    bundle: PathBuf,
+
+    /// Log directory
+    #[arg(long)]
+    log_dir: Option<PathBuf>,
+
+    /// Don't prompt for dialog
+    #[arg(short, long)]
+    yes: bool,
+}
+
+/// Import action for a bundled log.
+enum Action {
+    Import,
+    Duplicate,
+    Conflict(String),
+}
+
+pub fn main(args: Args) -> Result<()> {
+    handle_dialog_result(import(args))
+}
+
+fn import(args: Args) -> Result<DialogResult> {
+    cli::intro(style("Import logs").bold())?;
+    // Bundled logs are extracted here and removed when done
+    let staging = TempDir::create("gage-import")?;
+    let (manifest, files) = read_bundle(&args.bundle, staging.path())?;
+    let log_dir = resolve_log_dir(args.log_dir.as_ref());
+
+    py::init();
+    let existing = Python::attach(|py| list_logs(py, &log_dir))?
+        .into_iter()
+        .map(|log| (log.log_id, log.name))
+        .collect::<HashMap<_, _>>();
+    let mut actions = manifest
+        .logs
+        .iter()
+        .map(|log| log_action(log, &files, &existing))
+        .collect::<Result<Vec<_>>>()?;
+
+    let import_count = actions
+        .iter()
+        .filter(|action| matches!(action, Action::Import))
+        .count();
+    cli::log::remark(bundle_table(&manifest.logs, &actions))?;
+    if import_count == 0 {
+        report_conflicts(&manifest.logs, &actions)?;
+        return Ok(DialogResult::Message(summary(0, &actions)));
+    }
+    if !args.yes {
+        let msg = format!(
+            "You are about to import {import_count} {} into {}. Continue?",
+            plural!("log", import_count),
+            relpath(&log_dir).to_string_lossy()
+        );
+        let confirmed = cli::confirm(wrap(&msg, term_width() - 4))
+            .initial_value(true)
+            .interact()?;
+        if !confirmed {
+            return Err(Error::Canceled);
+        }
+    }
+
+    let mut imported = 0;
+    for (log, action) in manifest.logs.iter().zip(actions.iter_mut()) {
+        if !matches!(action, Action::Import) {
+            continue;
+        }
+        match import_log(&log_dir, log, &files) {
+            Ok(_) => imported += 1,
+            Err(e) => *action = Action::Conflict(e.to_string()),
+        }
+    }
+    report_conflicts(&manifest.logs, &actions)?;
+    Ok(DialogResult::Message(summary(imported, &actions)))
+}
+
+/// Reads a bundle manifest and extracts log files to `staging`.
+///
+/// Returns the manifest and the extracted path for each bundle file.
+/// Files with paths that aren't under `LOGS_DIR` aren't extracted.
+fn read_bundle(path: &Path, staging: &Path) -> Result<(Manifest, HashMap<String, PathBuf>)> {
+    let f = File::open(path)
+        .map_err(|e| Error::general(format!("Cannot read {}: {e}", path.to_string_lossy())))?;
+    let mut tar = tar::Archive::new(GzDecoder::new(BufReader::new(f)));
+    let mut manifest = None;
+    let mut files = HashMap::new();
+    let invalid = |e: &dyn std::fmt::Display| {
+        Error::general(format!(
+            "{} is not a valid log bundle: {e}",
+            path.to_string_lossy()
+        ))
+    };
+    for entry in tar.entries().map_err(|e| invalid(&e))? {
+        let mut entry = entry.map_err(|e| invalid(&e))?;
+        let name = entry
+            .path()
+            .map_err(|e| invalid(&e))?
+            .to_string_lossy()
+            .to_string();
+        if name == MANIFEST_PATH {
+            let mut bytes = Vec::with_capacity(entry.size() as usize);
+            entry.read_to_end(&mut bytes).map_err(|e| invalid(&e))?;
+            manifest = Some(serde_json::from_slice::<Manifest>(&bytes).map_err(|e| invalid(&e))?);
+        } else if is_safe_path(&name) {
+            let dest = staging.join(&name);
+            fs::create_dir_all(dest.parent().unwrap())?;
+            io::copy(&mut entry, &mut File::create(&dest)?).map_err(|e| invalid(&e))?;
+            files.insert(name, dest);
+        }
+    }
+    let manifest = manifest.ok_or_else(|| invalid(&format!("missing {MANIFEST_PATH}")))?;
+    Ok((manifest, files))
+}
+
+/// Returns the action for a bundled log.
+///
+/// `existing` maps log IDs to log names in the target log directory.
+/// The log ID is read from the bundled file name rather than the
+/// manifest so that it's consistent with the imported log.
+fn log_action(
+    log: &ManifestLog,
+    files: &HashMap<String, PathBuf>,
+    existing: &HashMap<String, String>,
+) -> Result<Action> {
+    if !is_safe_path(&log.file) {
+        return Ok(Action::Conflict(format!("invalid path {}", log.file)));
+    }
+    let Some(path) = files.get(&log.file) else {
+        return Ok(Action::Conflict(format!(
+            "{} missing from bundle",
+            log.file
+        )));
+    };
+    let Some(log_id) = EvalLogInfo::log_id_for_name(&log.file) else {
+        return Ok(Action::Conflict(format!("invalid log name {}", log.file)));
+    };
+    match existing.get(log_id) {
+        Some(name) if log_file::read_log_bytes(name)? == fs::read(path)? => Ok(Action::Duplicate),
+        Some(name) => Ok(Action::Conflict(format!(
+            "log exists with different contents ({})",
+            name.strip_prefix("file://").unwrap_or(name)
+        ))),
+        None => Ok(Action::Import),
+    }
+}
+
+/// Writes a bundled log and its sidecar files to `log_dir`.
+///
+/// Returns the imported log name.
+fn import_log(
+    log_dir: &Path,
+    log: &ManifestLog,
+    files: &HashMap<String, PathBuf>,
+) -> Result<String> {
+    let path = files
+        .get(&log.file)
+        .ok_or_else(|| Error::general(format!("{} missing from bundle", log.file)))?;
+    let rel_path = &log.file[LOGS_DIR.len()..];
+    let name = log_file::write_log_bytes(log_dir, rel_path, &fs::read(path)?)?;
+    for suffix in log_file::SIDECAR_SUFFIXES {
+        if let Some(path) = files.get(&format!("{}{suffix}", log.file)) {
+            log_file::write_sidecar(&name, suffix, &fs::read(path)?)?;
+        }
+    }
+    Ok(name)
+}
+
+/// Returns the ID of a bundled log, read from its file name.
+fn bundle_log_id(log: &ManifestLog) -> &str {
+    EvalLogInfo::log_id_for_name(&log.file).unwrap_or(&log.id)
+}
+
+/// Returns true if a bundle path is a relative path under `LOGS_DIR`.
+fn is_safe_path(path: &str) -> bool {
+    path.strip_prefix(LOGS_DIR).is_some_and(|rel| {
+        !rel.is_empty()
+            && Path::new(rel)
+                .components()
+                .all(|c| matches!(c, Component::Normal(_)))
+    })
+}
+
+fn bundle_table(logs: &[ManifestLog], actions: &[Action]) -> String {
+    let mut table = Builder::new();
+    table.push_record(["Id", "Task", "Status", "Model", "Created", "Action"]);
+    let mut colored_cells = Vec::new();
+    for (i, (log, action)) in logs.iter().zip(actions).enumerate() {
+        let action = match action {
+            Action::Import => "import",
+            Action::Duplicate => {
+                colored_cells.push((i + 1, Colors::dim()));
+                "duplicate"
+            }
+            Action::Conflict(_) => {
+                colored_cells.push((i + 1, Color::FG_RED));
+                "conflict"
+            }
+        };
+        let log_id = bundle_log_id(log);
+        table.push_record([
+            log_id.split_at_checked(6).map(|(s, _)| s).unwrap_or(log_id),
+            &log.task,
+            &log.status.to_string(),
+            &fit_path_name(&log.model, 20),
+            log.created.split('T').next().unwrap_or_default(),
+            action,
+        ]);
+    }
+    let mut table = table.build();
+    table
+        .with_term_fit()
+        .with_rounded()
+        .with_col_labels()
+        // Id is dim
+        .with(Colorization::exact(
+            [Colors::dim()],
+            Columns::one(0).intersect(Rows::new(1..)),
+        ))
+        // Task is yellow
+        .with(Colorization::exact(
+            [Color::FG_BRIGHT_YELLOW],
+            Columns::one(1).intersect(Rows::new(1..)),
+        ));
+    for (row, color) in colored_cells {
+        table.with(Colorization::exact(
+            [color],
+            Columns::one(5).intersect(Rows::one(row)),
+        ));
+    }
+    table.to_string()
+}
+
+fn report_conflicts(logs: &[ManifestLog], actions: &[Action]) -> Result<()> {
+    let conflicts = logs
+        .iter()
+        .zip(actions)
+        .filter_map(|(log, action)| match action {
+            Action::Conflict(msg) => Some(format!("{}: {msg}", bundle_log_id(log))),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    if !conflicts.is_empty() {
+        cli::log::warning(format!(
+            "Logs not imported due to conflicts:\n{}",
+            conflicts.join("\n")
+        ))?;
+    }
+    Ok(())
+}
+
+fn summary(imported: usize, actions: &[Action]) -> String {
+    let duplicates = actions
+        .iter()
+        .filter(|action| matches!(action, Action::Duplicate))
+        .count();
+    let conflicts = actions
+        .iter()
+        .filter(|action| matches!(action, Action::Conflict(_)))
+        .count();
+    let mut msg = format!("{imported} {} imported", plural!("log", imported));
+    if duplicates > 0 {
+        msg.push_str(&format!(
+            ", {duplicates} {} skipped",
+            plural!("duplicate", duplicates)
+        ));
+    }
+    if conflicts > 0 {
+        msg.push_str(&format!(", {conflicts} {}", plural!("conflict", conflicts)));
+    }
+    msg
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, fs, io::Write};
+
+    use zip::{ZipWriter, write::SimpleFileOptions};
+
+    use crate::{
+        commands::log::archive::BundleWriter, inspect::log_file::list_log_files, py, util::TempDir,
+    };
+
+    use super::{Action, import_log, is_safe_path, log_action, read_bundle};
+
+    #[test]
+    fn test_safe_path() {
+        assert!(is_safe_path("logs/a.eval"));
+        assert!(is_safe_path("logs/sub/a.eval"));
+        assert!(!is_safe_path("logs/"));
+        assert!(!is_safe_path("a.eval"));
+        assert!(!is_safe_path("logs/../a.eval"));
+        assert!(!is_safe_path("logs//etc/passwd"));
+    }
+
+    #[test]
+    fn test_archive_import() {
+        py::init();
+        let tmp = TempDir::create("gage-import-test").unwrap();
+        let (src, dest, staging) = (
+            tmp.path().join("src"),
+            tmp.path().join("dest"),
+            tmp.path().join("staging"),
+        );
+        for dir in [&src, &dest, &staging] {
+            fs::create_dir_all(dir).unwrap();
+        }
+        let write_eval = |dir: &std::path::Path, id: &str, model: &str| {
+            let start = format!(
+                r#"{{
+                    "version": 2,
+                    "eval": {{
+                        "eval_id": "E{id}", "run_id": "R1", "created": "2025-01-01T10:00:00+00:00",
+                        "task": "hello", "task_id": "T1", "dataset": {{}}, "model": "{model}"
+                    }},
+                    "plan": {{"name": "plan", "steps": []}}
+                }}"#
+            );
+            let path = dir.join(format!("2025-01-01T10-00-00+00-00_hello_{id}.eval"));
+            let mut zip = ZipWriter::new(fs::File::create(&path).unwrap());
+            zip.start_file("_journal/start.json", SimpleFileOptions::default())
+                .unwrap();
+            zip.write_all(start.as_bytes()).unwrap();
+            zip.finish().unwrap();
+        };
+
+        // Archive three logs, one with notes
+        for id in ["A1", "B1", "C1"] {
+            write_eval(&src, id, "mockllm/model");
+        }
+        let notes = r#"{"version": 1, "notes": []}"#;
+        fs::write(
+            src.join("2025-01-01T10-00-00+00-00_hello_C1.eval.notes.json"),
+            notes,
+        )
+        .unwrap();
+        let bundle_path = tmp.path().join("logs.tar.gz");
+        let mut bundle = BundleWriter::create(&bundle_path).unwrap();
+        for log in list_log_files(&src, false).unwrap() {
+            bundle.add(&src, &log).unwrap();
+        }
+        bundle.finish().unwrap();
+
+        // A1 is already imported, B1 exists with different contents,
+        // and C1 is new
+        fs::copy(
+            src.join("2025-01-01T10-00-00+00-00_hello_A1.eval"),
+            dest.join("2025-01-01T10-00-00+00-00_hello_A1.eval"),
+        )
+        .unwrap();
+        write_eval(&dest, "B1", "mockllm/other");
+        let existing = list_log_files(&dest, false)
+            .unwrap()
+            .into_iter()
+            .map(|log| (log.log_id, log.name))
+            .collect::<HashMap<_, _>>();
+
+        let (manifest, files) = read_bundle(&bundle_path, &staging).unwrap();
+        assert_eq!(manifest.logs.len(), 3);
+        let mut imported = Vec::new();
+        for log in &manifest.logs {
+            match log_action(log, &files, &existing).unwrap() {
+                Action::Import => {
+                    import_log(&dest, log, &files).unwrap();
+                    imported.push(log.id.as_str());
+                }
+                Action::Duplicate => assert_eq!(log.id, "A1"),
+                Action::Conflict(msg) => {
+                    assert_eq!(log.id, "B1");
+                    assert!(msg.starts_with("log exists with different contents"));
+                }
+            }
+        }
+        assert_eq!(imported, ["C1"]);
+        let name = "2025-01-01T10-00-00+00-00_hello_C1.eval";
+        assert_eq!(
+            fs::read(dest.join(name)).unwrap(),
+            fs::read(src.join(name)).unwrap()
+        );
+        assert_eq!(
+            fs::read_to_string(dest.join(format!("{name}.notes.json"))).unwrap(),
+            notes
+        );
+
+        // Importing again skips the imported log
+        let existing = list_log_files(&dest, false)
+            .unwrap()
+            .into_iter()
+            .map(|log| (log.log_id, log.name))
+            .collect::<HashMap<_, _>>();
+        let c1 = manifest.logs.iter().find(|log| log.id == "C1").unwrap();
+        assert!(matches!(
+            log_action(c1, &files, &existing).unwrap(),
+            Action::Duplicate
+        ));
+    }
+}
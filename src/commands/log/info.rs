@@ -13,8 +13,17 @@ use tabled::{
 };
 
 use crate::{
+    commands::log::{
+        common::{LogRecord, LogSelect},
+        query::LogQuery,
+    },
     error::Error,
-    inspect::log::{EvalLogInfo, list_logs, read_log_header, resolve_log_dir},
+    inspect::{
+        log::{EvalLogInfo, list_logs, resolve_log_dir},
+        log_grades::LogGrades,
+        log_index::{LogIndex, LogSummary},
+    },
+    output::{FormatArgs, print_record},
     py,
     result::Result,
     util::{TableExt, term_width, wrap},
@@ -22,8 +31,11 @@ use crate::{
 
 #[derive(ArgsTrait, Debug)]
 pub struct Args {
-    /// Eval ID
-    #[arg()]
+    /// Eval ID or filter expression
+    ///
+    /// A filter expression must match one log (see 'gage log list
+    /// --help').
+    #[arg(value_name = "ID")]
     id: String,
 
     /// Log directory
@@ -33,6 +45,9 @@ pub struct Args {
     /// Show more detail
     #[arg(short, long)]
     verbose: bool,
+
+    #[command(flatten)]
+    format: FormatArgs,
 }
 
 pub fn main(args: Args) -> Result<()> {
@@ -40,6 +55,11 @@ pub fn main(args: Args) -> Result<()> {
     Python::attach(|py| {
         let log_dir = resolve_log_dir(args.log_dir.as_ref());
         let logs = list_logs(py, &log_dir)?;
+        let mut index = LogIndex::open(&log_dir);
+        if LogQuery::is_query(&args.id) {
+            let log = LogSelect::select_one(py, &mut index, &logs, &args.id)?;
+            return print_info(py, &mut index, log.log(), &args);
+        }
         let matches = logs
             .iter()
             .filter(|log| log.log_id.starts_with(&args.id))
@@ -69,61 +89,100 @@ pub fn main(args: Args) -> Result<()> {
                 ),
             )));
         }
-        let log = matches[0];
-        let header = read_log_header(py, &log.name)?;
-
-        let mut table = Builder::new();
-        table.push_record(["Log", &log.log_id]);
-        table.push_record(["Task", &header.eval.task]);
-        table.push_record(["Created", &header.eval.created.to_human()]);
-        table.push_record(["Status", &header.status.to_string()]);
-        if let Some(error) = header.error.as_ref() {
-            table.push_record(["Error", &error.message]);
-        }
-        table.push_record([
-            "Dataset",
-            header.eval.dataset.name.as_deref().unwrap_or_default(),
-        ]);
-        table.push_record([
-            "Samples",
-            &header
-                .eval
-                .dataset
-                .evaluated_count()
-                .map(|n| n.to_string())
-                .unwrap_or_default(),
-        ]);
-        table.push_record(["Model", &header.eval.model]);
-        if args.verbose {
-            table.push_record(["File", &fmt_log_filename(log)]);
-            table.push_record(["Eval Id", &header.eval.eval_id]);
-            table.push_record(["Run Id", &header.eval.run_id]);
-        }
-        println!(
-            "{}",
-            table
-                .build()
-                .with_term_fit()
-                .with_row_labels()
-                .with_col_labels()
-                .with_rounded()
-                // Log Id - used as identifier so highlight with cyan
-                .with(Colorization::exact(
-                    [Color::FG_BRIGHT_CYAN],
-                    Columns::one(1).intersect(Rows::one(0))
-                ))
-                // Task - highlight with yello
-                .with(Colorization::exact(
-                    [Color::FG_BRIGHT_YELLOW],
-                    Columns::one(1).intersect(Rows::one(1))
-                ))
-        );
-        Ok(())
+        print_info(py, &mut index, matches[0], &args)
     })
 }
 
+fn print_info(py: Python<'_>, index: &mut LogIndex, log: &EvalLogInfo, args: &Args) -> Result<()> {
+    let summary = index.summary(py, log);
+    index.save();
+    let summary = LogSummary {
+        grades: LogGrades::open(&log.name)?.summary(),
+        ..summary?
+    };
+    if !args.format.is_table() {
+        return print_record(args.format.format(), &LogRecord::new(log, &summary));
+    }
+
+    let mut table = Builder::new();
+    table.push_record(["Log", &log.log_id]);
+    table.push_record(["Task", &summary.task]);
+    table.push_record(["Created", &summary.created.to_human()]);
+    table.push_record(["Status", &summary.status.to_string()]);
+    if let Some(error) = summary.error.as_ref() {
+        table.push_record(["Error", error]);
+    }
+    table.push_record(["Dataset", summary.dataset.as_deref().unwrap_or_default()]);
+    table.push_record([
+        "Samples",
+        &summary.samples.map(|n| n.to_string()).unwrap_or_default(),
+    ]);
+    table.push_record(["Model", &summary.model]);
+    table.push_record(["Accuracy", &fmt_accuracy(summary.accuracy, summary.stderr)]);
+    table.push_record([
+        "Human accuracy",
+        &fmt_grade_rate(
+            summary.grades.human_accuracy(),
+            summary.grades.graded(),
+            "graded",
+        ),
+    ]);
+    table.push_record([
+        "Agreement",
+        &fmt_grade_rate(
+            summary.grades.agreement(),
+            summary.grades.compared,
+            "compared",
+        ),
+    ]);
+    table.push_record(["Tags", &summary.tags.join(", ")]);
+    if args.verbose {
+        table.push_record(["File", &fmt_log_filename(log)]);
+        table.push_record(["Eval Id", &summary.eval_id]);
+        table.push_record(["Run Id", &summary.run_id]);
+    }
+    println!(
+        "{}",
+        table
+            .build()
+            .with_term_fit()
+            .with_row_labels()
+            .with_col_labels()
+            .with_rounded()
+            // Log Id - used as identifier so highlight with cyan
+            .with(Colorization::exact(
+                [Color::FG_BRIGHT_CYAN],
+                Columns::one(1).intersect(Rows::one(0))
+            ))
+            // Task - highlight with yello
+            .with(Colorization::exact(
+                [Color::FG_BRIGHT_YELLOW],
+                Columns::one(1).intersect(Rows::one(1))
+            ))
+    );
+    Ok(())
+}
+
+fn fmt_accuracy(accuracy: Option<f64>, stderr: Option<f64>) -> String {
+    match (accuracy, stderr) {
+        (Some(accuracy), Some(stderr)) => format!("{accuracy:.3} ± {stderr:.3}"),
+        (Some(accuracy), None) => format!("{accuracy:.3}"),
+        _ => String::new(),
+    }
+}
+
+fn fmt_grade_rate(rate: Option<f64>, count: usize, label: &str) -> String {
+    match rate {
+        Some(rate) => format!("{rate:.3} ({count} {label})"),
+        None => String::new(),
+    }
+}
+
 pub fn fmt_log_filename(log: &EvalLogInfo) -> String {
-    let file_name = PathBuf::from(log.name.strip_prefix("file://").expect("local files only"));
+    // Non-local logs are shown using their URI
+    let Some(file_name) = log.name.strip_prefix("file://").map(PathBuf::from) else {
+        return log.name.clone();
+    };
     let cwd = std::env::current_dir().unwrap();
     file_name
         .strip_prefix(cwd)
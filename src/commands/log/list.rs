@@ -2,20 +2,42 @@ use std::path::PathBuf;
 
 use clap::Args as ArgsTrait;
 use cliclack as cli;
-use console::style;
+use console::{Term, style};
 use pyo3::Python;
 
 use crate::{
-    commands::log::common::print_log_table,
+    commands::log::{
+        common::{print_log_records, print_log_table},
+        query::LogQuery,
+    },
     error::Error,
-    inspect::log::{LogFilter, list_logs_filter, resolve_log_dir},
+    inspect::{
+        log::{EvalLogInfo, LogFilter, list_logs_filter, resolve_log_dir},
+        log_index::LogIndex,
+        log_watch::LogDirWatch,
+    },
+    output::FormatArgs,
     py,
     result::Result,
-    util::term_height,
+    util::{relpath, term_height},
 };
 
 #[derive(ArgsTrait, Debug)]
 pub struct Args {
+    /// Show logs matching filters
+    ///
+    /// Filters have the form FIELD OP VALUE. FIELD is one of 'task',
+    /// 'status', 'model', 'dataset', 'type', 'tag', 'created', 'score'
+    /// (accuracy of the first scorer), or 'samples'. OP is one of '=',
+    /// '!=', '~' (matches regular expression), '<', '<=', '>', or
+    /// '>='. 'created' accepts a date (YYYY-MM-DD) or local time
+    /// (YYYY-MM-DDTHH:MM). Logs must match all filters.
+    ///
+    /// Filters may also be used to select logs for 'log delete', 'log
+    /// restore', 'log purge', 'log info', and 'log export'.
+    #[arg(value_name = "FILTER")]
+    filters: Vec<String>,
+
     /// Log directory
     #[arg(long)]
     log_dir: Option<PathBuf>,
@@ -35,6 +57,15 @@ pub struct Args {
     /// Display deleted logs
     #[arg(short, long)]
     deleted: bool,
+
+    /// Refresh the list as logs change
+    ///
+    /// Shows progress for running evals. Press Ctrl-C to stop.
+    #[arg(short, long)]
+    watch: bool,
+
+    #[command(flatten)]
+    format: FormatArgs,
 }
 
 impl From<&Args> for LogFilter {
@@ -58,41 +89,124 @@ pub fn main(args: Args) -> Result<()> {
     if args.all && args.limit.is_some() {
         return Err(Error::general("--all and --limit cannot both be used"));
     }
+    if args.watch && !args.format.is_table() {
+        return Err(Error::general(
+            "--watch cannot be used with --json or --format",
+        ));
+    }
 
     let log_dir = resolve_log_dir(args.log_dir.as_ref());
+    let queries = args
+        .filters
+        .iter()
+        .map(|filter| LogQuery::parse(filter))
+        .collect::<Result<Vec<_>>>()?;
 
     py::init();
     Python::attach(|py| {
-        let pb = cli::spinner();
-        pb.start("Reading logs");
-        let logs = list_logs_filter(py, &log_dir, &args)?;
-        pb.clear();
-
-        // Calc number of entries to show based on options
-        let count = std::cmp::min(
-            if args.all {
-                logs.len()
-            } else {
-                args.limit.unwrap_or_else(|| {
-                    let page_size = term_height() - 7;
-                    page_size * (args.more as usize + 1)
-                })
-            },
-            logs.len(),
-        );
+        let mut index = LogIndex::open(&log_dir);
+        if !args.format.is_table() {
+            let logs = list_logs_filter(py, &log_dir, &args)?;
+            let logs = filter_logs(py, &mut index, &logs, &queries);
+            // Show all logs unless limited
+            let count = match args.limit {
+                Some(limit) => std::cmp::min(limit, logs.len()),
+                None => logs.len(),
+            };
+            let result = print_log_records(
+                py,
+                &mut index,
+                logs[..count].iter().map(|(_, log)| *log),
+                args.format.format(),
+            );
+            index.save();
+            return result;
+        }
 
-        // Print table
-        print_log_table(py, logs[..count].iter());
+        if !args.watch {
+            let pb = cli::spinner();
+            pb.start("Reading logs");
+            let logs = list_logs_filter(py, &log_dir, &args)?;
+            let logs = filter_logs(py, &mut index, &logs, &queries);
+            pb.clear();
+            print_table(py, &mut index, &logs, &args);
+            return Ok(());
+        }
 
-        // If table truncated show what happened
-        if count < logs.len() {
+        // Show table until interrupted, refreshing when logs change
+        let mut watch = LogDirWatch::new(&log_dir);
+        let term = Term::stdout();
+        loop {
+            let logs = list_logs_filter(py, &log_dir, &args)?;
+            let logs = filter_logs(py, &mut index, &logs, &queries);
+            term.clear_screen()?;
+            print_table(py, &mut index, &logs, &args);
             println!(
                 "{}",
-                style(format!("Showing {} of {} (-m for more)", count, logs.len()))
-                    .dim()
-                    .italic()
+                style(format!(
+                    "Watching {} for changes (Ctrl-C to stop)",
+                    relpath(&log_dir).to_string_lossy()
+                ))
+                .dim()
+                .italic()
             );
+            watch.wait();
         }
-        Ok(())
     })
 }
+
+fn print_table(py: Python<'_>, index: &mut LogIndex, logs: &[(usize, &EvalLogInfo)], args: &Args) {
+    // Calc number of entries to show based on options
+    let count = std::cmp::min(
+        if args.all {
+            logs.len()
+        } else {
+            args.limit.unwrap_or_else(|| {
+                // Leave room for watch status
+                let page_size = term_height() - if args.watch { 8 } else { 7 };
+                page_size * (args.more as usize + 1)
+            })
+        },
+        logs.len(),
+    );
+
+    // Print table
+    print_log_table(py, index, logs[..count].iter().copied());
+    index.save();
+
+    // If table truncated show what happened
+    if count < logs.len() {
+        println!(
+            "{}",
+            style(format!("Showing {} of {} (-m for more)", count, logs.len()))
+                .dim()
+                .italic()
+        );
+    }
+}
+
+/// Returns logs matching queries with their log table positions.
+///
+/// Table positions are those of the unfiltered list so they can be
+/// used to select logs in other commands.
+fn filter_logs<'a>(
+    py: Python<'_>,
+    index: &mut LogIndex,
+    logs: &'a [EvalLogInfo],
+    queries: &[LogQuery],
+) -> Vec<(usize, &'a EvalLogInfo)> {
+    logs.iter()
+        .enumerate()
+        .map(|(i, log)| (i + 1, log))
+        .filter(|(_, log)| {
+            queries.is_empty()
+                || match index.summary(py, log) {
+                    Ok(summary) => queries.iter().all(|query| query.matches(&summary)),
+                    Err(err) => {
+                        log::error!("Error reading {}: {}", log.log_id, err);
+                        false
+                    }
+                }
+        })
+        .collect()
+}
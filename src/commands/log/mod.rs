@@ -1,14 +1,22 @@
 use clap::{Args as ArgsTrait, Subcommand};
 
-use crate::result::Result;
+use crate::{config::Config, result::Result};
 
-mod common;
+mod archive;
+pub mod common;
+mod compare;
 pub mod delete;
+mod export;
+mod import;
 mod info;
 pub mod list;
+mod notes;
+mod prune;
 mod purge;
+pub mod query;
 mod restore;
 pub mod review;
+mod tag;
 
 #[derive(ArgsTrait, Debug)]
 pub struct Args {
@@ -35,9 +43,53 @@ pub enum Cmd {
 
     /// Restore deleted logs
     Restore(restore::Args),
+
+    /// Show reviewer notes
+    ///
+    /// Notes are added to logs and samples using 'gage review'. Notes
+    /// are stored next to each log in 'LOG.notes.json'.
+    Notes(notes::Args),
+
+    /// Add or remove log tags
+    ///
+    /// Tags are stored in the log eval spec. Use '+TAG' to add a tag
+    /// and '-TAG' to remove a tag. Run type is derived from 'type:'
+    /// tags (e.g. 'type:eval').
+    Tag(tag::Args),
+
+    /// Delete logs using retention rules
+    ///
+    /// A log is pruned if it's selected by any rule. Pruned logs are
+    /// deleted and can be restored using 'gage log restore'.
+    ///
+    /// If rules aren't specified, uses the retention policy for the
+    /// active profile ('[profiles.NAME.retention]' in gage.toml) or the
+    /// project ('[retention]'). Policies support 'keep', 'older_than',
+    /// and 'status' keys.
+    Prune(prune::Args),
+
+    /// Compare samples and metrics of two logs
+    Compare(compare::Args),
+
+    /// Export log samples
+    Export(export::Args),
+
+    /// Archive logs to a bundle
+    ///
+    /// Bundles are gzipped tar files containing the selected log files
+    /// and a manifest describing each log. Use 'gage log import' to
+    /// import logs from a bundle.
+    Archive(archive::Args),
+
+    /// Import logs from a bundle
+    ///
+    /// Logs that already exist in the log directory are skipped. Logs
+    /// with the same Id but different contents are reported as
+    /// conflicts and are not imported.
+    Import(import::Args),
 }
 
-pub fn main(args: Args) -> Result<()> {
+pub fn main(args: Args, config: &Config) -> Result<()> {
     match args.cmd {
         Cmd::List(args) => list::main(args),
         Cmd::Review(args) => review::main(args),
@@ -45,5 +97,12 @@ pub fn main(args: Args) -> Result<()> {
         Cmd::Delete(args) => delete::main(args),
         Cmd::Purge(args) => purge::main(args),
         Cmd::Restore(args) => restore::main(args),
+        Cmd::Notes(args) => notes::main(args),
+        Cmd::Tag(args) => tag::main(args),
+        Cmd::Prune(args) => prune::main(args, config),
+        Cmd::Compare(args) => compare::main(args),
+        Cmd::Export(args) => export::main(args),
+        Cmd::Archive(args) => archive::main(args),
+        Cmd::Import(args) => import::main(args),
     }
 }
@@ -0,0 +1,158 @@
+use std::path::PathBuf;
+
+use clap::Args as ArgsTrait;
+use pyo3::Python;
+use serde::Serialize;
+use tabled::{
+    builder::Builder,
+    settings::{
+        Color,
+        object::{Columns, Object, Rows},
+        themes::Colorization,
+    },
+};
+
+use crate::{
+    commands::log::common::LogSelect,
+    inspect::{
+        log::{EvalLogInfo, list_logs, resolve_log_dir},
+        log_index::LogIndex,
+        log_notes::{LogNotes, Note},
+    },
+    output::{FormatArgs, print_records},
+    py,
+    result::Result,
+    theme::Colors,
+    util::TableExt,
+};
+
+#[derive(ArgsTrait, Debug)]
+pub struct Args {
+    /// Show notes for LOG
+    ///
+    /// LOG may be specified using its #, Id, range, or a filter
+    /// expression (see 'gage log list --help'). If not specified, shows
+    /// notes for all logs.
+    #[arg(value_name = "LOG")]
+    specs: Vec<String>,
+
+    /// Show notes containing TEXT (case insensitive)
+    #[arg(short, long, value_name = "TEXT")]
+    search: Option<String>,
+
+    /// Log directory
+    #[arg(long)]
+    log_dir: Option<PathBuf>,
+
+    #[command(flatten)]
+    format: FormatArgs,
+}
+
+/// Reviewer note record used for JSON and CSV output.
+#[derive(Serialize, Debug)]
+struct NoteRecord<'a> {
+    /// Log ID.
+    log_id: &'a str,
+
+    /// Task name.
+    task: &'a str,
+
+    /// Sample ID, or null for log notes.
+    sample_id: Option<&'a str>,
+
+    /// Sample epoch, or null for log notes.
+    epoch: Option<i64>,
+
+    /// Note text.
+    text: &'a str,
+
+    /// User that last updated the note.
+    author: Option<&'a str>,
+
+    /// Time the note was last updated (RFC 3339).
+    updated: &'a str,
+}
+
+pub fn main(args: Args) -> Result<()> {
+    let log_specs = LogSelect::parse_specs(&args.specs)?;
+    let log_dir = resolve_log_dir(args.log_dir.as_ref());
+    let search = args.search.as_ref().map(|s| s.to_lowercase());
+
+    py::init();
+    let logs = Python::attach(|py| {
+        let logs = list_logs(py, &log_dir)?;
+        if log_specs.is_empty() {
+            return Result::Ok(logs);
+        }
+        let mut index = LogIndex::open(&log_dir);
+        let selected = LogSelect::select_logs(py, &mut index, &logs, &log_specs)?;
+        index.save();
+        Ok(selected.into_iter().map(|log| log.log().clone()).collect())
+    })?;
+
+    let notes = logs
+        .iter()
+        .map(|log| Ok((log, LogNotes::open(&log.name)?)))
+        .collect::<Result<Vec<_>>>()?;
+    let records = notes
+        .iter()
+        .flat_map(|(log, notes)| notes.iter().map(move |note| note_record(log, note)))
+        .filter(|record| {
+            search
+                .as_ref()
+                .is_none_or(|search| record.text.to_lowercase().contains(search))
+        })
+        .collect::<Vec<_>>();
+    if !args.format.is_table() {
+        return print_records(args.format.format(), &records);
+    }
+    println!("{}", notes_table(&records));
+    Ok(())
+}
+
+fn note_record<'a>(log: &'a EvalLogInfo, note: &'a Note) -> NoteRecord<'a> {
+    NoteRecord {
+        log_id: &log.log_id,
+        task: &log.task,
+        sample_id: note.key.sample_id.as_deref(),
+        epoch: note.key.epoch,
+        text: &note.text,
+        author: note.author.as_deref(),
+        updated: &note.updated,
+    }
+}
+
+fn notes_table(records: &[NoteRecord]) -> String {
+    let mut table = Builder::new();
+    table.push_record(["Log", "Task", "Sample", "Epoch", "Note", "Author"]);
+    for record in records {
+        table.push_record([
+            record
+                .log_id
+                .split_at_checked(6)
+                .map(|(s, _)| s)
+                .unwrap_or(record.log_id),
+            record.task,
+            record.sample_id.unwrap_or("(log)"),
+            &record.epoch.map(|n| n.to_string()).unwrap_or_default(),
+            record.text,
+            record.author.unwrap_or_default(),
+        ]);
+    }
+    table
+        .build()
+        .with_term_fit()
+        .with_rounded()
+        .with_col_labels()
+        // Log is dim
+        .with(Colorization::exact(
+            [Colors::dim()],
+            Columns::one(0).intersect(Rows::new(1..)),
+        ))
+        // Task is yellow
+        .with(Colorization::exact(
+            [Color::FG_BRIGHT_YELLOW],
+            Columns::one(1).intersect(Rows::new(1..)),
+        ))
+        .to_string()
+}
@@ -0,0 +1,387 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    path::PathBuf,
+};
+
+use chrono::{DateTime, TimeDelta, Utc};
+use clap::Args as ArgsTrait;
+
+use crate::{
+    commands::log::common::{LogOpDialog, LogOpSuccessMap, SelectedLog, SelectedLogs},
+    config::{Config, Retention},
+    dialog::handle_dialog_result,
+    env,
+    error::Error,
+    inspect::{
+        log::{EvalStatus, resolve_log_dir},
+        log_index::LogSummary,
+    },
+    plural,
+    py::EpochMillis,
+    result::Result,
+    util::relpath,
+};
+
+#[derive(ArgsTrait, Debug)]
+pub struct Args {
+    /// Keep the N most recent logs for each task and model
+    #[arg(long, value_name = "N")]
+    keep: Option<usize>,
+
+    /// Prune logs created more than DURATION ago (e.g. '30d' or '12h')
+    #[arg(long, value_name = "DURATION", value_parser = parse_duration)]
+    older_than: Option<TimeDelta>,
+
+    /// Prune logs with STATUS (may be used more than once)
+    ///
+    /// STATUS may be one of 'started', 'success', 'cancelled', or
+    /// 'error'. Started logs modified in the last 24 hours are assumed
+    /// to be running and are not pruned.
+    #[arg(long, value_name = "STATUS", value_parser = parse_status)]
+    status: Vec<EvalStatus>,
+
+    /// Log directory
+    #[arg(long)]
+    log_dir: Option<PathBuf>,
+
+    /// Show logs that would be pruned without deleting them
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Don't prompt for dialog
+    #[arg(short, long)]
+    yes: bool,
+}
+
+/// Time since a started log was last modified before it's considered
+/// stale rather than running.
+///
+/// Logs for running evals are never pruned as Inspect is still writing
+/// them.
+const RUNNING_TIMEOUT: TimeDelta = TimeDelta::hours(24);
+
+/// Rules used to select logs to prune.
+///
+/// A log is pruned if it's selected by any rule.
+#[derive(Debug, Default)]
+struct RetentionRules {
+    keep: Option<usize>,
+    older_than: Option<TimeDelta>,
+    status: Vec<EvalStatus>,
+}
+
+impl RetentionRules {
+    fn from_args(args: &Args) -> Self {
+        Self {
+            keep: args.keep,
+            older_than: args.older_than,
+            status: args.status.clone(),
+        }
+    }
+
+    fn from_config(retention: &Retention, config: &Config) -> Result<Self> {
+        let invalid = |msg: String| {
+            Error::general(format!(
+                "Invalid retention in {}: {msg}",
+                relpath(&config.path).to_string_lossy()
+            ))
+        };
+        Ok(Self {
+            keep: retention.keep,
+            older_than: retention
+                .older_than
+                .as_deref()
+                .map(parse_duration)
+                .transpose()
+                .map_err(invalid)?,
+            status: retention
+                .status
+                .iter()
+                .map(|s| parse_status(s))
+                .collect::<std::result::Result<_, _>>()
+                .map_err(invalid)?,
+        })
+    }
+
+    fn is_empty(&self) -> bool {
+        self.keep.is_none() && self.older_than.is_none() && self.status.is_empty()
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.keep == Some(0) {
+            return Err(Error::general("keep must be at least 1"));
+        }
+        Ok(())
+    }
+
+    /// Returns the positions of logs to prune.
+    ///
+    /// Each log is paired with its last modified time, if known.
+    fn select(
+        &self,
+        logs: &[(&LogSummary, Option<&EpochMillis>)],
+        now: DateTime<Utc>,
+    ) -> BTreeSet<usize> {
+        let mut selected = BTreeSet::new();
+        for (pos, (log, _)) in logs.iter().enumerate() {
+            let too_old = self
+                .older_than
+                .is_some_and(|age| *log.created.as_datetime() < now - age);
+            if too_old || self.status.contains(&log.status) {
+                selected.insert(pos);
+            }
+        }
+        if let Some(keep) = self.keep {
+            let mut groups: HashMap<(&str, &str), Vec<usize>> = HashMap::new();
+            for (pos, (log, _)) in logs.iter().enumerate() {
+                groups
+                    .entry((log.task.as_str(), log.model.as_str()))
+                    .or_default()
+                    .push(pos);
+            }
+            for mut group in groups.into_values() {
+                // Most recent first
+                group.sort_by(|a, b| logs[*b].0.created.cmp(&logs[*a].0.created));
+                selected.extend(group.into_iter().skip(keep));
+            }
+        }
+        selected.retain(|pos| {
+            let (log, modified) = logs[*pos];
+            !is_running(log, modified, now)
+        });
+        selected
+    }
+}
+
+/// Returns true if a log is for a running eval.
+///
+/// A log is running if its status is started and it was modified (or
+/// created if the modified time isn't known) within `RUNNING_TIMEOUT`.
+fn is_running(log: &LogSummary, modified: Option<&EpochMillis>, now: DateTime<Utc>) -> bool {
+    log.status == EvalStatus::Started
+        && *modified.unwrap_or(&log.created).as_datetime() > now - RUNNING_TIMEOUT
+}
+
+pub fn main(args: Args, config: &Config) -> Result<()> {
+    let rules = match RetentionRules::from_args(&args) {
+        rules if rules.is_empty() => project_rules(config)?,
+        rules => rules,
+    };
+    rules.validate()?;
+    let dry_run = args.dry_run;
+    handle_dialog_result(
+        LogOpDialog::new("Prune logs")
+            .log_dir(resolve_log_dir(args.log_dir.as_ref()))
+            .select(move |py, index, logs| {
+                // Logs that can't be read are never pruned
+                let mut summaries = Vec::with_capacity(logs.len());
+                for (pos, log) in logs.iter().enumerate() {
+                    match index.summary(py, log) {
+                        Ok(summary) => summaries.push((pos, log, summary)),
+                        Err(err) => log::error!("Error reading {}: {}", log.log_id, err),
+                    }
+                }
+                let selected = rules.select(
+                    &summaries
+                        .iter()
+                        .map(|(_, log, s)| (s, log.mtime.as_ref()))
+                        .collect::<Vec<_>>(),
+                    Utc::now(),
+                );
+                Ok(selected
+                    .into_iter()
+                    .map(|i| {
+                        let (pos, log, _) = &summaries[i];
+                        SelectedLog::new(pos + 1, (*log).clone())
+                    })
+                    .collect::<SelectedLogs>())
+            })
+            .allow_empty(true)
+            .dry_run(dry_run)
+            .show_prompt(!args.yes)
+            .confirm_prompt(|selected| {
+                format!(
+                    "You are about to delete {} {}. Deleted logs can be restored \
+                     using 'gage log restore'. Continue?",
+                    selected.len(),
+                    plural!("log", selected.len())
+                )
+            })
+            .run(|log| log.delete(false))
+            .on_success(move |count| match count {
+                0 => "No logs to prune".into(),
+                _ if dry_run => format!("{count} {} would be pruned", plural!("log", count)),
+                _ => format!("{count} {} pruned", plural!("log", count)),
+            }),
+    )
+}
+
+/// Returns retention rules defined in the project config.
+///
+/// Rules for the active profile are used if defined, otherwise the
+/// project rules are used.
+fn project_rules(config: &Config) -> Result<RetentionRules> {
+    let profile = env::get("GAGE_PROFILE").and_then(|name| config.profiles.get(&name));
+    let retention = profile
+        .and_then(|profile| profile.retention.as_ref())
+        .or(config.retention.as_ref())
+        .ok_or_else(|| {
+            Error::general(
+                "No retention rules defined\n\
+                \n\
+                Use --keep, --older-than, or --status to specify rules, \
+                or define [retention] in gage.toml.",
+            )
+        })?;
+    let rules = RetentionRules::from_config(retention, config)?;
+    if rules.is_empty() {
+        return Err(Error::general(format!(
+            "Retention in {} doesn't define any rules",
+            relpath(&config.path).to_string_lossy()
+        )));
+    }
+    Ok(rules)
+}
+
+fn parse_duration(s: &str) -> std::result::Result<TimeDelta, String> {
+    humantime::parse_duration(s)
+        .map_err(|e| format!("invalid duration '{s}': {e}"))
+        .and_then(|d| TimeDelta::from_std(d).map_err(|e| e.to_string()))
+}
+
+fn parse_status(s: &str) -> std::result::Result<EvalStatus, String> {
+    match s {
+        "started" => Ok(EvalStatus::Started),
+        "success" => Ok(EvalStatus::Success),
+        "cancelled" => Ok(EvalStatus::Cancelled),
+        "error" => Ok(EvalStatus::Error),
+        _ => Err(format!(
+            "invalid status '{s}' (expected started, success, cancelled, or error)"
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeDelta, TimeZone, Utc};
+
+    use crate::{
+        inspect::{log::EvalStatus, log_index::LogSummary},
+        py::EpochMillis,
+    };
+
+    use super::RetentionRules;
+
+    fn summary(task: &str, model: &str, status: EvalStatus, days_ago: i64) -> LogSummary {
+        let created =
+            Utc.with_ymd_and_hms(2026, 1, 31, 0, 0, 0).unwrap() - TimeDelta::days(days_ago);
+        LogSummary {
+            location: String::new(),
+            task: task.into(),
+            task_id: String::new(),
+            eval_id: String::new(),
+            run_id: String::new(),
+            run_type: None,
+            status,
+            model: model.into(),
+            dataset: None,
+            samples: None,
+            created: EpochMillis::from_epoch_millis(created.timestamp_millis()),
+            accuracy: None,
+            stderr: None,
+            tags: Vec::new(),
+            error: None,
+            progress: None,
+            grades: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_select() {
+        let now = Utc.with_ymd_and_hms(2026, 1, 31, 0, 0, 0).unwrap();
+        let logs = [
+            summary("hello", "a", EvalStatus::Success, 1),
+            summary("hello", "a", EvalStatus::Success, 3),
+            summary("hello", "a", EvalStatus::Success, 2),
+            summary("hello", "b", EvalStatus::Success, 5),
+            summary("other", "a", EvalStatus::Error, 0),
+        ];
+        let logs = logs.iter().map(|log| (log, None)).collect::<Vec<_>>();
+        let select =
+            |rules: RetentionRules| rules.select(&logs, now).into_iter().collect::<Vec<_>>();
+
+        let keep = |keep| RetentionRules {
+            keep: Some(keep),
+            ..Default::default()
+        };
+        assert_eq!(select(keep(1)), [1, 2]);
+        assert_eq!(select(keep(2)), [1]);
+        assert_eq!(select(keep(3)), [] as [usize; 0]);
+
+        let older_than = RetentionRules {
+            older_than: Some(TimeDelta::days(2)),
+            ..Default::default()
+        };
+        assert_eq!(select(older_than), [1, 3]);
+
+        let status = RetentionRules {
+            status: vec![EvalStatus::Error, EvalStatus::Cancelled],
+            ..Default::default()
+        };
+        assert_eq!(select(status), [4]);
+
+        let combined = RetentionRules {
+            keep: Some(2),
+            status: vec![EvalStatus::Error],
+            ..Default::default()
+        };
+        assert_eq!(select(combined), [1, 4]);
+    }
+
+    #[test]
+    fn test_select_running() {
+        let now = Utc.with_ymd_and_hms(2026, 1, 31, 0, 0, 0).unwrap();
+        let modified = |hours_ago| {
+            EpochMillis::from_epoch_millis((now - TimeDelta::hours(hours_ago)).timestamp_millis())
+        };
+        let (recent, stale) = (modified(1), modified(48));
+        let logs = [
+            summary("hello", "a", EvalStatus::Started, 0),
+            summary("hello", "a", EvalStatus::Started, 3),
+            summary("hello", "a", EvalStatus::Started, 3),
+            summary("hello", "a", EvalStatus::Started, 5),
+            summary("hello", "a", EvalStatus::Success, 4),
+        ];
+        let logs = [
+            (&logs[0], None),
+            (&logs[1], Some(&recent)),
+            (&logs[2], Some(&stale)),
+            (&logs[3], None),
+            (&logs[4], None),
+        ];
+        let select =
+            |rules: RetentionRules| rules.select(&logs, now).into_iter().collect::<Vec<_>>();
+
+        // Started logs are pruned only when they haven't been modified
+        // recently
+        let status = RetentionRules {
+            status: vec![EvalStatus::Started],
+            ..Default::default()
+        };
+        assert_eq!(select(status), [2, 3]);
+
+        // Running logs are not pruned by other rules
+        let older_than = RetentionRules {
+            older_than: Some(TimeDelta::days(2)),
+            ..Default::default()
+        };
+        assert_eq!(select(older_than), [2, 3, 4]);
+        assert_eq!(
+            select(RetentionRules {
+                keep: Some(1),
+                ..Default::default()
+            }),
+            [2, 3, 4]
+        );
+    }
+}
@@ -0,0 +1,264 @@
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, TimeDelta, TimeZone, Utc};
+use regex::Regex;
+
+use crate::{error::Error, inspect::log_index::LogSummary, result::Result};
+
+/// Filter expression used to select logs.
+///
+/// Expressions have the form FIELD OP VALUE, e.g. 'status=error' or
+/// 'score<0.5', and are evaluated against the log header.
+#[derive(Debug, Clone)]
+pub struct LogQuery {
+    field: Field,
+    op: Op,
+    value: Value,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Field {
+    Task,
+    Status,
+    Model,
+    Dataset,
+    Type,
+    Tag,
+    Created,
+    Score,
+    Samples,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Op {
+    Eq,
+    Ne,
+    Match,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Debug, Clone)]
+enum Value {
+    Str(String),
+    Regex(Regex),
+    Num(f64),
+    // Times are compared using a range, e.g. a date matches any time
+    // during that day
+    Time {
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    },
+}
+
+/// Operators in the order they're matched.
+const OPS: [(&str, Op); 7] = [
+    ("!=", Op::Ne),
+    ("<=", Op::Le),
+    (">=", Op::Ge),
+    ("=", Op::Eq),
+    ("~", Op::Match),
+    ("<", Op::Lt),
+    (">", Op::Gt),
+];
+
+impl LogQuery {
+    /// Returns true if a log spec is a filter expression.
+    pub fn is_query(s: &str) -> bool {
+        s.contains(['=', '~', '<', '>'])
+    }
+
+    pub fn parse(expr: &str) -> Result<Self> {
+        let invalid = |msg: &str| Error::general(format!("Invalid filter '{expr}': {msg}"));
+        let (pos, op_str, op) = OPS
+            .iter()
+            .filter_map(|(s, op)| expr.find(s).map(|pos| (pos, *s, *op)))
+            .min_by_key(|(pos, s, _)| (*pos, std::cmp::Reverse(s.len())))
+            .ok_or_else(|| invalid("expected FIELD=VALUE"))?;
+        let (name, value) = (expr[..pos].trim(), expr[pos + op_str.len()..].trim());
+        let field = Field::from_name(name).ok_or_else(|| {
+            invalid(&format!(
+                "unknown field '{name}' (expected one of task, status, model, \
+                dataset, type, tag, created, score, or samples)"
+            ))
+        })?;
+        if value.is_empty() {
+            return Err(invalid("missing value"));
+        }
+        let value = match (field, op) {
+            (Field::Created | Field::Score | Field::Samples, Op::Match) => {
+                return Err(invalid(&format!("'~' is not supported for {name}")));
+            }
+            (Field::Created, _) => parse_time(value).ok_or_else(|| {
+                invalid("expected a date (YYYY-MM-DD) or time (YYYY-MM-DDTHH:MM:SS)")
+            })?,
+            (Field::Score | Field::Samples, _) => Value::Num(
+                value
+                    .parse()
+                    .map_err(|_| invalid(&format!("expected a number for {name}")))?,
+            ),
+            (_, Op::Match) => Value::Regex(
+                Regex::new(value).map_err(|e| invalid(&format!("invalid pattern: {e}")))?,
+            ),
+            (_, Op::Eq | Op::Ne) => Value::Str(value.into()),
+            (_, _) => {
+                return Err(invalid(&format!(
+                    "'{op_str}' is not supported for {name} (use '=', '!=', or '~')"
+                )));
+            }
+        };
+        Ok(Self {
+            field,
+            op,
+            value,
+        })
+    }
+
+    /// Returns true if a log matches the query.
+    pub fn matches(&self, log: &LogSummary) -> bool {
+        match self.field {
+            Field::Task => self.matches_str(Some(&log.task)),
+            Field::Status => self.matches_str(Some(&log.status.to_string())),
+            Field::Model => self.matches_str(Some(&log.model)),
+            Field::Dataset => self.matches_str(log.dataset.as_deref()),
+            Field::Type => self.matches_str(log.run_type.as_deref()),
+            Field::Tag => self.matches_tags(&log.tags),
+            Field::Created => self.matches_time(log.created.as_datetime()),
+            Field::Score => self.matches_num(log.accuracy),
+            Field::Samples => self.matches_num(log.samples.map(|n| n as f64)),
+        }
+    }
+
+    fn matches_str(&self, val: Option<&str>) -> bool {
+        let val = val.unwrap_or_default();
+        match (&self.value, self.op) {
+            (Value::Str(s), Op::Eq) => val == s,
+            (Value::Str(s), Op::Ne) => val != s,
+            (Value::Regex(re), Op::Match) => re.is_match(val),
+            _ => false,
+        }
+    }
+
+    fn matches_tags(&self, tags: &[String]) -> bool {
+        match (&self.value, self.op) {
+            (Value::Str(s), Op::Eq) => tags.contains(s),
+            (Value::Str(s), Op::Ne) => !tags.contains(s),
+            (Value::Regex(re), Op::Match) => tags.iter().any(|tag| re.is_match(tag)),
+            _ => false,
+        }
+    }
+
+    fn matches_time(&self, val: &DateTime<Utc>) -> bool {
+        let Value::Time { start, end } = &self.value else {
+            return false;
+        };
+        match self.op {
+            Op::Eq => val >= start && val < end,
+            Op::Ne => val < start || val >= end,
+            Op::Lt => val < start,
+            Op::Le => val < end,
+            Op::Gt => val >= end,
+            Op::Ge => val >= start,
+            Op::Match => false,
+        }
+    }
+
+    fn matches_num(&self, val: Option<f64>) -> bool {
+        let (Value::Num(n), Some(val)) = (&self.value, val) else {
+            return false;
+        };
+        if val.is_nan() {
+            return false;
+        }
+        match self.op {
+            Op::Eq => val == *n,
+            Op::Ne => val != *n,
+            Op::Lt => val < *n,
+            Op::Le => val <= *n,
+            Op::Gt => val > *n,
+            Op::Ge => val >= *n,
+            Op::Match => false,
+        }
+    }
+}
+
+impl Field {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "task" => Some(Self::Task),
+            "status" => Some(Self::Status),
+            "model" => Some(Self::Model),
+            "dataset" => Some(Self::Dataset),
+            "type" => Some(Self::Type),
+            "tag" => Some(Self::Tag),
+            "created" => Some(Self::Created),
+            "score" => Some(Self::Score),
+            "samples" => Some(Self::Samples),
+            _ => None,
+        }
+    }
+}
+
+/// Parses a time value as a range.
+///
+/// Dates and times without a timezone use local time. A date matches
+/// any time during that day and a time matches any time within its
+/// precision (minute or second).
+fn parse_time(s: &str) -> Option<Value> {
+    let local = |dt: NaiveDateTime| {
+        Local
+            .from_local_datetime(&dt)
+            .earliest()
+            .map(|dt| dt.to_utc())
+    };
+    let (start, len) = if let Ok(date) = NaiveDate::parse_from_str(s, "%Y-%m-%d") {
+        (local(date.and_hms_opt(0, 0, 0)?)?, TimeDelta::days(1))
+    } else if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
+        (dt.to_utc(), TimeDelta::seconds(1))
+    } else if let Ok(dt) = NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S") {
+        (local(dt)?, TimeDelta::seconds(1))
+    } else if let Ok(dt) = NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M") {
+        (local(dt)?, TimeDelta::minutes(1))
+    } else {
+        return None;
+    };
+    Some(Value::Time {
+        start,
+        end: start + len,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Field, LogQuery, Op};
+
+    #[test]
+    fn test_parse() {
+        let q = LogQuery::parse("task=summarize").unwrap();
+        assert_eq!((q.field, q.op), (Field::Task, Op::Eq));
+        let q = LogQuery::parse("model~gpt-4").unwrap();
+        assert_eq!((q.field, q.op), (Field::Model, Op::Match));
+        let q = LogQuery::parse("score<=0.5").unwrap();
+        assert_eq!((q.field, q.op), (Field::Score, Op::Le));
+        let q = LogQuery::parse("status!=error").unwrap();
+        assert_eq!((q.field, q.op), (Field::Status, Op::Ne));
+        let q = LogQuery::parse("created>2026-09-01T10:00").unwrap();
+        assert_eq!((q.field, q.op), (Field::Created, Op::Gt));
+
+        assert!(LogQuery::is_query("tag=nightly"));
+        assert!(!LogQuery::is_query("ABC123"));
+        assert!(!LogQuery::is_query("1:5"));
+
+        for (expr, err) in [
+            ("foo=bar", "unknown field 'foo'"),
+            ("task=", "missing value"),
+            ("task<a", "'<' is not supported for task"),
+            ("score~1", "'~' is not supported for score"),
+            ("score>high", "expected a number for score"),
+            ("created>yesterday", "expected a date"),
+        ] {
+            let msg = LogQuery::parse(expr).unwrap_err().to_string();
+            assert!(msg.contains(err), "{expr}: {msg}");
+        }
+    }
+}
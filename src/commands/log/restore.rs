@@ -13,6 +13,9 @@ use crate::{
 #[derive(ArgsTrait, Debug)]
 pub struct Args {
     /// The target log(s) to restore
+    ///
+    /// LOGS may be specified using their #, Id, range, or a filter
+    /// expression (see 'gage log list --help').
     #[arg(value_name = "LOG")]
     specs: Vec<String>,
 
@@ -0,0 +1,107 @@
+use std::{cell::Cell, path::PathBuf};
+
+use clap::Args as ArgsTrait;
+use cliclack as cli;
+
+use crate::{
+    commands::log::common::{LogOpDialog, LogOpSuccessMap, LogSelect},
+    dialog::handle_dialog_result,
+    error::Error,
+    inspect::{
+        log::resolve_log_dir,
+        log_file::{self, TagEdit, TagEditResult},
+    },
+    plural,
+    result::Result,
+};
+
+#[derive(ArgsTrait, Debug)]
+pub struct Args {
+    /// The target logs
+    ///
+    /// LOG may be specified using its # or Id. To tag a range, use one
+    /// of 'START:', ':END', or 'START:END'. Use a filter expression
+    /// such as 'model~gpt-4' to tag logs matching the filter (see 'gage
+    /// log list --help').
+    #[arg(value_name = "LOG")]
+    spec: String,
+
+    /// Tags to add (+TAG) or remove (-TAG)
+    ///
+    /// Options must be specified before LOG.
+    #[arg(value_name = "TAG", required = true, allow_hyphen_values = true)]
+    tags: Vec<String>,
+
+    /// Log directory
+    #[arg(long)]
+    log_dir: Option<PathBuf>,
+}
+
+pub fn main(args: Args) -> Result<()> {
+    let edit = parse_tags(&args.tags)?;
+    let log_specs = LogSelect::parse_specs(std::slice::from_ref(&args.spec))?;
+    let changed = Cell::new(0);
+    let running = Cell::new(0);
+    handle_dialog_result(
+        LogOpDialog::new("Tag logs")
+            .log_dir(resolve_log_dir(args.log_dir.as_ref()))
+            .log_specs(log_specs)
+            .show_prompt(false)
+            .run(|log| {
+                match log_file::edit_log_tags(&log.log().name, &edit)? {
+                    TagEditResult::Changed(_) => changed.set(changed.get() + 1),
+                    TagEditResult::Unchanged => {}
+                    TagEditResult::Running => {
+                        // Inspect is still writing the log
+                        cli::log::warning(format!(
+                            "Skipping {} (eval is running)",
+                            log.log().log_id
+                        ))?;
+                        running.set(running.get() + 1);
+                    }
+                }
+                Ok(())
+            })
+            .on_success(|count| {
+                let (changed, running) = (changed.get(), running.get());
+                let mut skipped = Vec::new();
+                if count - changed - running > 0 {
+                    skipped.push(format!("{} unchanged", count - changed - running));
+                }
+                if running > 0 {
+                    skipped.push(format!("{running} running"));
+                }
+                let msg = format!("{changed} {} tagged", plural!("log", changed));
+                if skipped.is_empty() {
+                    msg
+                } else {
+                    format!("{msg} ({})", skipped.join(", "))
+                }
+            }),
+    )
+}
+
+fn parse_tags(tags: &[String]) -> Result<TagEdit> {
+    let mut edit = TagEdit::default();
+    for arg in tags {
+        let (tags, tag) = match arg.split_at_checked(1) {
+            Some(("+", tag)) => (&mut edit.add, tag),
+            Some(("-", tag)) => (&mut edit.remove, tag),
+            _ => {
+                return Err(Error::general(format!(
+                    "invalid tag '{arg}' (use +TAG to add or -TAG to remove)"
+                )));
+            }
+        };
+        if tag.trim().is_empty() {
+            return Err(Error::general(format!("invalid tag '{arg}'")));
+        }
+        tags.push(tag.to_string());
+    }
+    if let Some(tag) = edit.add.iter().find(|tag| edit.remove.contains(tag)) {
+        return Err(Error::general(format!(
+            "tag '{tag}' cannot be both added and removed"
+        )));
+    }
+    Ok(edit)
+}
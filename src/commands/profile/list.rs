@@ -1,4 +1,8 @@
+use std::env;
+
+use clap::Args as ArgsTrait;
 use itertools::Itertools;
+use serde::Serialize;
 use tabled::{
     builder::Builder,
     settings::{
@@ -8,9 +12,55 @@ use tabled::{
     },
 };
 
-use crate::{config::Config, result::Result, util::TableExt};
+use crate::{
+    config::Config,
+    output::{FormatArgs, print_records},
+    result::Result,
+    util::TableExt,
+};
+
+#[derive(ArgsTrait, Debug)]
+pub struct Args {
+    #[command(flatten)]
+    format: FormatArgs,
+}
+
+/// Profile record used for JSON and CSV output.
+#[derive(Serialize, Debug)]
+pub struct ProfileRecord {
+    /// Profile name.
+    pub name: String,
+
+    /// Profile description.
+    pub description: Option<String>,
 
-pub fn main(config: &Config) -> Result<()> {
+    /// Log directory used by the profile.
+    pub log_dir: Option<String>,
+
+    /// Secrets file used by the profile.
+    pub secrets: Option<String>,
+
+    /// Whether the profile is active (GAGE_PROFILE).
+    pub active: bool,
+}
+
+pub fn main(args: Args, config: &Config) -> Result<()> {
+    if !args.format.is_table() {
+        let active = env::var("GAGE_PROFILE").ok();
+        let records = config
+            .profiles
+            .iter()
+            .sorted_by(|(lhs, _), (rhs, _)| lhs.cmp(rhs))
+            .map(|(name, profile)| ProfileRecord {
+                name: name.clone(),
+                description: profile.help.clone(),
+                log_dir: profile.log_dir.clone(),
+                secrets: profile.secrets.clone(),
+                active: active.as_ref() == Some(name),
+            })
+            .collect_vec();
+        return print_records(args.format.format(), &records);
+    }
     if config.profiles.is_empty() {
         println!("No profiles defined in {}", config.path.to_string_lossy());
         return Ok(());
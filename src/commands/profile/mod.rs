@@ -4,7 +4,7 @@ use crate::{config::Config, result::Result};
 
 mod list;
 mod status;
-mod use_;
+pub mod use_;
 
 #[derive(ArgsTrait, Debug)]
 pub struct Args {
@@ -18,7 +18,7 @@ pub enum Cmd {
     Use(use_::Args),
 
     /// Show avaliable profiles
-    List,
+    List(list::Args),
 
     /// Show current profile status
     Status(status::Args),
@@ -27,7 +27,7 @@ pub enum Cmd {
 pub fn main(args: Args, config: &Config) -> Result<()> {
     match args.cmd {
         Cmd::Use(args) => use_::main(args, config),
-        Cmd::List => list::main(config),
+        Cmd::List(args) => list::main(args, config),
         Cmd::Status(args) => status::main(args, config),
     }
 }
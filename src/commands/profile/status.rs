@@ -1,7 +1,8 @@
-use std::{env, path::Path};
+use std::{collections::BTreeMap, env, path::Path};
 
 use clap::Args as ArgsTrait;
 use itertools::Itertools;
+use serde::Serialize;
 use tabled::{
     Table,
     builder::Builder,
@@ -15,6 +16,7 @@ use tabled::{
 use crate::{
     config::Config,
     error::Error,
+    output::{FormatArgs, print_record},
     result::Result,
     theme::Colors,
     util::{TableExt, relpath},
@@ -25,6 +27,40 @@ pub struct Args {
     /// Show more information
     #[arg(short, long)]
     verbose: bool,
+
+    #[command(flatten)]
+    format: FormatArgs,
+}
+
+/// Profile status record used for JSON and CSV output.
+#[derive(Serialize, Debug)]
+pub struct ProfileStatusRecord {
+    /// Active profile name.
+    pub name: String,
+
+    /// Profile description.
+    pub description: Option<String>,
+
+    /// Log directory used by the profile.
+    pub log_dir: Option<String>,
+
+    /// Secrets file used by the profile.
+    pub secrets: Option<String>,
+
+    /// S3 endpoint URL used for s3:// log directories.
+    pub s3_endpoint_url: Option<String>,
+
+    /// S3 region used for s3:// log directories.
+    pub s3_region: Option<String>,
+
+    /// Environment variables defined by the profile.
+    pub env: BTreeMap<String, String>,
+
+    /// Config file that defines the profile.
+    pub config: String,
+
+    /// Path to .env file, if found.
+    pub dotenv: Option<String>,
 }
 
 pub fn main(args: Args, config: &Config) -> Result<()> {
@@ -32,6 +68,30 @@ pub fn main(args: Args, config: &Config) -> Result<()> {
         .map_err(|_| Error::general("GAGE_PROFILE not defined - no profile in use"))?;
     let config_path = &config.path;
     let dotenv = dotenvy::dotenv().ok();
+    if !args.format.is_table() {
+        let config = Config::from_file(config_path)?;
+        let profile = config.profiles.get(&profile_name).ok_or_else(|| {
+            Error::general(format!(
+                "Profile '{}' not defined in {}",
+                profile_name,
+                config_path.to_string_lossy()
+            ))
+        })?;
+        let record = ProfileStatusRecord {
+            name: profile_name.clone(),
+            description: profile.help.clone(),
+            log_dir: profile.log_dir.clone(),
+            secrets: profile.secrets.clone(),
+            s3_endpoint_url: profile.s3.as_ref().and_then(|s3| s3.endpoint_url.clone()),
+            s3_region: profile.s3.as_ref().and_then(|s3| s3.region.clone()),
+            env: profile.env.clone().into_iter().collect(),
+            config: relpath(config_path).to_string_lossy().into(),
+            dotenv: dotenv
+                .as_deref()
+                .map(|path| relpath(path).to_string_lossy().into()),
+        };
+        return print_record(args.format.format(), &record);
+    }
     let status = profile_status(&profile_name, config_path, dotenv.as_deref(), args.verbose);
     println!("{status}",);
     Ok(())
@@ -70,6 +130,18 @@ pub fn profile_status(
                     last_non_env += 1;
                     empty = false;
                 }
+                if let Some(s3) = profile.s3.as_ref() {
+                    if let Some(endpoint_url) = s3.endpoint_url.as_deref() {
+                        table.push_record(["S3 endpoint", endpoint_url]);
+                        last_non_env += 1;
+                        empty = false;
+                    }
+                    if let Some(region) = s3.region.as_deref() {
+                        table.push_record(["S3 region", region]);
+                        last_non_env += 1;
+                        empty = false;
+                    }
+                }
                 for (name, val) in profile.env.iter().sorted() {
                     table.push_record([name, val]);
                     empty = false;
@@ -24,21 +24,36 @@ pub fn main(args: Args, config: &Config) -> Result<()> {
         .ok_or_else(|| no_such_profile(profile_name, &config.path))?;
     log::debug!("Using {profile_name}: {profile:?}");
 
-    // Read existing .env in config dir
+    // Write GAGE_PROFILE to .env in config dir
     let config_parent = &config.path.parent().unwrap();
     let dotenv = config_parent.join(".env");
+    write_dotenv_profile(&dotenv, profile_name)?;
+
+    // Show profile details
+    let status = profile_status(profile_name, &config.path, Some(&dotenv), false);
+    println!("{status}");
+
+    Ok(())
+}
+
+/// Writes `GAGE_PROFILE` to a `.env` file.
+///
+/// Preserves other lines in the file and creates the file if it
+/// doesn't exist.
+pub fn write_dotenv_profile(dotenv: &Path, profile_name: &str) -> Result<()> {
+    // Read existing .env
     let lines = if dotenv.exists() {
-        read_lines(&dotenv)?
+        read_lines(dotenv)?
     } else {
         Vec::new()
     };
 
     // Write new .env with GAGE_PROFILE set to new profile
-    let mut file = File::create(&dotenv)?;
+    let mut file = File::create(dotenv)?;
     let eol = detect_eol(&lines).unwrap_or_else(|| "\n".into());
     let gage_profile_line = format!("GAGE_PROFILE={profile_name}{eol}");
     let mut wrote_profile = false;
-    let mut wrote_eol = false;
+    let mut wrote_eol = true;
     for line in lines {
         if line.starts_with("GAGE_PROFILE=") {
             file.write_all(gage_profile_line.as_bytes())?;
@@ -55,11 +70,6 @@ pub fn main(args: Args, config: &Config) -> Result<()> {
         file.write_all(gage_profile_line.as_bytes())?;
     }
     file.flush()?;
-
-    // Show profile details
-    let status = profile_status(profile_name, &config.path, Some(&dotenv), false);
-    println!("{status}");
-
     Ok(())
 }
 
@@ -97,3 +107,44 @@ fn no_such_profile(profile: &str, config: &Path) -> Error {
         config.to_string_lossy()
     ))
 }
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::write_dotenv_profile;
+
+    #[test]
+    fn test_write_dotenv_profile() {
+        let dir = std::env::temp_dir().join(format!("gage-dotenv-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let dotenv = dir.join(".env");
+        let write = |content: Option<&str>| {
+            match content {
+                Some(content) => fs::write(&dotenv, content).unwrap(),
+                None => _ = fs::remove_file(&dotenv),
+            }
+            write_dotenv_profile(&dotenv, "dev").unwrap();
+            fs::read_to_string(&dotenv).unwrap()
+        };
+
+        // New file doesn't start with a blank line
+        assert_eq!(write(None), "GAGE_PROFILE=dev\n");
+        assert_eq!(write(Some("")), "GAGE_PROFILE=dev\n");
+
+        // Missing newline is added before the profile
+        assert_eq!(write(Some("A=1\nB=2")), "A=1\nB=2\nGAGE_PROFILE=dev\n");
+        assert_eq!(write(Some("A=1\n")), "A=1\nGAGE_PROFILE=dev\n");
+
+        // Existing profile is replaced in place
+        assert_eq!(
+            write(Some("A=1\nGAGE_PROFILE=prod\nB=2\n")),
+            "A=1\nGAGE_PROFILE=dev\nB=2\n"
+        );
+
+        // Line endings are preserved
+        assert_eq!(write(Some("A=1\r\n")), "A=1\r\nGAGE_PROFILE=dev\r\n");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
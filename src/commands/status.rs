@@ -1,7 +1,12 @@
-use std::{collections::HashMap, env, io, path::PathBuf};
+use std::{
+    collections::HashMap,
+    env, io,
+    path::{Path, PathBuf},
+};
 
 use clap::Args as ArgsTrait;
 use pyo3::{Python, types::PyAnyMethods};
+use serde::Serialize;
 use tabled::{
     builder::Builder,
     settings::{
@@ -15,6 +20,7 @@ use crate::{
     config::Config,
     error::Error,
     inspect::log::resolve_log_dir,
+    output::{FormatArgs, print_record},
     py::{self, py_call},
     result::Result,
     util::{TableExt, relpath_str},
@@ -35,9 +41,60 @@ pub struct Args {
     /// Show more detail
     #[arg(short, long)]
     verbose: bool,
+
+    #[command(flatten)]
+    format: FormatArgs,
+}
+
+/// Status record used for JSON and CSV output.
+///
+/// Fields are documented in README.md ("JSON and CSV output").
+///
+/// Paths are relative to the current directory when under it.
+#[derive(Serialize, Debug)]
+pub struct StatusRecord {
+    /// Gage CLI version.
+    pub gage_version: String,
+
+    /// Installed gage_inspect version.
+    pub gage_inspect_version: String,
+
+    /// Path to the gage_inspect package.
+    pub gage_inspect_path: String,
+
+    /// Installed inspect_ai version.
+    pub inspect_ai_version: String,
+
+    /// Path to the inspect_ai package.
+    pub inspect_ai_path: String,
+
+    /// Python version.
+    pub python_version: String,
+
+    /// Python executable.
+    pub python_executable: String,
+
+    /// Python system path.
+    pub python_sys_path: Vec<String>,
+
+    /// Path to .env file, if found.
+    pub dotenv: Option<String>,
+
+    /// Log directory.
+    pub log_dir: String,
+
+    /// Gage config file.
+    pub config: String,
+
+    /// Active profile (GAGE_PROFILE).
+    pub active_profile: Option<String>,
 }
 
 pub fn main(args: Args, config: &Config) -> Result<()> {
+    if !args.format.is_table() {
+        return print_record(args.format.format(), &status_record(&args, config)?);
+    }
+
     // Special row types - used for styling
     let mut errors = Vec::new();
     let mut not_found = Vec::new();
@@ -188,6 +245,48 @@ pub fn main(args: Args, config: &Config) -> Result<()> {
     }
 }
 
+fn status_record(args: &Args, config: &Config) -> Result<StatusRecord> {
+    let cwd = env::current_dir().unwrap();
+    let relpath = |path: &Path| {
+        path.strip_prefix(&cwd)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .to_string()
+    };
+    let dotenv = dotenv_path()?;
+    py::init();
+    Python::attach(|py| {
+        let sys = py.import("sys").unwrap();
+        let sys_attr = |name: &str| sys.getattr(name).unwrap();
+        Ok(StatusRecord {
+            gage_version: VERSION.into(),
+            gage_inspect_version: pkg_version(py, "gage_inspect"),
+            gage_inspect_path: relpath_str(&pkg_path(py, "gage_inspect")).into(),
+            inspect_ai_version: pkg_version(py, "inspect_ai"),
+            inspect_ai_path: relpath_str(&pkg_path(py, "inspect_ai")).into(),
+            python_version: sys_attr("version")
+                .extract::<String>()
+                .unwrap()
+                .split(' ')
+                .next()
+                .unwrap()
+                .into(),
+            python_executable: relpath_str(&sys_attr("executable").extract::<String>().unwrap())
+                .into(),
+            python_sys_path: sys_attr("path")
+                .extract::<Vec<String>>()
+                .unwrap()
+                .iter()
+                .map(|path| relpath_str(path).into())
+                .collect(),
+            dotenv,
+            log_dir: relpath(&resolve_log_dir(args.log_dir.as_ref())),
+            config: relpath(&config.path),
+            active_profile: env::var("GAGE_PROFILE").ok(),
+        })
+    })
+}
+
 pub fn pkg_version<'py>(py: Python<'py>, pkg: &str) -> String {
     py_call(py, "gage_inspect._util", "pkg_version", (pkg,))
         .map(|bound| bound.extract::<String>().unwrap())
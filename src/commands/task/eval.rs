@@ -1,19 +1,44 @@
-use std::path::PathBuf;
+use std::{
+    env,
+    path::{Path, PathBuf},
+};
 
 use clap::Args as ArgsTrait;
 use cliclack as cli;
 use console::style;
 use itertools::Itertools;
 use pyo3::Python;
+use tabled::{
+    builder::Builder,
+    settings::{
+        Color,
+        object::{Cell, Columns, Object, Rows},
+        themes::Colorization,
+    },
+};
 
 use crate::{
-    commands::task::{list_value, select_model_dialog, select_tasks_dialog},
+    commands::{
+        log::common::print_log_table,
+        task::{
+            gate::{Baseline, Thresholds},
+            list_value,
+            progress::EvalProgressView,
+            select_model_dialog, select_tasks_dialog,
+        },
+    },
+    config::{Config, EvalSuite},
     dialog::{DialogResult, handle_dialog_result},
     error::Error,
-    inspect::{log::resolve_log_dir, task::eval_tasks},
+    inspect::{
+        log::{EvalLog, EvalLogInfo, EvalStatus, list_logs, read_log_header, resolve_log_dir},
+        log_index::LogIndex,
+        task::eval_tasks,
+    },
     py,
     result::Result,
-    util::PathExt,
+    theme::Colors,
+    util::{PathExt, TableExt},
 };
 
 #[derive(ArgsTrait, Debug)]
@@ -65,19 +90,118 @@ pub struct Args {
     /// Log directory
     #[arg(long)]
     log_dir: Option<PathBuf>,
+
+    /// Eval suite defined in gage.toml
+    ///
+    /// Suites are defined in '[evals.NAME]' sections and may be
+    /// overridden by the active profile ('[profiles.PROFILE.evals.NAME]').
+    /// Options specified on the command line take precedence over suite
+    /// settings.
+    #[arg(long, value_name = "NAME")]
+    suite: Option<String>,
+
+    /// Baseline log or tag to check for regressions
+    ///
+    /// Metrics for each eval are compared to those of the baseline. If
+    /// a tag is used, evals are compared to the latest log with the tag
+    /// for the same task and model. Metrics may not drop from the
+    /// baseline by more than --max-drop. An eval without a baseline
+    /// fails the check.
+    #[arg(long, value_name = "LOG|TAG")]
+    baseline: Option<String>,
+
+    /// Minimum value for each metric
+    #[arg(long, value_name = "N")]
+    min_score: Option<f64>,
+
+    /// Maximum drop from the baseline for each metric (default is 0)
+    ///
+    /// Gage exits with status 3 if a metric is below --min-score or
+    /// drops from the baseline by more than this value.
+    #[arg(long, value_name = "N")]
+    max_drop: Option<f64>,
 }
 
-pub fn main(args: Args) -> Result<()> {
+pub fn main(args: Args, config: &Config) -> Result<()> {
+    let suite = match args.suite.as_deref() {
+        Some(name) => Some(suite(name, config)?),
+        None => None,
+    };
+    let args = match suite.as_ref() {
+        Some(suite) => apply_suite(args, suite),
+        None => args,
+    };
     if args.limit.is_some() && !args.samples.is_empty() {
         return Err(Error::general("--limit cannot be used with samples"));
     }
+    if args.max_drop.is_some() && args.baseline.is_none() {
+        return Err(Error::general("--max-drop requires --baseline"));
+    }
+
+    // Show Gage progress unless an Inspect display is configured. Must
+    // be set before Python is initialized to apply to Inspect.
+    let show_progress = env::var_os("INSPECT_DISPLAY").is_none();
+    if show_progress {
+        unsafe { env::set_var("INSPECT_DISPLAY", "none") };
+    }
 
-    handle_dialog_result(eval_dialog(args))
+    let log_dir = resolve_log_dir(args.log_dir.as_ref());
+
+    // Resolve baseline before running evals - new logs aren't used as
+    // baselines
+    let thresholds = Thresholds {
+        baseline: match args.baseline.as_deref() {
+            Some(spec) => Some(resolve_baseline(&log_dir, spec)?),
+            None => None,
+        },
+        min_score: args.min_score,
+        max_drop: args.max_drop,
+    };
+
+    let mut logs = Vec::new();
+    handle_dialog_result(eval_dialog(
+        args,
+        suite.as_ref(),
+        thresholds.baseline.as_ref(),
+        &log_dir,
+        show_progress,
+        &mut logs,
+    ))?;
+    if !logs.is_empty() {
+        print_eval_logs(&log_dir, &logs)?;
+    }
+    let logs = read_eval_logs(&logs);
+    if logs.len() > 1 {
+        print_results_matrix(&logs);
+    }
+    if !thresholds.is_empty() {
+        thresholds.check(&logs)?;
+    }
+    Ok(())
 }
 
-fn eval_dialog(args: Args) -> Result<DialogResult> {
+fn eval_dialog(
+    args: Args,
+    suite: Option<&EvalSuite>,
+    baseline: Option<&Baseline>,
+    log_dir: &Path,
+    show_progress: bool,
+    logs: &mut Vec<String>,
+) -> Result<DialogResult> {
     cli::intro(style("Evaluate tasks").bold())?;
 
+    // Suite
+    if let Some(name) = args.suite.as_ref() {
+        cli::log::step(format!(
+            "Suite:\n{}{}",
+            style(name).cyan().bright(),
+            suite
+                .and_then(|suite| suite.help.as_ref())
+                .map(|help| format!("\n{}", style(help).dim()))
+                .unwrap_or_default()
+        ))?;
+    }
+
     py::init();
     Python::attach(|py| {
         let tasks = select_tasks_dialog(py, args.path.as_deref(), &args.tasks, args.yes)?;
@@ -139,6 +263,15 @@ fn eval_dialog(args: Args) -> Result<DialogResult> {
         if !args.task_args.is_empty() {
             options.push(format!("Task args: {}", args.task_args.iter().join(", ")));
         }
+        if let Some(baseline) = baseline {
+            options.push(format!("Baseline: {}", baseline.label()));
+        }
+        if let Some(val) = args.min_score {
+            options.push(format!("Min score: {val}"));
+        }
+        if let Some(val) = args.max_drop {
+            options.push(format!("Max drop: {val}"));
+        }
         if !options.is_empty() {
             cli::log::step(format!(
                 "Additional options:\n{}",
@@ -168,11 +301,14 @@ fn eval_dialog(args: Args) -> Result<DialogResult> {
             .map(|task| task.get_full_name())
             .collect::<Vec<_>>();
 
-        // Log dir
-        let log_dir = resolve_log_dir(args.log_dir.as_ref());
-
         // Run eval
-        eval_tasks(
+        let progress = if show_progress {
+            cli::log::step("Running evals")?;
+            Some(EvalProgressView::start(log_dir))
+        } else {
+            None
+        };
+        let result = eval_tasks(
             py,
             tasks,
             models,
@@ -185,8 +321,203 @@ fn eval_dialog(args: Args) -> Result<DialogResult> {
             args.epochs,
             4,
             log_dir.expect_string(),
-        )?;
+        );
+        if let Some(progress) = progress {
+            progress.stop();
+        }
+        *logs = result?;
 
         Ok(DialogResult::Done)
     })
 }
+
+/// Returns the named suite from the project config.
+fn suite(name: &str, config: &Config) -> Result<EvalSuite> {
+    config.eval_suite(name).ok_or_else(|| {
+        let mut names = config.evals.keys().collect::<Vec<_>>();
+        names.sort();
+        Error::general(if names.is_empty() {
+            format!(
+                "Eval suite '{name}' is not defined\n\n\
+                Define suites in '[evals.NAME]' sections in gage.toml."
+            )
+        } else {
+            format!(
+                "Eval suite '{name}' is not defined\n\n\
+                Defined suites: {}",
+                names.iter().join(", ")
+            )
+        })
+    })
+}
+
+/// Returns args with settings from a suite.
+///
+/// Command line args take precedence over suite settings. Task args
+/// from the command line are applied after suite task args.
+fn apply_suite(args: Args, suite: &EvalSuite) -> Args {
+    Args {
+        tasks: if args.tasks.is_empty() {
+            suite.tasks.clone().unwrap_or_default()
+        } else {
+            args.tasks
+        },
+        models: if args.models.is_empty() {
+            suite.models.clone().unwrap_or_default()
+        } else {
+            args.models
+        },
+        task_args: suite
+            .task_arg_strings()
+            .into_iter()
+            .chain(args.task_args)
+            .collect(),
+        dataset: args.dataset.or(suite.dataset.clone()),
+        limit: args.limit.or(if args.samples.is_empty() {
+            suite.limit
+        } else {
+            None
+        }),
+        samples: if args.samples.is_empty() && args.limit.is_none() {
+            suite.samples.clone().unwrap_or_default()
+        } else {
+            args.samples
+        },
+        sandbox: args.sandbox.or(suite.sandbox.clone()),
+        shuffle: args.shuffle || suite.shuffle.unwrap_or(false),
+        epochs: args.epochs.or(suite.epochs),
+        baseline: args.baseline.or(suite.baseline.clone()),
+        min_score: args.min_score.or(suite.min_score),
+        max_drop: args.max_drop.or(suite.max_drop),
+        ..args
+    }
+}
+
+/// Prints a table of logs created by an eval.
+///
+/// `logs` are the log locations returned by Inspect. Logs are matched
+/// by Id as locations may not use the same form as log names. Logs are
+/// shown with their `gage list` positions.
+fn print_eval_logs(log_dir: &Path, logs: &[String]) -> Result<()> {
+    let log_ids = logs
+        .iter()
+        .filter_map(|location| EvalLogInfo::log_id_for_name(location))
+        .collect::<Vec<_>>();
+    Python::attach(|py| {
+        let all_logs = list_logs(py, log_dir)?;
+        let mut index = LogIndex::open(log_dir);
+        print_log_table(
+            py,
+            &mut index,
+            all_logs
+                .iter()
+                .enumerate()
+                .filter(|(_, log)| log_ids.contains(&log.log_id.as_str()))
+                .map(|(i, log)| (i + 1, log)),
+        );
+        index.save();
+        Ok(())
+    })
+}
+
+/// Returns a baseline for checking eval results.
+fn resolve_baseline(log_dir: &Path, spec: &str) -> Result<Baseline> {
+    py::init();
+    Python::attach(|py| {
+        let logs = list_logs(py, log_dir)?;
+        let mut index = LogIndex::open(log_dir);
+        let baseline = Baseline::resolve(py, &mut index, &logs, spec);
+        index.save();
+        baseline
+    })
+}
+
+/// Reads headers for logs created by an eval.
+///
+/// Logs that can't be read are logged and skipped.
+fn read_eval_logs(logs: &[String]) -> Vec<EvalLog> {
+    Python::attach(|py| {
+        logs.iter()
+            .filter_map(|name| match read_log_header(py, name) {
+                Ok(log) => Some(log),
+                Err(e) => {
+                    log::error!("Error reading {name}: {e}");
+                    None
+                }
+            })
+            .collect()
+    })
+}
+
+/// Prints a matrix of eval results with tasks as rows and models as
+/// columns.
+///
+/// Cells show the accuracy of the first scorer ± stderr. Evals that
+/// don't succeed are colored by status.
+fn print_results_matrix(logs: &[EvalLog]) {
+    let tasks = logs.iter().map(log_task).unique().collect::<Vec<_>>();
+    let models = logs
+        .iter()
+        .map(|log| log.eval.model.as_str())
+        .unique()
+        .collect::<Vec<_>>();
+    let mut colored_cells = Vec::new();
+    let mut table = Builder::new();
+    table.push_record(std::iter::once("").chain(models.iter().copied()));
+    for (row, task) in tasks.iter().enumerate() {
+        let mut record = vec![task.to_string()];
+        for (col, model) in models.iter().enumerate() {
+            let log = logs
+                .iter()
+                .rev()
+                .find(|log| log_task(log) == *task && log.eval.model == *model);
+            if let Some(color) = log.and_then(|log| status_color(&log.status)) {
+                colored_cells.push((row + 1, col + 1, color));
+            }
+            record.push(log.map(results_cell).unwrap_or("-".into()));
+        }
+        table.push_record(record);
+    }
+    let mut table = table.build();
+    table
+        .with_term_fit()
+        .with_rounded()
+        .with_col_labels()
+        .with(Colorization::exact(
+            [Color::FG_BRIGHT_YELLOW],
+            Columns::first().intersect(Rows::new(1..)),
+        ));
+    for (row, col, color) in colored_cells {
+        table.with(Colorization::exact([color], Cell::new(row, col)));
+    }
+    println!("{table}");
+}
+
+pub(super) fn log_task(log: &EvalLog) -> &str {
+    log.eval
+        .task_display_name
+        .as_deref()
+        .unwrap_or(&log.eval.task)
+}
+
+/// Returns the accuracy ± stderr for an eval, or its status if the
+/// eval doesn't have an accuracy.
+fn results_cell(log: &EvalLog) -> String {
+    let results = log.results.as_ref();
+    match (
+        results.and_then(|results| results.first_accuracy()),
+        results.and_then(|results| results.first_stderr()),
+    ) {
+        (Some(accuracy), Some(stderr)) => format!("{accuracy:.3} ± {stderr:.3}"),
+        (Some(accuracy), None) => format!("{accuracy:.3}"),
+        (None, _) => log.status.to_string(),
+    }
+}
+
+fn status_color(status: &EvalStatus) -> Option<Color> {
+    match status {
+        EvalStatus::Success => None,
+        EvalStatus::Error => Some(Color::FG_RED),
+        EvalStatus::Started | EvalStatus::Cancelled => Some(Colors::dim()),
+    }
+}
@@ -0,0 +1,449 @@
+//! Score thresholds for evals (`eval --baseline`, `--min-score`, and
+//! `--max-drop`).
+//!
+//! Used to fail CI builds when eval results regress. Metrics are
+//! checked for each score of each eval log. Dispersion metrics (e.g.
+//! `stderr`) aren't checked as lower values aren't regressions.
+//!
+//! A baseline is either a log or a tag. When a tag is used, each eval
+//! is compared to the latest log with the tag for the same task and
+//! model.
+//!
+//! An eval without a baseline or without results is a violation, as
+//! is a run without eval results, so that checks don't pass when
+//! there's nothing to compare.
+
+use itertools::Itertools;
+use pyo3::Python;
+use tabled::{
+    builder::Builder,
+    settings::{
+        Color,
+        object::{Cell, Columns, Object, Rows},
+        themes::Colorization,
+    },
+};
+
+use crate::{
+    commands::{log::common::LogSelect, task::eval::log_task},
+    error::Error,
+    inspect::{
+        log::{EvalLog, EvalLogInfo, read_log_header},
+        log_index::LogIndex,
+    },
+    plural,
+    result::Result,
+    theme::Colors,
+    util::TableExt,
+};
+
+/// Exit code used when a threshold is violated.
+///
+/// Distinguishes regressions from other errors (exit code 1).
+pub const THRESHOLD_EXIT_CODE: u8 = 3;
+
+/// Metrics that aren't checked.
+const DISPERSION_METRICS: [&str; 3] = ["stderr", "std", "var"];
+
+/// Baseline logs used to check for regressions.
+pub enum Baseline {
+    Log(String, Box<EvalLog>),
+    Tag(String, Vec<EvalLog>),
+}
+
+impl Baseline {
+    /// Resolves a baseline spec from logs in `logs`.
+    ///
+    /// `spec` is a tag if any log has that tag, otherwise it's a log #,
+    /// ID, or filter.
+    pub fn resolve(
+        py: Python<'_>,
+        index: &mut LogIndex,
+        logs: &[EvalLogInfo],
+        spec: &str,
+    ) -> Result<Self> {
+        let mut tagged = Vec::new();
+        for log in logs {
+            if let Ok(summary) = index.summary(py, log)
+                && summary.tags.iter().any(|tag| tag == spec)
+            {
+                tagged.push(read_log_header(py, &log.name)?);
+            }
+        }
+        if !tagged.is_empty() {
+            return Ok(Self::Tag(spec.into(), tagged));
+        }
+        let log = LogSelect::select_one(py, index, logs, spec)?;
+        Ok(Self::Log(
+            log.log().log_id.clone(),
+            Box::new(read_log_header(py, &log.log().name)?),
+        ))
+    }
+
+    /// Returns a description of the baseline.
+    pub fn label(&self) -> String {
+        match self {
+            Self::Log(id, _) => format!("log {id}"),
+            Self::Tag(tag, logs) => format!(
+                "tag '{tag}' ({} {})",
+                logs.len(),
+                plural!("log", logs.len())
+            ),
+        }
+    }
+
+    /// Returns the baseline for an eval.
+    fn find(&self, log: &EvalLog) -> Option<&EvalLog> {
+        match self {
+            Self::Log(_, baseline) => Some(baseline.as_ref()),
+            // Logs are listed latest first
+            Self::Tag(_, logs) => logs.iter().find(|baseline| {
+                baseline.eval.task == log.eval.task && baseline.eval.model == log.eval.model
+            }),
+        }
+    }
+}
+
+/// Score thresholds.
+pub struct Thresholds {
+    pub baseline: Option<Baseline>,
+    pub min_score: Option<f64>,
+    pub max_drop: Option<f64>,
+}
+
+/// Result of checking a metric.
+#[derive(Debug)]
+struct MetricCheck {
+    task: String,
+    model: String,
+    score: String,
+    metric: String,
+    baseline: Option<f64>,
+    value: Option<f64>,
+    violations: Vec<String>,
+}
+
+impl MetricCheck {
+    /// Returns a check for a violation that applies to a log rather than
+    /// a metric.
+    fn log_violation(log: &EvalLog, violation: String) -> Self {
+        Self {
+            task: log_task(log).into(),
+            model: log.eval.model.clone(),
+            score: "-".into(),
+            metric: "-".into(),
+            baseline: None,
+            value: None,
+            violations: vec![violation],
+        }
+    }
+}
+
+impl Thresholds {
+    pub fn is_empty(&self) -> bool {
+        self.baseline.is_none() && self.min_score.is_none()
+    }
+
+    /// Checks eval results and prints a report.
+    ///
+    /// Returns an error with `THRESHOLD_EXIT_CODE` if any threshold is
+    /// violated or if there are no logs to check.
+    pub fn check(&self, logs: &[EvalLog]) -> Result<()> {
+        if logs.is_empty() {
+            return Err(Error::with_code(
+                THRESHOLD_EXIT_CODE,
+                "No eval results to check",
+            ));
+        }
+        let mut checks = Vec::new();
+        for log in logs {
+            let baseline = match self.baseline.as_ref() {
+                Some(baseline) => {
+                    let found = baseline.find(log);
+                    if found.is_none() {
+                        checks.push(MetricCheck::log_violation(
+                            log,
+                            format!("no baseline in {}", baseline.label()),
+                        ));
+                    }
+                    found
+                }
+                None => None,
+            };
+            checks.extend(self.check_log(log, baseline));
+        }
+        print_checks(&checks, self.baseline.is_some());
+        let failed = checks
+            .iter()
+            .filter(|check| !check.violations.is_empty())
+            .collect::<Vec<_>>();
+        if failed.is_empty() {
+            return Ok(());
+        }
+        Err(Error::with_code(
+            THRESHOLD_EXIT_CODE,
+            format!(
+                "{} {} failed:\n{}",
+                failed.len(),
+                plural!("check", failed.len()),
+                failed
+                    .iter()
+                    .flat_map(|check| check.violations.iter().map(move |v| (check, v)))
+                    .map(|(check, violation)| format!(
+                        "  {} ({}) {}/{}: {violation}",
+                        check.task, check.model, check.score, check.metric
+                    ))
+                    .join("\n")
+            ),
+        ))
+    }
+
+    fn check_log(&self, log: &EvalLog, baseline: Option<&EvalLog>) -> Vec<MetricCheck> {
+        let mut checks = Vec::new();
+        for (score, metric) in log_metrics(log)
+            .into_iter()
+            .chain(baseline.map(log_metrics).unwrap_or_default())
+            .unique()
+        {
+            let value = metric_value(log, &score, &metric);
+            let baseline = baseline.and_then(|baseline| metric_value(baseline, &score, &metric));
+            checks.push(MetricCheck {
+                task: log_task(log).into(),
+                model: log.eval.model.clone(),
+                violations: self.violations(value, baseline),
+                score,
+                metric,
+                baseline,
+                value,
+            });
+        }
+        if checks.is_empty() && !self.is_empty() {
+            checks.push(MetricCheck::log_violation(
+                log,
+                format!("no results (status {})", log.status),
+            ));
+        }
+        checks
+    }
+
+    fn violations(&self, value: Option<f64>, baseline: Option<f64>) -> Vec<String> {
+        let mut violations = Vec::new();
+        let Some(value) = value else {
+            if self.min_score.is_some() || baseline.is_some() {
+                violations.push("missing value".into());
+            }
+            return violations;
+        };
+        if let Some(min) = self.min_score
+            && value < min
+        {
+            violations.push(format!("{value:.3} is below --min-score {min}"));
+        }
+        if let Some(baseline) = baseline {
+            let max_drop = self.max_drop.unwrap_or(0.0);
+            let drop = baseline - value;
+            // Allow for float rounding when comparing equal values
+            if drop > max_drop + f64::EPSILON {
+                violations.push(format!(
+                    "dropped {drop:.3} from baseline {baseline:.3} (--max-drop {max_drop})"
+                ));
+            }
+        }
+        violations
+    }
+}
+
+/// Returns score and metric names for checked metrics.
+///
+/// Metrics are sorted by name within a score.
+fn log_metrics(log: &EvalLog) -> Vec<(String, String)> {
+    log.results
+        .iter()
+        .flat_map(|results| &results.scores)
+        .flat_map(|score| {
+            score
+                .metrics
+                .keys()
+                .filter(|metric| !DISPERSION_METRICS.contains(&metric.as_str()))
+                .sorted()
+                .map(|metric| (score.name.clone(), metric.clone()))
+        })
+        .collect()
+}
+
+fn metric_value(log: &EvalLog, score: &str, metric: &str) -> Option<f64> {
+    log.results
+        .as_ref()?
+        .scores
+        .iter()
+        .find(|s| s.name == score)?
+        .metrics
+        .get(metric)
+        .map(|m| m.value.as_f64())
+}
+
+/// Prints a table of metric checks.
+///
+/// Baseline and delta columns are shown when `baseline` is true.
+fn print_checks(checks: &[MetricCheck], baseline: bool) {
+    let fmt = |val: Option<f64>| val.map(|v| format!("{v:.3}")).unwrap_or("-".into());
+    let mut header = vec!["Task", "Model", "Score", "Metric"];
+    if baseline {
+        header.push("Baseline");
+    }
+    header.push("Value");
+    if baseline {
+        header.push("Delta");
+    }
+    header.push("Result");
+    let result_col = header.len() - 1;
+    let mut colored_cells = Vec::new();
+    let mut table = Builder::new();
+    table.push_record(header);
+    for (i, check) in checks.iter().enumerate() {
+        let passed = check.violations.is_empty();
+        colored_cells.push((
+            i + 1,
+            if passed {
+                Color::FG_GREEN
+            } else {
+                Color::FG_RED
+            },
+        ));
+        let mut row = vec![
+            check.task.clone(),
+            check.model.clone(),
+            check.score.clone(),
+            check.metric.clone(),
+        ];
+        if baseline {
+            row.push(fmt(check.baseline));
+        }
+        row.push(fmt(check.value));
+        if baseline {
+            row.push(
+                check
+                    .value
+                    .zip(check.baseline)
+                    .map(|(value, baseline)| format!("{:+.3}", value - baseline))
+                    .unwrap_or("-".into()),
+            );
+        }
+        row.push(if passed { "pass" } else { "FAIL" }.into());
+        table.push_record(row);
+    }
+    let mut table = table.build();
+    table
+        .with_term_fit()
+        .with_rounded()
+        .with_col_labels()
+        .with(Colorization::exact(
+            [Color::FG_BRIGHT_YELLOW],
+            Columns::first().intersect(Rows::new(1..)),
+        ))
+        .with(Colorization::exact(
+            [Colors::dim()],
+            Columns::one(1).intersect(Rows::new(1..)),
+        ));
+    for (row, color) in colored_cells {
+        table.with(Colorization::exact([color], Cell::new(row, result_col)));
+    }
+    println!("{table}");
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{error::Error, inspect::log::EvalLog};
+
+    use super::{Baseline, THRESHOLD_EXIT_CODE, Thresholds};
+
+    fn eval_log(task: &str, accuracy: f64) -> EvalLog {
+        serde_json::from_value(serde_json::json!({
+            "version": 2,
+            "status": "success",
+            "eval": {
+                "eval_id": "E1", "run_id": "R1", "created": "2025-01-01T10:00:00+00:00",
+                "task": task, "task_id": "T1", "dataset": {}, "model": "mockllm/model"
+            },
+            "plan": {"name": "plan", "steps": []},
+            "results": {
+                "total_samples": 1,
+                "completed_samples": 1,
+                "scores": [{
+                    "name": "match", "scorer": "match",
+                    "metrics": {"accuracy": {"name": "accuracy", "value": accuracy}}
+                }]
+            }
+        }))
+        .unwrap()
+    }
+
+    fn exit_code(result: crate::result::Result<()>) -> Option<u8> {
+        match result {
+            Err(Error::Code(code, _)) => Some(code),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_check() {
+        let min_score = Thresholds {
+            baseline: None,
+            min_score: Some(0.5),
+            max_drop: None,
+        };
+        assert!(min_score.check(&[eval_log("hello", 1.0)]).is_ok());
+        assert_eq!(
+            exit_code(min_score.check(&[eval_log("hello", 0.25)])),
+            Some(THRESHOLD_EXIT_CODE)
+        );
+
+        // No logs to check is an error
+        assert_eq!(exit_code(min_score.check(&[])), Some(THRESHOLD_EXIT_CODE));
+
+        // Evals without a baseline are violations
+        let baseline = Thresholds {
+            baseline: Some(Baseline::Tag("ci".into(), vec![eval_log("hello", 1.0)])),
+            min_score: None,
+            max_drop: None,
+        };
+        assert!(baseline.check(&[eval_log("hello", 1.0)]).is_ok());
+        assert_eq!(
+            exit_code(baseline.check(&[eval_log("other", 1.0)])),
+            Some(THRESHOLD_EXIT_CODE)
+        );
+        assert_eq!(exit_code(baseline.check(&[])), Some(THRESHOLD_EXIT_CODE));
+    }
+
+    #[test]
+    fn test_violations() {
+        let thresholds = Thresholds {
+            baseline: None,
+            min_score: Some(0.8),
+            max_drop: Some(0.05),
+        };
+        assert!(thresholds.violations(Some(0.8), Some(0.85)).is_empty());
+        assert_eq!(
+            thresholds.violations(Some(0.7), None),
+            vec!["0.700 is below --min-score 0.8"]
+        );
+        assert_eq!(
+            thresholds.violations(Some(0.9), Some(1.0)),
+            vec!["dropped 0.100 from baseline 1.000 (--max-drop 0.05)"]
+        );
+        assert_eq!(
+            thresholds.violations(None, Some(1.0)),
+            vec!["missing value"]
+        );
+
+        let no_drop = Thresholds {
+            baseline: None,
+            min_score: None,
+            max_drop: None,
+        };
+        assert!(no_drop.violations(Some(0.5), Some(0.5)).is_empty());
+        assert!(no_drop.violations(Some(0.6), Some(0.5)).is_empty());
+        assert_eq!(no_drop.violations(Some(0.4), Some(0.5)).len(), 1);
+        assert!(no_drop.violations(None, None).is_empty());
+    }
+}
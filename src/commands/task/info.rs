@@ -1,12 +1,18 @@
 use clap::Args as ArgsTrait;
 use pyo3::Python;
+use serde::Serialize;
 use tabled::{
     builder::Builder,
     settings::{Padding, Style, Width, object::Columns, peaker::Priority, themes::Colorization},
 };
 
 use crate::{
-    commands::task::select_task, inspect::task::get_task_doc, py, result::Result, theme::Colors,
+    commands::task::select_task,
+    inspect::task::get_task_doc,
+    output::{FormatArgs, print_record},
+    py,
+    result::Result,
+    theme::Colors,
     util::term_width,
 };
 
@@ -18,6 +24,42 @@ pub struct Args {
     /// Path to find tasks
     #[arg(short, long)]
     path: Option<String>,
+
+    #[command(flatten)]
+    format: FormatArgs,
+}
+
+/// Task info record used for JSON and CSV output.
+#[derive(Serialize, Debug)]
+pub struct TaskInfoRecord {
+    /// Task name.
+    pub name: String,
+
+    /// Task source file.
+    pub source: String,
+
+    /// Task description.
+    pub description: Option<String>,
+
+    /// Task input params from the task docstring.
+    pub params: Vec<TaskParamRecord>,
+
+    /// Description of task output from the task docstring.
+    pub returns: Option<String>,
+}
+
+/// Task param record used for JSON and CSV output.
+#[derive(Serialize, Debug)]
+pub struct TaskParamRecord {
+    /// Param name.
+    pub name: String,
+
+    /// Param type.
+    #[serde(rename = "type")]
+    pub type_name: Option<String>,
+
+    /// Param description.
+    pub description: Option<String>,
 }
 
 pub fn main(args: Args) -> Result<()> {
@@ -34,6 +76,35 @@ pub fn main(args: Args) -> Result<()> {
         // Try to get task doc
         let doc = get_task_doc(py, &task)?;
 
+        if !args.format.is_table() {
+            let record = TaskInfoRecord {
+                name: task.name.clone(),
+                source: task.file.clone(),
+                description: doc
+                    .as_ref()
+                    .and_then(|doc| doc.description.clone())
+                    .or_else(|| task.get_description()),
+                params: doc
+                    .as_ref()
+                    .map(|doc| {
+                        doc.params
+                            .iter()
+                            .map(|p| TaskParamRecord {
+                                name: p.arg_name.clone(),
+                                type_name: p.type_name.clone(),
+                                description: p.description.clone(),
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+                returns: doc
+                    .as_ref()
+                    .and_then(|doc| doc.returns.as_ref())
+                    .and_then(|returns| returns.description.clone()),
+            };
+            return print_record(args.format.format(), &record);
+        }
+
         // Task description
         table.push_record([
             "Description",
@@ -1,6 +1,7 @@
 use clap::Args as ArgsTrait;
 use itertools::Itertools;
 use pyo3::Python;
+use serde::Serialize;
 use tabled::{
     builder::Builder,
     settings::{
@@ -10,18 +11,53 @@ use tabled::{
     },
 };
 
-use crate::{commands::task::list_tasks_dialog, py, result::Result, theme::Colors, util::TableExt};
+use crate::{
+    commands::task::{list_tasks, list_tasks_dialog},
+    output::{FormatArgs, print_records},
+    py,
+    result::Result,
+    theme::Colors,
+    util::TableExt,
+};
 
 #[derive(ArgsTrait, Debug)]
 pub struct Args {
     /// Path to find tasks
     #[arg(short, long)]
     path: Option<String>,
+
+    #[command(flatten)]
+    format: FormatArgs,
+}
+
+/// Task record used for JSON and CSV output.
+#[derive(Serialize, Debug)]
+pub struct TaskRecord {
+    /// Task name.
+    pub name: String,
+
+    /// Task description.
+    pub description: Option<String>,
+
+    /// Task source file.
+    pub source: String,
 }
 
 pub fn main(args: Args) -> Result<()> {
     py::init();
     Python::attach(|py| {
+        if !args.format.is_table() {
+            let records = list_tasks(py, args.path.as_deref())?
+                .iter()
+                .sorted_by_key(|task| &task.name)
+                .map(|task| TaskRecord {
+                    name: task.name.clone(),
+                    description: task.get_description(),
+                    source: task.file.clone(),
+                })
+                .collect_vec();
+            return print_records(args.format.format(), &records);
+        }
         let tasks = list_tasks_dialog(py, args.path.as_deref())?;
         if tasks.is_empty() {
             println!("No tasks found");
@@ -7,6 +7,7 @@ use pyo3::Python;
 
 use crate::{
     commands::endpoint,
+    config::Config,
     error::Error,
     inspect::{self, task::TaskInfo},
     result::Result,
@@ -14,8 +15,10 @@ use crate::{
 };
 
 pub mod eval;
+mod gate;
 mod info;
 mod list;
+mod progress;
 pub mod run;
 
 #[derive(ArgsTrait, Debug)]
@@ -39,17 +42,16 @@ pub enum Cmd {
     Run(run::Args),
 
     /// Start a task endpoint
-    #[command(hide = true)] // TODO
     Serve(endpoint::start::Args),
 }
 
-pub fn main(args: Args) -> Result<()> {
+pub fn main(args: Args, config: &Config) -> Result<()> {
     match args.cmd {
-        Cmd::Eval(args) => eval::main(args),
+        Cmd::Eval(args) => eval::main(args, config),
         Cmd::Info(args) => info::main(args),
         Cmd::List(args) => list::main(args),
         Cmd::Run(args) => run::main(args),
-        Cmd::Serve(args) => endpoint::start::main(args),
+        Cmd::Serve(args) => endpoint::start::serve_main(args, config),
     }
 }
 
@@ -0,0 +1,185 @@
+//! Eval progress display.
+//!
+//! Progress is shown as a line per eval (task × model) under the
+//! current `cliclack` step. Evals are found by watching the log
+//! directory for new logs while Inspect runs. Progress lines are
+//! redrawn in place when stderr is a terminal. Otherwise final progress
+//! is shown once when the evals finish.
+
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use console::{Term, style};
+
+use crate::{
+    inspect::{log::EvalStatus, log_file, log_progress::EvalProgress, s3},
+    plural,
+    theme::{S_BAR, SPINNER_CHARS},
+};
+
+/// Interval between progress updates.
+const UPDATE_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Interval between reading logs in S3.
+///
+/// S3 logs are downloaded in full to read progress so they're read
+/// less often than local logs.
+const S3_READ_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Shows progress for evals logged to a log directory.
+pub struct EvalProgressView {
+    stop: Arc<AtomicBool>,
+    handle: JoinHandle<()>,
+}
+
+impl EvalProgressView {
+    /// Starts showing progress for logs created in `log_dir`.
+    ///
+    /// Logs that exist when the view is started are ignored.
+    pub fn start(log_dir: &Path) -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+        let existing = list_logs(log_dir);
+        let log_dir = log_dir.to_path_buf();
+        let handle = thread::spawn({
+            let stop = stop.clone();
+            move || show_progress(log_dir, existing, stop)
+        });
+        Self { stop, handle }
+    }
+
+    /// Stops showing progress.
+    pub fn stop(self) {
+        self.stop.store(true, Ordering::Relaxed);
+        self.handle.thread().unpark();
+        let _ = self.handle.join();
+    }
+}
+
+fn show_progress(log_dir: PathBuf, existing: HashSet<String>, stop: Arc<AtomicBool>) {
+    let term = Term::stderr();
+    let mut evals = Vec::<EvalProgress>::new();
+    let mut lines = 0;
+    let mut frame = 0;
+    let read_interval = if log_dir.to_str().is_some_and(s3::is_s3) {
+        S3_READ_INTERVAL
+    } else {
+        UPDATE_INTERVAL
+    };
+    let frames_per_read = (read_interval.as_millis() / UPDATE_INTERVAL.as_millis()) as usize;
+    loop {
+        // Read stop before updating so final progress is complete
+        let stopping = stop.load(Ordering::Relaxed);
+        if stopping || frame % frames_per_read == 0 {
+            update_evals(&log_dir, &existing, &mut evals);
+        }
+        if stopping || term.is_term() {
+            let _ = term.clear_last_lines(lines);
+            lines = 0;
+            for line in progress_lines(&evals, frame, stopping) {
+                let _ = term.write_line(&line);
+                lines += 1;
+            }
+        }
+        if stopping {
+            break;
+        }
+        frame += 1;
+        thread::park_timeout(UPDATE_INTERVAL);
+    }
+}
+
+fn list_logs(log_dir: &Path) -> HashSet<String> {
+    log_file::list_log_files(log_dir, false)
+        .map(|logs| logs.into_iter().map(|log| log.name).collect())
+        .unwrap_or_default()
+}
+
+/// Adds new logs to `evals` and updates progress for running evals.
+///
+/// Errors are logged rather than returned as logs are read while
+/// Inspect writes them. Failed reads are tried again on the next
+/// update.
+fn update_evals(log_dir: &Path, existing: &HashSet<String>, evals: &mut Vec<EvalProgress>) {
+    for name in list_logs(log_dir) {
+        if !existing.contains(&name) && !evals.iter().any(|eval| eval.location == name) {
+            evals.push(EvalProgress::new(&name));
+        }
+    }
+    // Log names start with their create time - show in create order
+    evals.sort_by(|a, b| a.location.cmp(&b.location));
+    for eval in evals.iter_mut().filter(|eval| !eval.is_done()) {
+        if let Err(e) = eval.update() {
+            log::debug!("Error reading progress for {}: {e}", eval.location);
+        }
+    }
+}
+
+fn progress_lines(evals: &[EvalProgress], frame: usize, done: bool) -> Vec<String> {
+    let bar = style(S_BAR).dim();
+    let evals = evals
+        .iter()
+        .filter(|eval| !eval.task.is_empty())
+        .collect::<Vec<_>>();
+    if evals.is_empty() {
+        return if done {
+            Vec::new()
+        } else {
+            vec![format!("{bar}  {}", style("Starting evals").dim())]
+        };
+    }
+    let task_width = evals.iter().map(|eval| eval.task.len()).max().unwrap_or(0);
+    let model_width = evals.iter().map(|eval| eval.model.len()).max().unwrap_or(0);
+    evals
+        .iter()
+        .map(|eval| {
+            format!(
+                "{bar}  {} {:task_width$}  {}  {}",
+                status_symbol(eval, frame),
+                eval.task,
+                style(format!("{:model_width$}", eval.model)).dim(),
+                progress_details(eval),
+            )
+        })
+        .collect()
+}
+
+fn status_symbol(eval: &EvalProgress, frame: usize) -> String {
+    match eval.status {
+        EvalStatus::Started => {
+            let chars = SPINNER_CHARS.chars().collect::<Vec<_>>();
+            style(chars[frame % chars.len()]).cyan().to_string()
+        }
+        EvalStatus::Success => style("✔").green().to_string(),
+        EvalStatus::Cancelled => style("■").yellow().to_string(),
+        EvalStatus::Error => style("✖").red().to_string(),
+    }
+}
+
+fn progress_details(eval: &EvalProgress) -> String {
+    let mut details = vec![match eval.total {
+        Some(total) => format!("{}/{total} samples", eval.completed),
+        None => format!("{} samples", eval.completed),
+    }];
+    if let Some(accuracy) = eval.accuracy() {
+        details.push(format!("accuracy {accuracy:.3}"));
+    }
+    if eval.errors > 0 {
+        details.push(
+            style(format!("{} {}", eval.errors, plural!("error", eval.errors)))
+                .red()
+                .to_string(),
+        );
+    }
+    if eval.tokens > 0 {
+        details.push(format!("{} tokens", eval.tokens));
+    }
+    details.join(&style(" · ").dim().to_string())
+}
@@ -16,13 +16,17 @@ use crate::{
         log::{EvalStatus, resolve_log_dir},
         model::{ChatMessageAssistant, ChatMessageContent, Content, ModelOutput},
         scorer::Score,
-        task::{TaskInfo, get_task_doc, run_task},
+        task::{TaskInfo, TaskResult, get_task_doc, run_task},
     },
     py::{self, Docstring},
     result::Result,
     util::{PathExt, term_width, wrap, wrap_map},
 };
 
+mod batch;
+mod input;
+mod repl;
+
 lazy_static! {
     static ref DEP_ERROR_P: Regex = Regex::new(
         r"(?s)PrerequisiteError: \[bold\]ERROR\[/bold\]: ([\w ]+) requires .*?\[bold\]pip install (\w+)\[/bold\]").unwrap();
@@ -39,6 +43,14 @@ pub struct Args {
     #[arg(short, long)]
     input: Option<String>,
 
+    /// JSONL or CSV file of inputs to run
+    ///
+    /// Runs the task for each row in FILE. Rows have an `input` field
+    /// and optional `target` and task arguments. Use an `args` object
+    /// for JSONL task arguments and `args.NAME` columns for CSV.
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["input", "target"])]
+    inputs: Option<PathBuf>,
+
     /// Task argument NAME=VALUE (may use more than once)
     #[arg(short = 'T', value_name = "TASK_ARG")]
     task_args: Vec<String>,
@@ -71,16 +83,37 @@ pub struct Args {
     #[arg(long)]
     log_dir: Option<PathBuf>,
 
+    /// Write a log for each input (default is one log for all inputs)
+    #[arg(long, requires = "inputs")]
+    log_per_input: bool,
+
+    /// Continue running inputs after an error (default is to stop)
+    #[arg(long, requires = "inputs")]
+    continue_on_error: bool,
+
+    /// Run inputs interactively
+    ///
+    /// Loads the task once and prompts for inputs until stopped. Use
+    /// `:help` at the prompt for commands to change run settings.
+    #[arg(long, conflicts_with = "inputs")]
+    repl: bool,
+
     /// Don't prompt for input
     ///
-    /// --input is required when this option is used.
+    /// --input or --inputs is required when this option is used.
     #[arg(short, long)]
     yes: bool,
 }
 
 pub fn main(args: Args) -> Result<()> {
+    if args.inputs.is_some() {
+        return batch::main(args);
+    }
+    if args.repl {
+        return repl::main(args);
+    }
     if args.yes && args.input.is_none() {
-        return Err(Error::general("--input required with --yes"));
+        return Err(Error::general("--input or --inputs required with --yes"));
     }
     handle_dialog_result(run_dialog(args))
 }
@@ -193,91 +226,100 @@ fn run_dialog(args: Args) -> Result<DialogResult> {
         pb.clear();
 
         // Show result
-        match result {
-            Ok(result) => {
-                let log = result.log();
-                match result.log().status {
-                    EvalStatus::Success => {
-                        let result = result.sample();
-
-                        // Output
-                        if result.output.choices.is_empty() {
-                            cli::log::error(
-                                "ERROR: model returned an empty response - \
-                                 does the solver call generate()?",
-                            )?;
-                        } else {
-                            let output = result.output.to_dialog_info();
-                            cli::log::info(format!("Output:\n\n{output}"))?;
-                        }
-
-                        // Score
-                        if let Some(scores) = result.scores.as_ref()
-                            && !scores.is_empty()
-                        {
-                            let mut scores = scores.values().map(|score| score.to_dialog_info());
-                            cli::log::remark(format!("Score:\n\n{}", scores.join("\n\n")))?;
-                        }
+        show_result(result)?;
+        Ok(DialogResult::Done)
+    })
+}
 
-                        Ok(DialogResult::Done)
-                    }
-                    EvalStatus::Error => {
-                        let error = log.error.as_ref().expect("error for status");
-                        cli::log::error(wrap_map(&error.message, term_width() - 4, |s| {
-                            style(s).red().bright().to_string()
-                        }))?;
-                        Err(Error::Quiet)
+/// Shows the output and score for a run.
+///
+/// Returns an error if the run failed. Errors shown here are returned
+/// as `Error::Quiet`.
+fn show_result(result: Result<TaskResult>) -> Result<()> {
+    match result {
+        Ok(result) => {
+            let log = result.log();
+            match result.log().status {
+                EvalStatus::Success => {
+                    let result = result.sample();
+
+                    // Output
+                    if result.output.choices.is_empty() {
+                        cli::log::error(
+                            "ERROR: model returned an empty response - \
+                             does the solver call generate()?",
+                        )?;
+                    } else {
+                        let output = result.output.to_dialog_info();
+                        cli::log::info(format!("Output:\n\n{output}"))?;
                     }
-                    _ => {
-                        cli::log::error(format!("Unexpected log status: {}", log.status))?;
-                        Err(Error::Quiet)
+
+                    // Score
+                    if let Some(scores) = result.scores.as_ref()
+                        && !scores.is_empty()
+                    {
+                        let mut scores = scores.values().map(|score| score.to_dialog_info());
+                        cli::log::remark(format!("Score:\n\n{}", scores.join("\n\n")))?;
                     }
+
+                    Ok(())
                 }
-            }
-            Err(err) => match &err {
-                // Special handling for Inspect formatted error messages
-                Error::Py(py_err) => {
-                    let msg = py_err.to_string();
-
-                    // Missing Python package
-                    if let Some(captures) = DEP_ERROR_P.captures(&msg) {
-                        let dep = captures.get(1).unwrap().as_str();
-                        let pkg = captures.get(2).unwrap().as_str();
-                        let msg = format!(
-                            "Missing required package for {dep}\n\
-                            \n\
-                            Task model requires the {pkg} Python package. Install \
-                            it by running 'uv pip install {pkg}'."
-                        );
-                        Err(Error::general(wrap(&msg, term_width())))
-
-                    // Client init error
-                    } else if let Some(captures) = CLIENT_INIT_ERROR_P.captures(&msg) {
-                        let client = captures.get(1).unwrap().as_str();
-                        let missing_env = CLIENT_ENV_P
-                            .captures_iter(&msg)
-                            .map(|c| c.get(1).unwrap().as_str())
-                            .collect_vec()
-                            .join(", ");
-                        let msg = format!(
-                            "Error initializing {client}\n\
-                            \n\
-                            Missing one of: {missing_env}"
-                        );
-                        Err(Error::general(wrap(&msg, term_width())))
-
-                    // Anything else pass through
-                    } else {
-                        Err(err)
-                    }
+                EvalStatus::Error => {
+                    let error = log.error.as_ref().expect("error for status");
+                    cli::log::error(wrap_map(&error.message, term_width() - 4, |s| {
+                        style(s).red().bright().to_string()
+                    }))?;
+                    Err(Error::Quiet)
                 }
-                _ => Err(err),
-            },
+                _ => {
+                    cli::log::error(format!("Unexpected log status: {}", log.status))?;
+                    Err(Error::Quiet)
+                }
+            }
         }
-    })
+        Err(err) => match &err {
+            // Special handling for Inspect formatted error messages
+            Error::Py(py_err) => {
+                let msg = py_err.to_string();
+
+                // Missing Python package
+                if let Some(captures) = DEP_ERROR_P.captures(&msg) {
+                    let dep = captures.get(1).unwrap().as_str();
+                    let pkg = captures.get(2).unwrap().as_str();
+                    let msg = format!(
+                        "Missing required package for {dep}\n\
+                        \n\
+                        Task model requires the {pkg} Python package. Install \
+                        it by running 'uv pip install {pkg}'."
+                    );
+                    Err(Error::general(wrap(&msg, term_width())))
+
+                // Client init error
+                } else if let Some(captures) = CLIENT_INIT_ERROR_P.captures(&msg) {
+                    let client = captures.get(1).unwrap().as_str();
+                    let missing_env = CLIENT_ENV_P
+                        .captures_iter(&msg)
+                        .map(|c| c.get(1).unwrap().as_str())
+                        .collect_vec()
+                        .join(", ");
+                    let msg = format!(
+                        "Error initializing {client}\n\
+                        \n\
+                        Missing one of: {missing_env}"
+                    );
+                    Err(Error::general(wrap(&msg, term_width())))
+
+                // Anything else pass through
+                } else {
+                    Err(err)
+                }
+            }
+            _ => Err(err),
+        },
+    }
 }
 
-fn task_summary(task: &TaskInfo, task_doc: Option<&Docstring>) -> Option<String> {
+pub fn task_summary(task: &TaskInfo, task_doc: Option<&Docstring>) -> Option<String> {
     task_doc
         .and_then(|doc| doc.short_description.clone())
         .or_else(|| task.get_description())
@@ -294,7 +336,7 @@ fn task_summary(task: &TaskInfo, task_doc: Option<&Docstring>) -> Option<String>
         })
 }
 
-fn input_placeholder(task_doc: Option<&Docstring>) -> String {
+pub fn input_placeholder(task_doc: Option<&Docstring>) -> String {
     task_doc
         .map(|doc| {
             if doc.params.len() == 1
@@ -326,7 +368,7 @@ fn input_placeholder(task_doc: Option<&Docstring>) -> String {
         .unwrap_or_default()
 }
 
-fn target_placeholder(task_doc: Option<&Docstring>) -> String {
+pub fn target_placeholder(task_doc: Option<&Docstring>) -> String {
     task_doc
         .map(|doc| {
             doc.returns
@@ -508,11 +550,11 @@ mod tests {
         let captures = CLIENT_INIT_ERROR_P.captures(msg).unwrap();
         assert_eq!("OpenAI", captures.get(1).unwrap().as_str());
 
-        let captures = CLIENT_ENV_P.captures_iter(&msg).collect_vec();
+        let captures = CLIENT_ENV_P.captures_iter(msg).collect_vec();
         assert_eq!(3, captures.len());
         assert_eq!(
             "OPENAI_API_KEY",
-            captures.get(0).unwrap().get(1).unwrap().as_str()
+            captures.first().unwrap().get(1).unwrap().as_str()
         );
         assert_eq!(
             "AZUREAI_OPENAI_API_KEY",
@@ -0,0 +1,513 @@
+//! Batch mode for `gage run` (`--inputs`).
+//!
+//! Runs a task for each row in a JSONL or CSV inputs file. Rows have an
+//! `input` field and optional `target` and task arguments. JSONL task
+//! arguments are specified using an `args` object. CSV task arguments
+//! are specified using `args.NAME` columns. Other fields are ignored so
+//! that dataset files can be used as inputs.
+//!
+//! Each run writes its own log. Unless `--log-per-input` is specified,
+//! run logs are written to a temp directory and merged into a single
+//! log (see `log_merge`).
+
+use std::{fs, path::Path};
+
+use cliclack as cli;
+use console::style;
+use itertools::Itertools;
+use pyo3::Python;
+use serde_json::{Map, Value};
+use tabled::{
+    builder::Builder,
+    settings::{
+        Color,
+        object::{Cell, Columns, Object, Rows},
+        themes::Colorization,
+    },
+};
+
+use crate::{
+    commands::{
+        dataset::common::DatasetFormat,
+        task::{
+            run::{Args, task_summary},
+            select_model_dialog, select_task_dialog,
+        },
+    },
+    dialog::{DialogResult, handle_dialog_result},
+    error::Error,
+    inspect::{
+        log::{EvalStatus, resolve_log_dir},
+        log_file::{read_log_bytes, relative_name, write_log_bytes},
+        log_merge::merge_logs,
+        task::{TaskResult, get_task_doc, run_task},
+    },
+    output::csv_cell,
+    plural, py,
+    result::Result,
+    theme::Colors,
+    util::{PathExt, TableExt, TempDir, relpath},
+};
+
+/// Input row read from an inputs file.
+#[derive(Debug, PartialEq)]
+struct InputRow {
+    input: String,
+    target: Option<String>,
+    task_args: Vec<String>,
+}
+
+/// Result of running a task for an input row.
+struct InputResult {
+    input: String,
+    output: Option<String>,
+    score: Option<String>,
+    error: Option<String>,
+}
+
+pub fn main(args: Args) -> Result<()> {
+    let mut results = Vec::new();
+    let result = handle_dialog_result(batch_dialog(args, &mut results));
+    if !results.is_empty() {
+        println!("{}", results_table(&results));
+    }
+    result
+}
+
+fn batch_dialog(args: Args, results: &mut Vec<InputResult>) -> Result<DialogResult> {
+    cli::intro(style("Run task").bold())?;
+    let inputs = args.inputs.as_deref().expect("--inputs");
+    let rows = read_inputs(inputs)?;
+    if rows.is_empty() {
+        return Err(Error::general(format!(
+            "No inputs in {}",
+            inputs.to_string_lossy()
+        )));
+    }
+
+    py::init();
+    Python::attach(|py| {
+        let task = select_task_dialog(py, args.task.as_deref(), args.path.as_deref(), args.yes)?;
+        let task_doc = get_task_doc(py, &task)?;
+
+        // Task summary
+        if let Some(summary) = task_summary(&task, task_doc.as_ref()) {
+            cli::log::remark(summary)?;
+        }
+
+        // Inputs
+        cli::log::step(format!(
+            "Inputs:\n{}",
+            style(format!(
+                "{} ({} {})",
+                relpath(inputs).to_string_lossy(),
+                rows.len(),
+                plural!("row", rows.len())
+            ))
+            .dim()
+        ))?;
+
+        // Model
+        let model = select_model_dialog(args.model.as_deref(), args.yes)?;
+
+        // Additional options (display only)
+        let mut options = Vec::new();
+        if args.score {
+            options.push("Output will be scored".into());
+        }
+        if !args.task_args.is_empty() {
+            options.push(format!("Task args: {}", args.task_args.iter().join(", ")));
+        }
+        if !args.tags.is_empty() {
+            options.push(format!("Tags: {}", args.tags.iter().join(", ")));
+        }
+        if args.log_per_input {
+            options.push("Log per input".into());
+        }
+        if args.continue_on_error {
+            options.push("Continue on error".into());
+        }
+        if !options.is_empty() {
+            cli::log::step(format!(
+                "Additional options:\n{}",
+                options.iter().map(|s| style(s).dim()).join("\n")
+            ))?;
+        }
+
+        // Confirm before running unless --yes
+        if !args.yes
+            && !cli::confirm(format!(
+                "You are about to run {} for {} {}. Continue?",
+                style(&task.name).cyan().bright(),
+                rows.len(),
+                plural!("input", rows.len())
+            ))
+            .initial_value(true)
+            .interact()?
+        {
+            return Err(Error::Canceled);
+        }
+
+        // Run logs are merged from a temp dir unless logged per input.
+        // The temp dir is removed when dropped unless merging fails.
+        let log_dir = resolve_log_dir(args.log_dir.as_ref());
+        let tmp_log_dir = if args.log_per_input {
+            None
+        } else {
+            Some(TempDir::create("gage-run")?)
+        };
+        let run_log_dir = tmp_log_dir
+            .as_ref()
+            .map(|dir| dir.path().to_path_buf())
+            .unwrap_or_else(|| log_dir.clone());
+        log::debug!("Using log_dir {run_log_dir:?} for batch run");
+
+        // Run task for each input
+        let pb = cli::progress_bar(rows.len() as u64);
+        pb.start(format!("Running {}", task.name));
+        let mut logs = Vec::new();
+        let mut stopped = None;
+        for (i, row) in rows.into_iter().enumerate() {
+            let mut task_args = args.task_args.clone();
+            task_args.extend(row.task_args);
+            let result = run_task(
+                py,
+                task.get_full_name(),
+                row.input.clone(),
+                task_args,
+                model.clone(),
+                row.target.or_else(|| args.score.then_some("".into())),
+                Some(run_log_dir.expect_string()),
+                args.tags.clone(),
+            );
+            pb.inc(1);
+            let result = match result {
+                Ok(result) => {
+                    logs.push(result.log().location.clone());
+                    input_result(row.input, &result)
+                }
+                // Missing model applies to all inputs
+                Err(e @ Error::MissingModel) => {
+                    pb.error("Missing model");
+                    return Err(e);
+                }
+                Err(e) => InputResult {
+                    input: row.input,
+                    output: None,
+                    score: None,
+                    error: Some(match e {
+                        // Python errors are displayed rather than formatted
+                        Error::Py(err) => err.to_string(),
+                        e => e.to_string(),
+                    }),
+                },
+            };
+            let error = result.error.clone();
+            results.push(result);
+            if let Some(error) = error
+                && !args.continue_on_error
+            {
+                stopped = Some((i + 1, error));
+                break;
+            }
+        }
+        let errors = results.iter().filter(|r| r.error.is_some()).count();
+        match stopped {
+            Some(_) => pb.error(format!("Stopped {}", task.name)),
+            None if errors > 0 => pb.error(format!(
+                "Ran {} with {errors} {}",
+                task.name,
+                plural!("error", errors)
+            )),
+            None => pb.stop(format!("Ran {}", task.name)),
+        }
+
+        // Merge run logs
+        if !args.log_per_input && !logs.is_empty() {
+            let merged = merge_logs(&logs, &inputs.to_string_lossy(), &log_dir)
+                .map_err(|e| merge_error(e, &logs, &log_dir, tmp_log_dir))?;
+            cli::log::step(format!(
+                "Log:\n{}",
+                style(relative_name(&log_dir, &merged)).dim()
+            ))?;
+        }
+
+        match stopped {
+            Some((n, error)) => Err(Error::general(format!(
+                "Stopped after error for input {n}: {error}\n\
+                \n\
+                Use --continue-on-error to run remaining inputs."
+            ))),
+            None => Ok(DialogResult::Done),
+        }
+    })
+}
+
+/// Returns an error for a failed merge after saving run logs.
+///
+/// Run logs are copied to `log_dir` so they aren't lost when the temp
+/// dir is removed. If they can't be copied, the temp dir is kept.
+fn merge_error(e: Error, logs: &[String], log_dir: &Path, tmp_log_dir: Option<TempDir>) -> Error {
+    let saved = match logs.iter().try_for_each(|log| copy_log(log, log_dir)) {
+        Ok(()) => format!(
+            "Run logs were saved to {} without merging.",
+            relpath(log_dir).to_string_lossy()
+        ),
+        Err(copy_err) => {
+            log::error!("Error copying run logs: {copy_err}");
+            match tmp_log_dir {
+                Some(dir) => format!("Run logs were kept in {}.", dir.keep().to_string_lossy()),
+                None => "Run logs could not be saved.".into(),
+            }
+        }
+    };
+    Error::general(format!("Error merging run logs: {e}\n\n{saved}"))
+}
+
+fn copy_log(log: &str, log_dir: &Path) -> Result<()> {
+    let file_name = log.rsplit('/').next().unwrap_or(log);
+    write_log_bytes(log_dir, file_name, &read_log_bytes(log)?)?;
+    Ok(())
+}
+
+fn input_result(input: String, result: &TaskResult) -> InputResult {
+    let log = result.log();
+    if log.status != EvalStatus::Success {
+        return InputResult {
+            input,
+            output: None,
+            score: None,
+            error: Some(
+                log.error
+                    .as_ref()
+                    .map(|e| e.message.clone())
+                    .unwrap_or_else(|| format!("log status {}", log.status)),
+            ),
+        };
+    }
+    let sample = result.sample();
+    InputResult {
+        input,
+        output: Some(sample.output.completion.clone()),
+        score: sample
+            .default_score()
+            .or_else(|| {
+                sample
+                    .scores
+                    .as_ref()
+                    .and_then(|scores| scores.iter().min_by_key(|(name, _)| *name))
+            })
+            .map(|(_, score)| score.value.to_string()),
+        error: sample.error.as_ref().map(|e| e.message.clone()),
+    }
+}
+
+fn read_inputs(path: &Path) -> Result<Vec<InputRow>> {
+    let contents = fs::read_to_string(path)
+        .map_err(|e| Error::general(format!("Cannot read {}: {e}", path.to_string_lossy())))?;
+    match DatasetFormat::for_path(path) {
+        DatasetFormat::Jsonl => read_jsonl_inputs(&contents, path),
+        DatasetFormat::Csv => read_csv_inputs(&contents, path),
+    }
+}
+
+fn read_jsonl_inputs(contents: &str, path: &Path) -> Result<Vec<InputRow>> {
+    let mut rows = Vec::new();
+    for (i, line) in contents.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let location = || format!("{}:{}", path.to_string_lossy(), i + 1);
+        let row: Map<String, Value> = serde_json::from_str(line)
+            .map_err(|e| Error::general(format!("{}: {e}", location())))?;
+        let task_args = match row.get("args") {
+            Some(Value::Object(args)) => args
+                .iter()
+                .map(|(name, val)| format!("{name}={}", csv_cell(val)))
+                .collect(),
+            Some(Value::Null) | None => Vec::new(),
+            Some(_) => {
+                return Err(Error::general(format!(
+                    "{}: args must be an object",
+                    location()
+                )));
+            }
+        };
+        rows.push(InputRow {
+            input: match row.get("input") {
+                Some(Value::String(input)) => input.clone(),
+                _ => {
+                    return Err(Error::general(format!(
+                        "{}: input must be a string",
+                        location()
+                    )));
+                }
+            },
+            target: row.get("target").map(csv_cell).filter(|s| !s.is_empty()),
+            task_args,
+        });
+    }
+    Ok(rows)
+}
+
+fn read_csv_inputs(contents: &str, path: &Path) -> Result<Vec<InputRow>> {
+    let csv_error = |e: csv::Error| Error::general(format!("{}: {e}", path.to_string_lossy()));
+    let mut r = csv::Reader::from_reader(contents.as_bytes());
+    let header = r.headers().map_err(csv_error)?.clone();
+    let input_col = header
+        .iter()
+        .position(|name| name == "input")
+        .ok_or_else(|| {
+            Error::general(format!(
+                "{}: missing 'input' column",
+                path.to_string_lossy()
+            ))
+        })?;
+    let target_col = header.iter().position(|name| name == "target");
+    let arg_cols = header
+        .iter()
+        .enumerate()
+        .filter_map(|(i, name)| name.strip_prefix("args.").map(|name| (i, name)))
+        .collect::<Vec<_>>();
+    let mut rows = Vec::new();
+    for record in r.records() {
+        let record = record.map_err(csv_error)?;
+        let cell = |i: usize| record.get(i).unwrap_or_default();
+        rows.push(InputRow {
+            input: cell(input_col).into(),
+            target: target_col
+                .map(cell)
+                .filter(|s| !s.is_empty())
+                .map(String::from),
+            task_args: arg_cols
+                .iter()
+                .filter(|(i, _)| !cell(*i).is_empty())
+                .map(|(i, name)| format!("{name}={}", cell(*i)))
+                .collect(),
+        });
+    }
+    Ok(rows)
+}
+
+fn results_table(results: &[InputResult]) -> String {
+    let one_line = |s: &str| s.split_whitespace().join(" ");
+    let mut table = Builder::new();
+    table.push_record(["#", "Input", "Output", "Score"]);
+    for (i, result) in results.iter().enumerate() {
+        table.push_record([
+            (i + 1).to_string(),
+            one_line(&result.input),
+            match (&result.error, &result.output) {
+                (Some(error), _) => one_line(error),
+                (None, Some(output)) => one_line(output),
+                (None, None) => String::new(),
+            },
+            match result.score.as_deref() {
+                Some("C") => "Correct".into(),
+                Some("I") => "Incorrect".into(),
+                score => score.unwrap_or_default().into(),
+            },
+        ]);
+    }
+    let mut table = table.build();
+    table
+        .with_term_fit()
+        .with_rounded()
+        .with_col_labels()
+        .with(Colorization::exact(
+            [Color::FG_BRIGHT_CYAN],
+            Columns::first().intersect(Rows::new(1..)),
+        ));
+    for (i, result) in results.iter().enumerate() {
+        let row = i + 1;
+        if result.error.is_some() {
+            table.with(Colorization::exact([Color::FG_RED], Cell::new(row, 2)));
+        }
+        match result.score.as_deref() {
+            Some("C") => table.with(Colorization::exact([Color::FG_CYAN], Cell::new(row, 3))),
+            Some("I") => table.with(Colorization::exact([Color::FG_RED], Cell::new(row, 3))),
+            _ => table.with(Colorization::exact([Colors::dim()], Cell::new(row, 3))),
+        };
+    }
+    table.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::{error::Error, util::TempDir};
+
+    use super::{InputRow, merge_error, read_csv_inputs, read_jsonl_inputs};
+
+    #[test]
+    fn test_read_inputs() {
+        let path = Path::new("inputs");
+        let jsonl = r#"{"input": "Alice", "target": "Hello Alice"}
+
+{"id": 2, "input": "Bob", "args": {"style": "formal", "n": 2}}
+"#;
+        let csv = "id,input,target,args.style,lang\n1,Alice,Hello Alice,,en\n2,Bob,,formal,en\n";
+        let expected = [
+            InputRow {
+                input: "Alice".into(),
+                target: Some("Hello Alice".into()),
+                task_args: vec![],
+            },
+            InputRow {
+                input: "Bob".into(),
+                target: None,
+                task_args: vec!["style=formal".into(), "n=2".into()],
+            },
+        ];
+        assert_eq!(read_jsonl_inputs(jsonl, path).unwrap(), expected);
+
+        let rows = read_csv_inputs(csv, path).unwrap();
+        assert_eq!(rows[0], expected[0]);
+        assert_eq!(rows[1].task_args, ["style=formal"]);
+
+        assert!(read_jsonl_inputs(r#"{"input": ["Alice"]}"#, path).is_err());
+        assert!(read_csv_inputs("text\nAlice\n", path).is_err());
+    }
+
+    #[test]
+    fn test_merge_error() {
+        let tmp = TempDir::create("gage-run-test").unwrap();
+        let log = tmp.path().join("2025-01-01T10-00-00+00-00_hello_A1.eval");
+        std::fs::write(&log, "log").unwrap();
+        let logs = [log.to_string_lossy().to_string()];
+        let log_dir = TempDir::create("gage-logs-test").unwrap();
+
+        // Run logs are copied to the log dir
+        let err = merge_error(Error::general("bad log"), &logs, log_dir.path(), Some(tmp));
+        assert!(err.to_string().contains("bad log"));
+        assert!(err.to_string().contains("saved to"));
+        assert_eq!(
+            std::fs::read(
+                log_dir
+                    .path()
+                    .join("2025-01-01T10-00-00+00-00_hello_A1.eval")
+            )
+            .unwrap(),
+            b"log"
+        );
+
+        // Temp dir is kept if logs can't be copied
+        let tmp = TempDir::create("gage-run-test").unwrap();
+        let missing = [tmp
+            .path()
+            .join("missing.eval")
+            .to_string_lossy()
+            .to_string()];
+        let tmp_path = tmp.path().to_path_buf();
+        let err = merge_error(
+            Error::general("bad log"),
+            &missing,
+            log_dir.path(),
+            Some(tmp),
+        );
+        assert!(err.to_string().contains("kept in"));
+        assert!(tmp_path.exists());
+        std::fs::remove_dir_all(&tmp_path).unwrap();
+    }
+}
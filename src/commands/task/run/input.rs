@@ -0,0 +1,280 @@
+//! Input prompt with history for `gage run --repl`.
+//!
+//! The prompt works like a multiline cliclack `Input` - Enter adds a
+//! line, Esc shows a preview, and Enter in preview submits. Up on the
+//! first line shows the previous input and Down on the last line shows
+//! the next input. Recalled inputs are shown in preview so Enter runs
+//! them again.
+//!
+//! cliclack doesn't support custom prompts so the prompt is rendered
+//! here using the CLI theme.
+
+use std::io;
+
+use cliclack::{StringCursor, Theme, ThemeState};
+use console::{Key, Term, measure_text_width};
+
+use crate::theme::CliTheme;
+
+/// Input prompt with history.
+pub struct HistoryInput<'a> {
+    prompt: String,
+    placeholder: StringCursor,
+    history: &'a [String],
+
+    /// Position in history - `history.len()` for a new input.
+    pos: usize,
+
+    /// New input saved while showing history.
+    draft: String,
+
+    input: StringCursor,
+    preview: bool,
+}
+
+impl<'a> HistoryInput<'a> {
+    /// Returns a prompt for an input with previous inputs in `history`.
+    ///
+    /// Enter without typing submits the last input in `history`.
+    pub fn new(prompt: &str, history: &'a [String]) -> Self {
+        let mut placeholder = StringCursor::default();
+        if let Some(last) = history.last() {
+            placeholder.extend(&format!("{last} (default)"));
+        }
+        Self {
+            prompt: prompt.into(),
+            placeholder,
+            history,
+            pos: history.len(),
+            draft: String::new(),
+            input: StringCursor::default(),
+            // Preview to submit the last input using Enter
+            preview: !history.is_empty(),
+        }
+    }
+
+    /// Sets the placeholder used when there are no previous inputs.
+    pub fn placeholder(mut self, placeholder: &str) -> Self {
+        if self.history.is_empty() {
+            self.placeholder.extend(placeholder);
+        }
+        self
+    }
+
+    /// Shows the prompt and returns the submitted input.
+    ///
+    /// Returns an `Interrupted` error if the prompt is canceled with
+    /// Ctrl-C or Esc in preview.
+    pub fn interact(&mut self) -> io::Result<String> {
+        let term = Term::stderr();
+        if !term.is_term() {
+            return Err(io::ErrorKind::NotConnected.into());
+        }
+        term.hide_cursor()?;
+        let result = self.interact_on(&term);
+        term.show_cursor()?;
+        result
+    }
+
+    fn interact_on(&mut self, term: &Term) -> io::Result<String> {
+        let mut state = ThemeState::Active;
+        let mut lines = 0;
+        loop {
+            let frame = self.render(&state);
+            term.clear_last_lines(lines)?;
+            term.write_str(&frame)?;
+            lines = frame_lines(&frame, term.size().1 as usize);
+            match state {
+                ThemeState::Submit => return Ok(self.input.to_string()),
+                ThemeState::Cancel => return Err(io::ErrorKind::Interrupted.into()),
+                _ => {}
+            }
+            state = match term.read_key() {
+                Ok(key) => self.on_key(key),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => ThemeState::Cancel,
+                Err(e) => return Err(e),
+            };
+        }
+    }
+
+    fn on_key(&mut self, key: Key) -> ThemeState {
+        match key {
+            Key::Enter if self.preview => return self.submit(),
+            Key::Escape if self.preview => return ThemeState::Cancel,
+            Key::Escape => self.preview = true,
+            Key::ArrowUp if self.preview || self.on_first_line() => {
+                if let Some(pos) = self.pos.checked_sub(1) {
+                    self.recall(pos);
+                }
+            }
+            Key::ArrowDown if self.preview || self.on_last_line() => {
+                if self.pos < self.history.len() {
+                    self.recall(self.pos + 1);
+                }
+            }
+            key => {
+                let input = &mut self.input;
+                match key {
+                    Key::Enter => input.insert('\n'),
+                    Key::Char(c) if !c.is_ascii_control() => input.insert(c),
+                    Key::Backspace => input.delete_left(),
+                    Key::Del => input.delete_right(),
+                    Key::ArrowLeft => input.move_left(),
+                    Key::ArrowRight => input.move_right(),
+                    Key::ArrowUp => input.move_up(),
+                    Key::ArrowDown => input.move_down(),
+                    Key::Home => input.move_home(),
+                    Key::End => input.move_end(),
+                    // Ctrl-W
+                    Key::Char('\u{17}') => input.delete_word_to_the_left(),
+                    _ => return ThemeState::Active,
+                }
+                self.preview = false;
+            }
+        }
+        ThemeState::Active
+    }
+
+    fn submit(&mut self) -> ThemeState {
+        if self.input.is_empty() {
+            match self.history.last() {
+                Some(last) => self.input.extend(last),
+                None => return ThemeState::Error("Input required".into()),
+            }
+        }
+        ThemeState::Submit
+    }
+
+    fn on_first_line(&self) -> bool {
+        let (left, _, _) = self.input.split();
+        !left.contains('\n')
+    }
+
+    fn on_last_line(&self) -> bool {
+        let (_, _, right) = self.input.split();
+        !right.contains('\n')
+    }
+
+    /// Shows the input at `pos` in history.
+    ///
+    /// Inputs from history are shown in preview. The new input is
+    /// restored when moving past the last input in history.
+    fn recall(&mut self, pos: usize) {
+        if self.pos == self.history.len() {
+            self.draft = self.input.to_string();
+        }
+        self.pos = pos;
+        let (text, preview) = match self.history.get(pos) {
+            Some(input) => (input.as_str(), true),
+            None => (self.draft.as_str(), false),
+        };
+        self.input.clear();
+        for c in text.chars() {
+            self.input.insert(c);
+        }
+        self.preview = preview;
+    }
+
+    fn render(&self, state: &ThemeState) -> String {
+        let theme = CliTheme;
+        let help = match (self.preview, self.history.is_empty()) {
+            (true, false) => "[Enter](Submit)  [↑↓](History)",
+            (true, true) => "[Enter](Submit)",
+            (false, _) => "[Esc](Preview)",
+        };
+        let input = if self.input.is_empty() {
+            theme.format_placeholder(state, &self.placeholder)
+        } else {
+            theme.format_input(state, &self.input)
+        };
+        theme.format_header(state, &self.prompt)
+            + &input
+            + &theme.format_footer_with_message(state, help)
+    }
+}
+
+/// Returns the number of terminal lines used to show a frame.
+fn frame_lines(frame: &str, width: usize) -> usize {
+    frame
+        .lines()
+        .map(|line| measure_text_width(line).div_ceil(width.max(1)).max(1))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use console::Key;
+
+    use super::HistoryInput;
+
+    fn submit(input: &mut HistoryInput, keys: impl IntoIterator<Item = Key>) -> String {
+        for key in keys {
+            input.on_key(key);
+        }
+        if !input.preview {
+            input.on_key(Key::Escape);
+        }
+        input.on_key(Key::Enter);
+        input.input.to_string()
+    }
+
+    #[test]
+    fn test_history() {
+        let history = ["a".to_string(), "b\nc".to_string()];
+
+        // Enter without typing submits the last input
+        let mut input = HistoryInput::new("Input:", &history);
+        input.on_key(Key::Enter);
+        assert_eq!(input.input.to_string(), "b\nc");
+
+        // Up and Down move through history
+        let mut input = HistoryInput::new("Input:", &history);
+        assert_eq!(submit(&mut input, [Key::ArrowUp, Key::ArrowUp]), "a");
+        let mut input = HistoryInput::new("Input:", &history);
+        assert_eq!(
+            submit(&mut input, [Key::ArrowUp, Key::ArrowUp, Key::ArrowDown]),
+            "b\nc"
+        );
+
+        // Up past the first input is ignored
+        let mut input = HistoryInput::new("Input:", &history);
+        assert_eq!(
+            submit(&mut input, [Key::ArrowUp, Key::ArrowUp, Key::ArrowUp]),
+            "a"
+        );
+
+        // Typed input is restored after Down past the last input
+        let mut input = HistoryInput::new("Input:", &history);
+        assert_eq!(
+            submit(
+                &mut input,
+                [Key::Char('x'), Key::ArrowUp, Key::ArrowDown, Key::Char('y')]
+            ),
+            "xy"
+        );
+
+        // Up in multiline input moves the cursor until the first line
+        let mut input = HistoryInput::new("Input:", &history);
+        assert_eq!(
+            submit(
+                &mut input,
+                [
+                    Key::Char('x'),
+                    Key::Enter,
+                    Key::Char('y'),
+                    Key::ArrowUp,
+                    Key::Char('z')
+                ]
+            ),
+            "xz\ny"
+        );
+        let mut input = HistoryInput::new("Input:", &history);
+        assert_eq!(
+            submit(
+                &mut input,
+                [Key::Char('x'), Key::Enter, Key::ArrowUp, Key::ArrowUp]
+            ),
+            "b\nc"
+        );
+    }
+}
@@ -0,0 +1,331 @@
+//! Interactive mode for `gage run` (`--repl`).
+//!
+//! Loads a task once and runs it for each input. The task is loaded
+//! again when task arguments change. Lines starting with `:` are
+//! commands that change run settings between inputs (see `HELP`).
+//! Previous inputs are kept for the session - use Up and Down to recall
+//! them, press Enter without typing to run the last input again, or use
+//! `:history` and `:again N` to run an earlier input.
+
+use cliclack as cli;
+use console::style;
+use itertools::Itertools;
+use pyo3::Python;
+
+use crate::{
+    commands::task::{
+        run::{Args, input::HistoryInput, input_placeholder, show_result, task_summary},
+        select_model_dialog, select_task_dialog,
+    },
+    dialog::{DialogResult, handle_dialog_result},
+    error::Error,
+    inspect::{
+        log::resolve_log_dir,
+        task::{get_task_doc, load_task, run_loaded_task},
+    },
+    py,
+    result::Result,
+    util::PathExt,
+};
+
+const HELP: &str = "\
+:model [MODEL]     Set the model (use the default model if not specified)
+:target [TARGET]   Set the target used to score output (clear if not specified)
+:arg NAME=VALUE    Set a task argument (NAME without a value removes it)
+:tags [TAG...]     Set run tags (clear if not specified)
+:show              Show current settings
+:history           Show previous inputs
+:again [N]         Run a previous input again (default is the last input)
+:help              Show this help
+:quit              Exit (or press Ctrl-C)";
+
+/// REPL command.
+#[derive(Debug, PartialEq)]
+enum Command {
+    Model(Option<String>),
+    Target(Option<String>),
+    Arg(String, Option<String>),
+    Tags(Vec<String>),
+    Show,
+    History,
+    Again(Option<usize>),
+    Help,
+    Quit,
+}
+
+/// Settings applied to each run.
+struct Settings {
+    model: Option<String>,
+    target: Option<String>,
+    task_args: Vec<String>,
+    tags: Vec<String>,
+    score: bool,
+}
+
+impl Settings {
+    /// Sets a task argument, replacing any existing value.
+    ///
+    /// If `value` is None, the argument is removed.
+    fn set_arg(&mut self, name: &str, value: Option<&str>) {
+        self.task_args
+            .retain(|arg| arg.split_once('=').map(|(n, _)| n) != Some(name));
+        if let Some(value) = value {
+            self.task_args.push(format!("{name}={value}"));
+        }
+    }
+
+    fn show(&self) -> String {
+        let none = || style("None").dim().to_string();
+        [
+            format!(
+                "Model: {}",
+                self.model.as_deref().map(String::from).unwrap_or_else(none)
+            ),
+            format!(
+                "Target: {}",
+                self.target
+                    .as_deref()
+                    .map(String::from)
+                    .unwrap_or_else(none)
+            ),
+            format!(
+                "Task args: {}",
+                if self.task_args.is_empty() {
+                    none()
+                } else {
+                    self.task_args.join(", ")
+                }
+            ),
+            format!(
+                "Tags: {}",
+                if self.tags.is_empty() {
+                    none()
+                } else {
+                    self.tags.join(", ")
+                }
+            ),
+        ]
+        .join("\n")
+    }
+}
+
+pub fn main(args: Args) -> Result<()> {
+    handle_dialog_result(repl_dialog(args))
+}
+
+fn repl_dialog(args: Args) -> Result<DialogResult> {
+    cli::intro(style("Run task").bold())?;
+    py::init();
+    Python::attach(|py| {
+        let task = select_task_dialog(py, args.task.as_deref(), args.path.as_deref(), args.yes)?;
+        let task_doc = get_task_doc(py, &task)?;
+
+        // Task summary
+        if let Some(summary) = task_summary(&task, task_doc.as_ref()) {
+            cli::log::remark(summary)?;
+        }
+
+        let mut settings = Settings {
+            model: select_model_dialog(args.model.as_deref(), args.yes)?,
+            target: args.target,
+            task_args: args.task_args,
+            tags: args.tags,
+            score: args.score,
+        };
+        let log_dir = resolve_log_dir(args.log_dir.as_ref());
+        log::debug!("Using log_dir {log_dir:?} for run");
+        let mut loaded = load_task(py, task.get_full_name(), settings.task_args.clone())?;
+        let placeholder = input_placeholder(task_doc.as_ref());
+
+        cli::log::remark(format!(
+            "Enter an input to run {} or :help for commands",
+            style(&task.name).cyan().bright()
+        ))?;
+
+        let mut history: Vec<String> = Vec::new();
+        let mut next_input = args.input;
+        loop {
+            // Input
+            let input = match next_input.take() {
+                Some(input) => {
+                    cli::log::step(format!(
+                        "Input:\n{}",
+                        input.split("\n").map(|s| style(s).dim()).join("\n")
+                    ))?;
+                    input
+                }
+                None => {
+                    let mut prompt =
+                        HistoryInput::new("Input:", &history).placeholder(&placeholder);
+                    match prompt.interact() {
+                        Ok(input) => input,
+                        Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {
+                            return Ok(DialogResult::Done);
+                        }
+                        Err(e) => return Err(e.into()),
+                    }
+                }
+            };
+
+            // Commands
+            if input.trim_start().starts_with(':') {
+                let cmd = match parse_command(input.trim()) {
+                    Ok(cmd) => cmd,
+                    Err(msg) => {
+                        cli::log::error(msg)?;
+                        continue;
+                    }
+                };
+                match cmd {
+                    Command::Model(model) => settings.model = model,
+                    Command::Target(target) => settings.target = target,
+                    Command::Arg(name, value) => settings.set_arg(&name, value.as_deref()),
+                    Command::Tags(tags) => settings.tags = tags,
+                    Command::Show => {}
+                    Command::History => {
+                        cli::log::info(if history.is_empty() {
+                            style("No inputs").dim().to_string()
+                        } else {
+                            history
+                                .iter()
+                                .enumerate()
+                                .map(|(i, input)| {
+                                    format!("{} {}", style(i + 1).cyan(), input.replace('\n', " "))
+                                })
+                                .join("\n")
+                        })?;
+                        continue;
+                    }
+                    Command::Again(n) => {
+                        match n
+                            .map(|n| history.get(n.wrapping_sub(1)))
+                            .unwrap_or(history.last())
+                        {
+                            Some(input) => next_input = Some(input.clone()),
+                            None => cli::log::error("No such input - try :history")?,
+                        }
+                        continue;
+                    }
+                    Command::Help => {
+                        cli::log::info(HELP)?;
+                        continue;
+                    }
+                    Command::Quit => return Ok(DialogResult::Done),
+                }
+                cli::log::step(format!("Settings:\n{}", settings.show()))?;
+                continue;
+            }
+
+            // Run
+            history.retain(|prev| *prev != input);
+            history.push(input.clone());
+            if loaded.task_args != settings.task_args {
+                match load_task(py, task.get_full_name(), settings.task_args.clone()) {
+                    Ok(task) => loaded = task,
+                    Err(e) => {
+                        show_error(e)?;
+                        continue;
+                    }
+                }
+            }
+            let pb = cli::spinner();
+            pb.start(format!("Running {}", task.name));
+            let result = run_loaded_task(
+                py,
+                &loaded,
+                input,
+                settings.model.clone(),
+                settings
+                    .target
+                    .clone()
+                    .or_else(|| settings.score.then_some("".into())),
+                Some(log_dir.expect_string()),
+                settings.tags.clone(),
+            );
+            pb.clear();
+
+            // Errors are shown without ending the session
+            if let Err(e) = show_result(result) {
+                show_error(e)?;
+            }
+        }
+    })
+}
+
+fn show_error(e: Error) -> std::io::Result<()> {
+    match e {
+        Error::Quiet => Ok(()),
+        Error::Py(err) => cli::log::error(err.to_string()),
+        e => cli::log::error(e.to_string()),
+    }
+}
+
+fn parse_command(line: &str) -> std::result::Result<Command, String> {
+    let line = line.strip_prefix(':').unwrap_or(line);
+    let (name, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+    let rest = rest.trim();
+    let value = (!rest.is_empty()).then(|| rest.to_string());
+    match name {
+        "model" => Ok(Command::Model(value)),
+        "target" => Ok(Command::Target(value)),
+        "arg" => match rest.split_once('=') {
+            Some((name, value)) if !name.trim().is_empty() => Ok(Command::Arg(
+                name.trim().into(),
+                Some(value.trim())
+                    .filter(|s| !s.is_empty())
+                    .map(String::from),
+            )),
+            None if !rest.is_empty() => Ok(Command::Arg(rest.into(), None)),
+            _ => Err("Usage: :arg NAME=VALUE".into()),
+        },
+        "tags" => Ok(Command::Tags(
+            rest.split_whitespace().map(String::from).collect(),
+        )),
+        "show" => Ok(Command::Show),
+        "history" => Ok(Command::History),
+        "again" => match value.map(|n| n.parse::<usize>()) {
+            Some(Ok(n)) => Ok(Command::Again(Some(n))),
+            Some(Err(_)) => Err("Usage: :again [N]".into()),
+            None => Ok(Command::Again(None)),
+        },
+        "help" | "h" | "?" => Ok(Command::Help),
+        "quit" | "q" | "exit" => Ok(Command::Quit),
+        _ => Err(format!("Unknown command ':{name}' - try :help")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Command, parse_command};
+
+    #[test]
+    fn test_parse_command() {
+        assert_eq!(
+            parse_command(":model openai/gpt-4o"),
+            Ok(Command::Model(Some("openai/gpt-4o".into())))
+        );
+        assert_eq!(parse_command(":model"), Ok(Command::Model(None)));
+        assert_eq!(
+            parse_command(":target Hello Alice"),
+            Ok(Command::Target(Some("Hello Alice".into())))
+        );
+        assert_eq!(
+            parse_command(":arg style = formal"),
+            Ok(Command::Arg("style".into(), Some("formal".into())))
+        );
+        assert_eq!(
+            parse_command(":arg style"),
+            Ok(Command::Arg("style".into(), None))
+        );
+        assert!(parse_command(":arg").is_err());
+        assert!(parse_command(":arg =formal").is_err());
+        assert_eq!(
+            parse_command(":tags a b"),
+            Ok(Command::Tags(vec!["a".into(), "b".into()]))
+        );
+        assert_eq!(parse_command(":again 2"), Ok(Command::Again(Some(2))));
+        assert!(parse_command(":again last").is_err());
+        assert_eq!(parse_command(":q"), Ok(Command::Quit));
+        assert!(parse_command(":frob").is_err());
+    }
+}
@@ -1,7 +1,7 @@
 #![allow(dead_code)]
 
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     fs::File,
     io::{self, Read},
     path::{Path, PathBuf},
@@ -9,12 +9,15 @@ use std::{
 
 use serde::Deserialize;
 
-use crate::{error::Error, result::Result, util::find_try_parents};
+use crate::{env, error::Error, result::Result, util::find_try_parents};
 
 #[derive(Deserialize, Debug)]
 pub struct Config {
     #[serde(default)]
     pub profiles: HashMap<String, Profile>,
+    pub retention: Option<Retention>,
+    #[serde(default)]
+    pub evals: HashMap<String, EvalSuite>,
     #[serde(skip)]
     pub path: PathBuf,
 }
@@ -23,6 +26,8 @@ impl Default for Config {
     fn default() -> Self {
         Self {
             profiles: Default::default(),
+            retention: None,
+            evals: Default::default(),
             path: "gage.toml".into(),
         }
     }
@@ -33,12 +38,119 @@ pub struct Profile {
     pub help: Option<String>,
     pub secrets: Option<String>,
     pub log_dir: Option<String>,
+    pub retention: Option<Retention>,
+    pub s3: Option<S3Config>,
 
     #[serde(default)]
     pub env: HashMap<String, String>,
+
+    /// Suite fields that override project suites when the profile is
+    /// active.
+    #[serde(default)]
+    pub evals: HashMap<String, EvalSuite>,
+}
+
+/// S3 settings used for `s3://` log directories.
+///
+/// Credentials are read from the standard AWS environment variables,
+/// which may be set using profile `env`.
+#[derive(Deserialize, Default, Debug, Clone)]
+pub struct S3Config {
+    pub endpoint_url: Option<String>,
+    pub region: Option<String>,
+}
+
+/// Log retention policy used by `gage log prune`.
+#[derive(Deserialize, Default, Debug, Clone)]
+pub struct Retention {
+    pub keep: Option<usize>,
+    pub older_than: Option<String>,
+    #[serde(default)]
+    pub status: Vec<String>,
+}
+
+/// Named eval suite run using `gage eval --suite`.
+///
+/// Fields are optional so that profile suites can override individual
+/// fields of a project suite (see `merge`).
+#[derive(Deserialize, Default, Debug, Clone)]
+pub struct EvalSuite {
+    pub help: Option<String>,
+    pub tasks: Option<Vec<String>>,
+    pub models: Option<Vec<String>>,
+    pub task_args: Option<BTreeMap<String, toml::Value>>,
+    pub dataset: Option<String>,
+    pub limit: Option<usize>,
+    pub samples: Option<Vec<String>>,
+    pub shuffle: Option<bool>,
+    pub epochs: Option<usize>,
+    pub sandbox: Option<String>,
+    pub baseline: Option<String>,
+    pub min_score: Option<f64>,
+    pub max_drop: Option<f64>,
+}
+
+impl EvalSuite {
+    /// Returns a suite with fields from `other` where defined.
+    ///
+    /// Task args are merged by name.
+    pub fn merge(&self, other: &EvalSuite) -> EvalSuite {
+        let task_args = match (&self.task_args, &other.task_args) {
+            (Some(base), Some(other)) => {
+                let mut merged = base.clone();
+                merged.extend(other.clone());
+                Some(merged)
+            }
+            (base, other) => other.clone().or(base.clone()),
+        };
+        EvalSuite {
+            help: other.help.clone().or(self.help.clone()),
+            tasks: other.tasks.clone().or(self.tasks.clone()),
+            models: other.models.clone().or(self.models.clone()),
+            task_args,
+            dataset: other.dataset.clone().or(self.dataset.clone()),
+            limit: other.limit.or(self.limit),
+            samples: other.samples.clone().or(self.samples.clone()),
+            shuffle: other.shuffle.or(self.shuffle),
+            epochs: other.epochs.or(self.epochs),
+            sandbox: other.sandbox.clone().or(self.sandbox.clone()),
+            baseline: other.baseline.clone().or(self.baseline.clone()),
+            min_score: other.min_score.or(self.min_score),
+            max_drop: other.max_drop.or(self.max_drop),
+        }
+    }
+
+    /// Returns task args as NAME=VALUE strings.
+    ///
+    /// String values are used as is. Other values use their TOML
+    /// representation.
+    pub fn task_arg_strings(&self) -> Vec<String> {
+        self.task_args
+            .iter()
+            .flatten()
+            .map(|(name, val)| match val {
+                toml::Value::String(s) => format!("{name}={s}"),
+                val => format!("{name}={val}"),
+            })
+            .collect()
+    }
 }
 
 impl Config {
+    /// Returns the named eval suite.
+    ///
+    /// If a profile is active and defines the suite, its fields override
+    /// those of the project suite.
+    pub fn eval_suite(&self, name: &str) -> Option<EvalSuite> {
+        let profile = env::get("GAGE_PROFILE")
+            .and_then(|profile| self.profiles.get(&profile))
+            .and_then(|profile| profile.evals.get(name));
+        match (self.evals.get(name), profile) {
+            (Some(suite), Some(profile)) => Some(suite.merge(profile)),
+            (suite, profile) => suite.or(profile).cloned(),
+        }
+    }
+
     pub fn from_arg(arg: Option<&PathBuf>) -> Result<Self> {
         arg.map(|path| Self::from_file(path))
             .unwrap_or_else(Self::from_default_file)
@@ -102,5 +214,87 @@ mod tests {
         assert_eq!("secrets.json", foo.secrets.as_ref().unwrap());
         assert_eq!("123", foo.env["bar"]);
         assert_eq!("321", foo.env["baz"]);
+        assert!(foo.s3.is_none());
+    }
+
+    #[test]
+    fn test_s3_toml() {
+        let config: Config = toml::from_str(
+            r#"
+            [profiles.shared]
+            log_dir = "s3://eval-logs/team"
+            s3.endpoint_url = "http://localhost:9000"
+            s3.region = "us-west-2"
+            "#,
+        )
+        .unwrap();
+
+        let s3 = config.profiles["shared"].s3.as_ref().unwrap();
+        assert_eq!("http://localhost:9000", s3.endpoint_url.as_ref().unwrap());
+        assert_eq!("us-west-2", s3.region.as_ref().unwrap());
+    }
+
+    #[test]
+    fn test_retention_toml() {
+        let config: Config = toml::from_str(
+            r#"
+            [retention]
+            keep = 10
+
+            [profiles.ci.retention]
+            older_than = "30d"
+            status = ["error", "cancelled"]
+            "#,
+        )
+        .unwrap();
+
+        let retention = config.retention.as_ref().unwrap();
+        assert_eq!(Some(10), retention.keep);
+        assert!(retention.older_than.is_none());
+
+        let ci = config.profiles["ci"].retention.as_ref().unwrap();
+        assert!(ci.keep.is_none());
+        assert_eq!("30d", ci.older_than.as_ref().unwrap());
+        assert_eq!(vec!["error", "cancelled"], ci.status);
+    }
+
+    #[test]
+    fn test_evals_toml() {
+        let config: Config = toml::from_str(
+            r#"
+            [evals.nightly]
+            help = "Nightly evals"
+            tasks = ["greet", "summarize"]
+            models = ["openai/gpt-4o", "anthropic/claude-sonnet-4-0"]
+            task_args = { style = "formal", max_words = 50 }
+            limit = 100
+            epochs = 2
+
+            [profiles.ci.evals.nightly]
+            models = ["openai/gpt-4o-mini"]
+            task_args = { max_words = 20 }
+            limit = 10
+            "#,
+        )
+        .unwrap();
+
+        let nightly = &config.evals["nightly"];
+        assert_eq!(
+            Some(vec!["greet".into(), "summarize".into()]),
+            nightly.tasks
+        );
+        assert_eq!(2, nightly.models.as_ref().unwrap().len());
+        assert_eq!(
+            vec!["max_words=50", "style=formal"],
+            nightly.task_arg_strings()
+        );
+
+        let ci = nightly.merge(&config.profiles["ci"].evals["nightly"]);
+        assert_eq!(Some("Nightly evals"), ci.help.as_deref());
+        assert_eq!(Some(vec!["greet".into(), "summarize".into()]), ci.tasks);
+        assert_eq!(Some(vec!["openai/gpt-4o-mini".into()]), ci.models);
+        assert_eq!(vec!["max_words=20", "style=formal"], ci.task_arg_strings());
+        assert_eq!(Some(10), ci.limit);
+        assert_eq!(Some(2), ci.epochs);
     }
 }
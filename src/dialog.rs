@@ -29,7 +29,9 @@ pub fn handle_dialog_result(result: Result<DialogResult>) -> Result<()> {
             }
             _ => cli::outro_cancel("Error\n")?,
         },
-        Err(Error::Custom(_)) => cli::outro_cancel("Error\n")?,
+        Err(Error::Custom(_) | Error::Code(..) | Error::MissingModel) => {
+            cli::outro_cancel("Error\n")?
+        }
         Err(Error::Quiet) => cli::outro_cancel("Error")?,
         Err(Error::Canceled) => cli::outro_cancel("Canceled")?,
     };
@@ -6,6 +6,8 @@ pub enum Error {
     Py(pyo3::PyErr),
     IO(std::io::Error),
     Custom(String),
+    Code(u8, String),
+    MissingModel,
     Quiet,
     Canceled,
 }
@@ -27,6 +29,12 @@ impl Display for Error {
             }
             Self::IO(io_error) => Display::fmt(io_error, f),
             Self::Custom(msg) => f.write_str(msg),
+            Self::Code(_, msg) => f.write_str(msg),
+            Self::MissingModel => f.write_str(
+                "Missing required model\n\
+                \n\
+                Specify --model or set GAGE_MODEL or INSPECT_EVAL_MODEL.",
+            ),
             Self::Quiet => Ok(()),
             Self::Canceled => Ok(()),
         }
@@ -56,6 +64,11 @@ impl Error {
         Self::Custom(msg.to_string())
     }
 
+    /// Returns an error that exits with `code` rather than 1.
+    pub fn with_code(code: u8, msg: impl Display) -> Self {
+        Self::Code(code, msg.to_string())
+    }
+
     pub fn no_tasks() -> Self {
         // TODO - review error msg - suggest -p/--path or TASTPATH env?
         Self::Custom("Cannot find tasks to run".into())
@@ -79,11 +92,6 @@ impl Error {
     }
 
     pub fn missing_required_model() -> Self {
-        Self::Custom(
-            "Missing required model\n\
-            \n\
-            Specify --model or set GAGE_MODEL or INSPECT_EVAL_MODEL."
-                .into(),
-        )
+        Self::MissingModel
     }
 }
@@ -2,10 +2,14 @@
 
 use pyo3::{
     Bound, FromPyObject, Python,
+    exceptions::PyAttributeError,
     types::{PyAnyMethods, PyDict, PyDictMethods},
 };
 
+use serde::Deserialize;
+
 use crate::{
+    error::Error,
     inspect::{
         Metadata,
         log::{SampleId, SampleInput, Target},
@@ -38,8 +42,36 @@ pub fn list_datasets<'py>(py: Python<'py>, path: &Vec<String>) -> Result<Vec<Dat
     )
 }
 
+/// Loads the samples for a dataset.
+///
+/// Samples are loaded by `gage_inspect`, using the same loader that
+/// finds datasets for `list_datasets`.
+pub fn load_dataset<'py>(py: Python<'py>, dataset: &DatasetInfo) -> Result<Vec<Sample>> {
+    let samples = py_call(
+        py,
+        "gage_inspect.dataset",
+        "load_dataset",
+        (&dataset.file, &dataset.name),
+    )
+    .map_err(|err| {
+        if err.is_instance_of::<PyAttributeError>(py) {
+            Error::general(
+                "Installed gage_inspect doesn't support loading datasets\n\
+                \n\
+                Upgrade gage-inspect to show dataset samples.",
+            )
+        } else {
+            Error::Py(err)
+        }
+    })?;
+    samples
+        .try_iter()?
+        .map(|sample| Ok(sample?.extract()?))
+        .collect()
+}
+
 /// Sample for an evaluation task.
-#[derive(FromPyObject, Debug)]
+#[derive(FromPyObject, Deserialize, Debug)]
 pub struct Sample {
     /// The input to be submitted to the model.
     pub input: SampleInput,
@@ -48,6 +80,7 @@ pub struct Sample {
     pub choices: Option<Vec<String>>,
 
     /// Ideal target output. May be a literal value or narrative text to be used by a model grader.
+    #[serde(default)]
     pub target: Target,
 
     /// Unique identifier for sample.
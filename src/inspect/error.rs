@@ -1,12 +1,14 @@
 use pyo3::FromPyObject;
+use serde::Deserialize;
 
 /// Eval error details.
-#[derive(FromPyObject, Debug)]
+#[derive(FromPyObject, Deserialize, Debug)]
 pub struct EvalError {
     // Error message.
     pub message: String,
 
     /// Error traceback.
+    #[serde(default)]
     pub traceback: String,
     //
     // """Error traceback with ANSI color codes."""
@@ -3,6 +3,7 @@
 use std::{collections::HashMap, fmt::Display};
 
 use pyo3::{FromPyObject, PyAny, PyErr, exceptions::PyTypeError, types::PyAnyMethods};
+use serde::{Deserialize, Deserializer, de};
 
 use crate::{
     inspect::{
@@ -127,6 +128,48 @@ impl<'a, 'py> FromPyObject<'a, 'py> for Event {
     }
 }
 
+/// Deserializes an event using its 'event' field.
+impl<'de> Deserialize<'de> for Event {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        fn from_value<'de, T, D>(value: serde_json::Value) -> Result<T, D::Error>
+        where
+            T: serde::de::DeserializeOwned,
+            D: Deserializer<'de>,
+        {
+            serde_json::from_value(value).map_err(de::Error::custom)
+        }
+
+        let value = serde_json::Value::deserialize(deserializer)?;
+        let event = value
+            .get("event")
+            .and_then(|event| event.as_str())
+            .ok_or_else(|| de::Error::missing_field("event"))?
+            .to_string();
+        Ok(match event.as_str() {
+            "sample_init" => Self::SampleInit(from_value::<_, D>(value)?),
+            "sample_limit" => Self::SampleLimit(from_value::<_, D>(value)?),
+            "sandbox" => Self::Sandbox(from_value::<_, D>(value)?),
+            "state" => Self::State(from_value::<_, D>(value)?),
+            "store" => Self::Store(from_value::<_, D>(value)?),
+            "model" => Self::Model(from_value::<_, D>(value)?),
+            "tool" => Self::Tool(from_value::<_, D>(value)?),
+            "approval" => Self::Approval(from_value::<_, D>(value)?),
+            "input" => Self::Input(from_value::<_, D>(value)?),
+            "score" => Self::Score(from_value::<_, D>(value)?),
+            "error" => Self::Error(from_value::<_, D>(value)?),
+            "logger" => Self::Logger(from_value::<_, D>(value)?),
+            "info" => Self::Info(from_value::<_, D>(value)?),
+            "span_begin" => Self::SpanBegin(from_value::<_, D>(value)?),
+            "span_end" => Self::SpanEnd(from_value::<_, D>(value)?),
+            "step" => Self::Step(from_value::<_, D>(value)?),
+            "subtask" => Self::Subtask(from_value::<_, D>(value)?),
+            other => {
+                return Err(de::Error::custom(format!("unknown event type '{other}'")));
+            }
+        })
+    }
+}
+
 impl Event {
     pub fn base(&self) -> &BaseEvent {
         match self {
@@ -151,9 +194,10 @@ impl Event {
     }
 }
 
-#[derive(FromPyObject, Debug)]
+#[derive(FromPyObject, Deserialize, Debug)]
 pub struct BaseEvent {
     #[pyo3(attribute("event"))]
+    #[serde(rename = "event")]
     pub event_name: String,
 
     /// Unique identifer for event.
@@ -166,6 +210,7 @@ pub struct BaseEvent {
     pub timestamp: EpochMillis,
 
     /// Working time (within sample) at which the event occurred.
+    #[serde(default)]
     pub working_start: f64,
 
     /// Additional event metadata.
@@ -175,14 +220,16 @@ pub struct BaseEvent {
     pub pending: Option<bool>,
 }
 
-#[derive(Debug)]
+#[derive(Deserialize, Debug)]
 pub struct SampleInitEvent {
+    #[serde(flatten)]
     pub base: BaseEvent,
 
     /// Sample.
     pub sample: Sample,
 
     /// Initial state.
+    #[serde(default)]
     pub state: JsonValue,
 }
 
@@ -197,8 +244,9 @@ impl<'a, 'py> FromPyObject<'a, 'py> for SampleInitEvent {
     }
 }
 
-#[derive(Debug)]
+#[derive(Deserialize, Debug)]
 pub struct SampleLimitEvent {
+    #[serde(flatten)]
     pub base: BaseEvent,
 }
 
@@ -212,8 +260,9 @@ impl<'a, 'py> FromPyObject<'a, 'py> for SampleLimitEvent {
 }
 
 /// Sandbox execution or I/O
-#[derive(Debug)]
+#[derive(Deserialize, Debug)]
 pub struct SandboxEvent {
+    #[serde(flatten)]
     pub base: BaseEvent,
 
     /// Sandbox action
@@ -240,7 +289,8 @@ pub struct SandboxEvent {
     // completed: datetime | None = Field(default=None)
 }
 
-#[derive(Debug)]
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "snake_case")]
 pub enum SandboxAction {
     Exec,
     ReadFile,
@@ -282,11 +332,13 @@ impl<'a, 'py> FromPyObject<'a, 'py> for SandboxEvent {
 }
 
 /// Change to the current `TaskState`
-#[derive(Debug)]
+#[derive(Deserialize, Debug)]
 pub struct StateEvent {
+    #[serde(flatten)]
     pub base: BaseEvent,
 
     /// List of changes to the `TaskState`
+    #[serde(default)]
     pub changes: Vec<JsonChange>,
 }
 
@@ -301,11 +353,13 @@ impl<'a, 'py> FromPyObject<'a, 'py> for StateEvent {
 }
 
 /// Change to data within the current `Store`.
-#[derive(Debug)]
+#[derive(Deserialize, Debug)]
 pub struct StoreEvent {
+    #[serde(flatten)]
     pub base: BaseEvent,
 
     /// List of changes to the `Store`.
+    #[serde(default)]
     pub changes: Vec<JsonChange>,
 }
 
@@ -320,8 +374,9 @@ impl<'a, 'py> FromPyObject<'a, 'py> for StoreEvent {
 }
 
 /// Call to a language model.
-#[derive(Debug)]
+#[derive(Deserialize, Debug)]
 pub struct ModelEvent {
+    #[serde(flatten)]
     pub base: BaseEvent,
 
     /// Model name.
@@ -331,12 +386,15 @@ pub struct ModelEvent {
     pub role: Option<String>,
 
     /// Model input (list of messages).
+    #[serde(default)]
     pub input: Vec<ChatMessage>,
 
     /// Tools available to the model.
+    #[serde(default)]
     pub tools: Vec<ToolInfo>,
 
     /// Directive to the model which tools to prefer.
+    #[serde(default)]
     pub tool_choice: ToolChoice,
 
     // config: GenerateConfig
@@ -364,17 +422,25 @@ pub struct ModelEvent {
     pub working_time: Option<f64>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub enum ToolChoice {
+    #[default]
     Auto,
     Any,
     None,
+
+    /// Use a specific tool function.
+    Function(String),
 }
 
 impl<'a, 'py> FromPyObject<'a, 'py> for ToolChoice {
     type Error = PyErr;
     fn extract(ob: pyo3::Borrowed<'a, 'py, pyo3::PyAny>) -> pyo3::PyResult<Self> {
-        match ob.extract::<String>()?.as_str() {
+        let Ok(choice) = ob.extract::<String>() else {
+            // ToolFunction
+            return Ok(Self::Function(ob.getattr("name")?.extract()?));
+        };
+        match choice.as_str() {
             "auto" => Ok(Self::Auto),
             "any" => Ok(Self::Any),
             "none" => Ok(Self::None),
@@ -392,11 +458,33 @@ impl Display for ToolChoice {
             Self::Auto => "auto".fmt(f),
             Self::Any => "any".fmt(f),
             Self::None => "none".fmt(f),
+            Self::Function(name) => name.fmt(f),
         }
     }
 }
 
-#[derive(Debug)]
+impl<'de> Deserialize<'de> for ToolChoice {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Choice {
+            Str(String),
+            Function { name: String },
+        }
+        match Choice::deserialize(deserializer)? {
+            Choice::Str(s) => match s.as_str() {
+                "auto" => Ok(Self::Auto),
+                "any" => Ok(Self::Any),
+                "none" => Ok(Self::None),
+                other => Err(de::Error::custom(format!("unknown tool choice '{other}'"))),
+            },
+            Choice::Function { name } => Ok(Self::Function(name)),
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "lowercase")]
 pub enum CacheType {
     Read,
     Write,
@@ -426,12 +514,14 @@ impl Display for CacheType {
 }
 
 /// Model call (raw request/response data).
-#[derive(FromPyObject, Debug)]
+#[derive(FromPyObject, Deserialize, Debug)]
 pub struct ModelCall {
     /// Raw data posted to model.
+    #[serde(default)]
     pub request: HashMap<String, JsonValue>,
 
-    /// Raw response data from model.
+    /// Raw response data from model (not read from log files).
+    #[serde(skip)]
     pub response: HashMap<String, pyo3::Py<PyAny>>,
 
     /// Time taken for underlying model call.
@@ -461,11 +551,13 @@ impl<'a, 'py> FromPyObject<'a, 'py> for ModelEvent {
     }
 }
 
-#[derive(Debug)]
+#[derive(Deserialize, Debug)]
 pub struct ToolEvent {
+    #[serde(flatten)]
     pub base: BaseEvent,
 
     /// Type of tool call (currently only 'function')
+    #[serde(rename = "type", default)]
     pub call_type: String,
 
     /// Unique identifier for tool call.
@@ -475,12 +567,14 @@ pub struct ToolEvent {
     pub function: String,
 
     /// Arguments to function.
+    #[serde(default)]
     pub arguments: HashMap<String, JsonValue>,
 
     /// Custom view of tool call input.
     pub view: Option<ToolCallContent>,
 
     /// Function return value.
+    #[serde(default)]
     pub result: ToolResult,
 
     /// Bytes truncated (from,to) if truncation occurred
@@ -528,7 +622,7 @@ impl<'a, 'py> FromPyObject<'a, 'py> for ToolEvent {
 }
 
 /// Content to include in tool call view.
-#[derive(FromPyObject, Debug)]
+#[derive(FromPyObject, Deserialize, Debug)]
 pub struct ToolCallContent {
     /// Optional (plain text) title for tool call content.
     pub title: Option<String>,
@@ -540,7 +634,8 @@ pub struct ToolCallContent {
     pub content: String,
 }
 
-#[derive(FromPyObject, Debug)]
+#[derive(FromPyObject, Deserialize, Debug)]
+#[serde(untagged)]
 pub enum ToolResult {
     String(String),
     Int(i64),
@@ -552,7 +647,14 @@ pub enum ToolResult {
     List(Vec<ToolResultItem>),
 }
 
-#[derive(FromPyObject, Debug)]
+impl Default for ToolResult {
+    fn default() -> Self {
+        Self::String(String::new())
+    }
+}
+
+#[derive(FromPyObject, Deserialize, Debug)]
+#[serde(untagged)]
 pub enum ToolResultItem {
     Text(ContentText),
     Image(ContentImage),
@@ -561,18 +663,20 @@ pub enum ToolResultItem {
 }
 
 /// Error raised by a tool call.
-#[derive(FromPyObject, Debug)]
+#[derive(FromPyObject, Deserialize, Debug)]
 pub struct ToolCallError {
     /// Error type.
     #[pyo3(attribute("type"))]
+    #[serde(rename = "type")]
     pub error_type: String,
 
     /// Error message.
     pub message: String,
 }
 
-#[derive(Debug)]
+#[derive(Deserialize, Debug)]
 pub struct ApprovalEvent {
+    #[serde(flatten)]
     pub base: BaseEvent,
 }
 
@@ -585,8 +689,9 @@ impl<'a, 'py> FromPyObject<'a, 'py> for ApprovalEvent {
     }
 }
 
-#[derive(Debug)]
+#[derive(Deserialize, Debug)]
 pub struct InputEvent {
+    #[serde(flatten)]
     pub base: BaseEvent,
 }
 
@@ -599,8 +704,9 @@ impl<'a, 'py> FromPyObject<'a, 'py> for InputEvent {
     }
 }
 
-#[derive(Debug)]
+#[derive(Deserialize, Debug)]
 pub struct ScoreEvent {
+    #[serde(flatten)]
     pub base: BaseEvent,
 
     /// Score value.
@@ -610,6 +716,7 @@ pub struct ScoreEvent {
     pub target: Option<Target>,
 
     /// Was this an intermediate scoring?
+    #[serde(default)]
     pub intermediate: bool,
 }
 
@@ -625,8 +732,9 @@ impl<'a, 'py> FromPyObject<'a, 'py> for ScoreEvent {
     }
 }
 
-#[derive(Debug)]
+#[derive(Deserialize, Debug)]
 pub struct ErrorEvent {
+    #[serde(flatten)]
     pub base: BaseEvent,
 
     /// Sample error
@@ -643,8 +751,9 @@ impl<'a, 'py> FromPyObject<'a, 'py> for ErrorEvent {
     }
 }
 
-#[derive(Debug)]
+#[derive(Deserialize, Debug)]
 pub struct LoggerEvent {
+    #[serde(flatten)]
     pub base: BaseEvent,
 }
 
@@ -657,8 +766,9 @@ impl<'a, 'py> FromPyObject<'a, 'py> for LoggerEvent {
     }
 }
 
-#[derive(Debug)]
+#[derive(Deserialize, Debug)]
 pub struct InfoEvent {
+    #[serde(flatten)]
     pub base: BaseEvent,
 }
 
@@ -672,8 +782,9 @@ impl<'a, 'py> FromPyObject<'a, 'py> for InfoEvent {
 }
 
 /// Mark the beginning of a transcript span.
-#[derive(Debug)]
+#[derive(Deserialize, Debug)]
 pub struct SpanBeginEvent {
+    #[serde(flatten)]
     pub base: BaseEvent,
 
     /// Unique identifier for span.
@@ -683,6 +794,7 @@ pub struct SpanBeginEvent {
     pub parent_id: Option<String>,
 
     /// Optional 'type' field for span.
+    #[serde(rename = "type")]
     pub span_type: Option<String>,
 
     /// Span name.
@@ -702,8 +814,9 @@ impl<'a, 'py> FromPyObject<'a, 'py> for SpanBeginEvent {
     }
 }
 
-#[derive(Debug)]
+#[derive(Deserialize, Debug)]
 pub struct SpanEndEvent {
+    #[serde(flatten)]
     pub base: BaseEvent,
 }
 
@@ -716,8 +829,9 @@ impl<'a, 'py> FromPyObject<'a, 'py> for SpanEndEvent {
     }
 }
 
-#[derive(Debug)]
+#[derive(Deserialize, Debug)]
 pub struct StepEvent {
+    #[serde(flatten)]
     pub base: BaseEvent,
 }
 
@@ -730,8 +844,9 @@ impl<'a, 'py> FromPyObject<'a, 'py> for StepEvent {
     }
 }
 
-#[derive(Debug)]
+#[derive(Deserialize, Debug)]
 pub struct SubtaskEvent {
+    #[serde(flatten)]
     pub base: BaseEvent,
 }
 
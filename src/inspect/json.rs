@@ -6,10 +6,12 @@ use pyo3::{
     Borrowed, FromPyObject, PyAny, PyErr, PyResult, exceptions::PyTypeError, types::PyAnyMethods,
 };
 
+use serde::Deserialize;
+
 use crate::py::Any;
 
 /// Describes a change to data using JSON Patch format.
-#[derive(FromPyObject, Debug)]
+#[derive(FromPyObject, Deserialize, Debug)]
 pub struct JsonChange {
     // """Change operation."""
     pub op: JsonChangeOp,
@@ -19,18 +21,22 @@ pub struct JsonChange {
 
     /// Location from which data was moved or copied.
     #[pyo3(attribute("from_"))]
+    #[serde(rename = "from", alias = "from_")]
     pub from_location: Option<String>,
 
     // Changed value.
+    #[serde(default)]
     pub value: JsonValue,
 
     /// Replaced value.
+    #[serde(default)]
     pub replaced: JsonValue,
     //
     // model_config = {"populate_by_name": True}
 }
 
-#[derive(Debug)]
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "lowercase")]
 pub enum JsonChangeOp {
     Remove,
     Add,
@@ -71,8 +77,10 @@ impl Display for JsonChangeOp {
     }
 }
 
-#[derive(Debug)]
+#[derive(Deserialize, Debug, Default)]
+#[serde(untagged)]
 pub enum JsonValue {
+    #[default]
     None,
     String(String),
     Int(i64),
@@ -133,10 +141,11 @@ impl Display for JsonValue {
 }
 
 /// JSON Schema for type.
-#[derive(FromPyObject, Debug)]
+#[derive(FromPyObject, Deserialize, Debug)]
 pub struct JSONSchema {
     /// JSON type of tool parameter.
     #[pyo3(attribute("type"))]
+    #[serde(rename = "type")]
     pub json_type: Option<String>,
 
     /// Format of the parameter (e.g. date-time).
@@ -146,10 +155,12 @@ pub struct JSONSchema {
     pub description: Option<String>,
 
     /// Default value for parameter.
+    #[serde(default)]
     pub default: Any,
 
     /// Valid values for enum parameters.
     #[pyo3(attribute("enum"))]
+    #[serde(rename = "enum")]
     pub enum_vals: Option<Vec<Any>>,
     //
     // """Valid type for array parameters."""
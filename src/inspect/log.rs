@@ -12,6 +12,7 @@ use pyo3::{
     Borrowed, Bound, FromPyObject, PyAny, PyErr, PyResult, Python, exceptions::PyValueError,
     types::PyAnyMethods,
 };
+use serde::{Deserialize, Deserializer, Serialize, de};
 
 use crate::{
     env,
@@ -19,7 +20,9 @@ use crate::{
         Args, Attributes, Metadata,
         error::EvalError,
         event::{Event, SpanBeginEvent, StateEvent},
+        log_file,
         model::{ChatMessage, ModelOutput, ModelUsage},
+        s3,
         scorer::Score,
     },
     py::{Any, EpochMillis, py_call},
@@ -38,19 +41,36 @@ pub struct EvalLogInfo {
     /// Log Id, derived from log name
     #[pyo3(attribute("name"), from_py_with = EvalLogInfo::log_id_from_py_name)]
     pub log_id: String,
+
+    /// Object size for S3 logs
+    #[pyo3(default)]
+    pub size: Option<u64>,
+
+    /// Object ETag for S3 logs
+    #[pyo3(default)]
+    pub etag: Option<String>,
 }
 
 impl EvalLogInfo {
     fn log_id_from_py_name<'py>(py_name: &Bound<'py, PyAny>) -> PyResult<String> {
         let name = py_name.extract::<String>()?;
-        name.rsplit_once("_")
-            .and_then(|(_, suffix)| suffix.split_once("."))
-            .map(|(id, _)| id.into())
+        Self::log_id_for_name(&name)
+            .map(String::from)
             .ok_or(PyErr::new::<PyValueError, _>(format!(
                 "cannot get log ID from name '{name}'"
             )))
     }
 
+    /// Returns the log Id for a log name.
+    ///
+    /// The Id is the last underscore-separated part of the file name,
+    /// without extensions.
+    pub fn log_id_for_name(name: &str) -> Option<&str> {
+        name.rsplit_once("_")
+            .and_then(|(_, suffix)| suffix.split_once("."))
+            .map(|(id, _)| id)
+    }
+
     pub fn short_log_id(&self) -> &str {
         self.log_id
             .split_at_checked(6)
@@ -58,7 +78,7 @@ impl EvalLogInfo {
             .unwrap_or(&self.log_id)
     }
 }
-#[derive(FromPyObject, Debug)]
+#[derive(FromPyObject, Deserialize, Debug)]
 pub struct EvalLog {
     /// Eval log file format version.
     pub version: u16,
@@ -76,6 +96,7 @@ pub struct EvalLog {
     pub results: Option<EvalResults>,
 
     /// Eval stats (runtime, model usage)
+    #[serde(default)]
     pub stats: EvalStats,
 
     /// Error that halted eval (if status=="error")
@@ -88,7 +109,15 @@ pub struct EvalLog {
     pub reductions: Option<Vec<EvalSampleReductions>>,
 
     /// Location that the log file was read from.
+    #[serde(default)]
     pub location: String,
+
+    /// Number of samples completed for a running eval.
+    ///
+    /// Only available for `.eval` logs read natively.
+    #[serde(skip)]
+    #[pyo3(default)]
+    pub completed_samples: Option<usize>,
     //
     // /// ETag from S3 for conditional writes.
     // pub etag: Option<String>,
@@ -104,7 +133,8 @@ impl EvalLog {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Eq, Ord)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, PartialOrd, Eq, Ord)]
+#[serde(rename_all = "lowercase")]
 pub enum EvalStatus {
     Started,
     Success,
@@ -138,7 +168,7 @@ impl Display for EvalStatus {
     }
 }
 
-#[derive(FromPyObject, Debug)]
+#[derive(FromPyObject, Deserialize, Debug)]
 pub struct EvalSpec {
     /// Globally unique id for eval set (if any).
     pub eval_set_id: Option<String>,
@@ -159,6 +189,7 @@ pub struct EvalSpec {
     pub task_id: String,
 
     /// Task version.
+    #[serde(default)]
     pub task_version: TaskVersion,
 
     /// Task source file.
@@ -171,12 +202,15 @@ pub struct EvalSpec {
     pub task_registry_name: Option<String>,
 
     /// Attributes of the @task decorator.
+    #[serde(default)]
     pub task_attribs: Attributes,
 
     /// Arguments used for invoking the task (including defaults).
+    #[serde(default)]
     pub task_args: Args,
 
     /// Arguments explicitly passed by caller for invoking the task.
+    #[serde(default)]
     pub task_args_passed: Args,
 
     /// Solver name.
@@ -204,18 +238,21 @@ pub struct EvalSpec {
     pub model_base_url: Option<String>,
 
     /// Model specific arguments.
+    #[serde(default)]
     pub model_args: Args,
 
     // model_roles: dict[str, ModelConfig] | None = Field(default=None)
     // """Model roles."""
 
-    // config: EvalConfig
-    // """Configuration values for eval."""
-    //
+    /// Configuration values for eval.
+    #[serde(default)]
+    pub config: EvalConfig,
+
     /// Source revision of eval.
     pub revision: Option<EvalRevision>,
 
     /// Package versions for eval.
+    #[serde(default)]
     pub packages: HashMap<String, String>,
 
     /// Additional eval metadata.
@@ -232,6 +269,12 @@ pub struct EvalSpec {
 }
 
 impl EvalSpec {
+    /// Returns the total number of samples for the eval (evaluated
+    /// dataset samples * epochs).
+    pub fn total_samples(&self) -> Option<usize> {
+        Some(self.dataset.evaluated_count()? * self.config.epochs.unwrap_or(1))
+    }
+
     pub fn task_description(&self) -> Option<String> {
         self.task_attribs
             .get("description")
@@ -249,12 +292,19 @@ impl EvalSpec {
     }
 }
 
-#[derive(FromPyObject, Debug)]
+#[derive(FromPyObject, Deserialize, Debug)]
+#[serde(untagged)]
 pub enum TaskVersion {
     Str(String),
     Int(u32),
 }
 
+impl Default for TaskVersion {
+    fn default() -> Self {
+        Self::Int(0)
+    }
+}
+
 impl Display for TaskVersion {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -264,7 +314,13 @@ impl Display for TaskVersion {
     }
 }
 
-#[derive(FromPyObject, Debug)]
+#[derive(FromPyObject, Deserialize, Default, Debug)]
+pub struct EvalConfig {
+    /// Number of epochs to run samples over.
+    pub epochs: Option<usize>,
+}
+
+#[derive(FromPyObject, Deserialize, Debug)]
 pub struct EvalRevision {
     // Type of revision (currently only "git")
     // type: String,
@@ -281,7 +337,7 @@ impl Display for EvalRevision {
     }
 }
 
-#[derive(FromPyObject, Debug)]
+#[derive(FromPyObject, Deserialize, Debug, Default)]
 pub struct EvalDataset {
     /// Dataset name.
     pub name: Option<String>,
@@ -305,7 +361,7 @@ impl EvalDataset {
     }
 }
 
-#[derive(FromPyObject, Debug)]
+#[derive(FromPyObject, Deserialize, Debug)]
 pub struct EvalScorer {
     /// Scorer name
     pub name: String,
@@ -320,19 +376,21 @@ pub struct EvalScorer {
     pub metadata: Option<Metadata>,
 }
 
-#[derive(FromPyObject, Debug)]
+#[derive(FromPyObject, Deserialize, Debug)]
+#[serde(untagged)]
 pub enum Metrics {
     List(Vec<MetricsOneOrMap>),
     Map(HashMap<String, Vec<EvalMetricDefinition>>),
 }
 
-#[derive(FromPyObject, Debug)]
+#[derive(FromPyObject, Deserialize, Debug)]
+#[serde(untagged)]
 pub enum MetricsOneOrMap {
     One(EvalMetricDefinition),
     Map(HashMap<String, Vec<EvalMetricDefinition>>),
 }
 
-#[derive(FromPyObject, Debug)]
+#[derive(FromPyObject, Deserialize, Debug)]
 pub struct EvalMetricDefinition {
     /// Metric name
     pub name: String,
@@ -475,12 +533,13 @@ impl<'a> Iterator for MetricsIter<'a> {
     }
 }
 
-#[derive(FromPyObject, Debug)]
+#[derive(FromPyObject, Deserialize, Debug)]
 pub struct EvalPlan {
     /// Plan name.
     pub name: String,
 
     /// Steps in plan.
+    #[serde(default)]
     pub steps: Vec<EvalPlanStep>,
 
     /// Step to always run at the end.
@@ -490,16 +549,17 @@ pub struct EvalPlan {
     // """Generation config."""
 }
 
-#[derive(FromPyObject, Debug)]
+#[derive(FromPyObject, Deserialize, Debug)]
 pub struct EvalPlanStep {
     /// Name of solver.
     pub solver: String,
 
     /// Parameters used to instantiate solver.
+    #[serde(default)]
     pub params: HashMap<String, Any>,
 }
 
-#[derive(FromPyObject, Debug)]
+#[derive(FromPyObject, Deserialize, Debug)]
 pub struct EvalResults {
     /// Total samples in eval (dataset samples * epochs)
     pub total_samples: usize,
@@ -516,21 +576,15 @@ pub struct EvalResults {
 
 impl EvalResults {
     pub fn first_accuracy(&self) -> Option<f64> {
-        Some(match self.scores.first()?.metrics.get("accuracy")?.value {
-            MetricVal::Int(i) => i as f64,
-            MetricVal::Float(f) => f,
-        })
+        Some(self.scores.first()?.metrics.get("accuracy")?.value.as_f64())
     }
 
     pub fn first_stderr(&self) -> Option<f64> {
-        Some(match self.scores.first()?.metrics.get("stderr")?.value {
-            MetricVal::Int(i) => i as f64,
-            MetricVal::Float(f) => f,
-        })
+        Some(self.scores.first()?.metrics.get("stderr")?.value.as_f64())
     }
 }
 
-#[derive(FromPyObject, Debug)]
+#[derive(FromPyObject, Deserialize, Debug)]
 pub struct EvalScore {
     /// Score name.
     pub name: String,
@@ -548,16 +602,18 @@ pub struct EvalScore {
     pub unscored_samples: Option<usize>,
 
     /// Parameters specified when creating scorer.
+    #[serde(default)]
     pub params: HashMap<String, Any>,
 
     /// Metrics computed for this scorer.
+    #[serde(default)]
     pub metrics: HashMap<String, EvalMetric>,
 
     /// Additional scorer metadata.
     pub metadata: Option<Metadata>,
 }
 
-#[derive(FromPyObject, Debug)]
+#[derive(FromPyObject, Deserialize, Debug)]
 pub struct EvalMetric {
     /// Metric name.
     pub name: String,
@@ -566,6 +622,7 @@ pub struct EvalMetric {
     pub value: MetricVal,
 
     /// Params specified when creating metric.
+    #[serde(default)]
     pub params: HashMap<String, Any>,
 
     /// Additional metadata associated with metric.
@@ -578,6 +635,31 @@ pub enum MetricVal {
     Float(f64),
 }
 
+/// Null metric values (used for NaN in JSON logs) are read as NaN.
+impl<'de> Deserialize<'de> for MetricVal {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        match Option::<serde_json::Number>::deserialize(deserializer)? {
+            Some(n) => match n.as_i64() {
+                Some(i) => Ok(Self::Int(i)),
+                None => n
+                    .as_f64()
+                    .map(Self::Float)
+                    .ok_or_else(|| de::Error::custom(format!("invalid metric value {n}"))),
+            },
+            None => Ok(Self::Float(f64::NAN)),
+        }
+    }
+}
+
+impl MetricVal {
+    pub fn as_f64(&self) -> f64 {
+        match self {
+            Self::Int(i) => *i as f64,
+            Self::Float(f) => *f,
+        }
+    }
+}
+
 impl Display for MetricVal {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -587,22 +669,27 @@ impl Display for MetricVal {
     }
 }
 
-#[derive(FromPyObject, Debug)]
+#[derive(FromPyObject, Deserialize, Debug, Default)]
 pub struct EvalStats {
-    /// Evaluation start time.
-    pub started_at: EpochMillis,
+    /// Evaluation start time (empty if not started).
+    #[pyo3(from_py_with = EpochMillis::extract_opt)]
+    #[serde(default, deserialize_with = "EpochMillis::deserialize_opt")]
+    pub started_at: Option<EpochMillis>,
 
-    /// Evaluation completion time.
-    pub completed_at: EpochMillis,
+    /// Evaluation completion time (empty if not completed).
+    #[pyo3(from_py_with = EpochMillis::extract_opt)]
+    #[serde(default, deserialize_with = "EpochMillis::deserialize_opt")]
+    pub completed_at: Option<EpochMillis>,
 
     /// Model token usage for evaluation.
+    #[serde(default)]
     pub model_usage: HashMap<String, ModelUsage>,
     //
     // # allow field model_usage
     // model_config = ConfigDict(protected_namespaces=())
 }
 
-#[derive(FromPyObject, Debug)]
+#[derive(FromPyObject, Deserialize, Debug)]
 pub struct EvalSample {
     /// Unique id for sample.
     pub id: SampleId,
@@ -614,6 +701,7 @@ pub struct EvalSample {
     pub input: SampleInput,
 
     /// Sample target value(s)
+    #[serde(default)]
     pub target: Target,
 
     // sandbox: SandboxEnvironmentSpec | None = Field(default=None)
@@ -626,6 +714,7 @@ pub struct EvalSample {
     pub setup: Option<String>,
 
     /// Chat conversation history for sample.
+    #[serde(default)]
     pub messages: Vec<ChatMessage>,
 
     /// Model output from sample.
@@ -635,16 +724,19 @@ pub struct EvalSample {
     pub scores: Option<HashMap<String, Score>>,
 
     /// Additional sample metadata.
+    #[serde(default)]
     pub metadata: Metadata,
 
     // store: dict[str, Any] = Field(default_factory=dict)
     // """State at end of sample execution."""
     //
     /// Events that occurred during sample execution.
+    #[serde(default)]
     pub events: Vec<Event>,
-    //
-    // model_usage: dict[str, ModelUsage] = Field(default_factory=dict)
-    // """Model token usage for sample."""
+
+    /// Model token usage for sample.
+    #[serde(default)]
+    pub model_usage: HashMap<String, ModelUsage>,
 
     // total_time: float | None = Field(default=None)
     // """Total time that the sample was running."""
@@ -655,13 +747,14 @@ pub struct EvalSample {
     // uuid: str | None = Field(default=None)
     // """Globally unique identifier for sample run (exists for samples created in Inspect >= 0.3.70)"""
 
-    // error: EvalError | None = Field(default=None)
-    // """Error that halted sample."""
+    /// Error that halted sample.
+    pub error: Option<EvalError>,
 
     // error_retries: list[EvalError] | None = Field(default=None)
     // """Errors that were retried for this sample."""
     //
     /// Attachments referenced from messages and events.
+    #[serde(default)]
     pub attachments: Attachments,
     //
     // limit: EvalSampleLimit | None = Field(default=None)
@@ -691,6 +784,18 @@ impl EvalSample {
             .collect_vec()
     }
 
+    /// Returns the sample input as text.
+    ///
+    /// Input stored as an attachment is resolved from the sample
+    /// attachments.
+    pub fn input_text(&self) -> String {
+        let input = self.input.text();
+        match input.strip_prefix("attachment://") {
+            Some(key) => self.attachments.get(key).cloned().unwrap_or(input),
+            None => input,
+        }
+    }
+
     /// Returns an option of bool, where bool is true if at least one
     /// score value is "C" and there are no "I" scores. Returns None if
     /// there are no scores.
@@ -784,7 +889,8 @@ impl<'a> Iterator for StepsIterator<'a> {
     }
 }
 
-#[derive(FromPyObject, Debug)]
+#[derive(FromPyObject, Deserialize, Debug)]
+#[serde(untagged)]
 pub enum SampleId {
     Int(i32),
     Str(String),
@@ -799,18 +905,52 @@ impl Display for SampleId {
     }
 }
 
-#[derive(FromPyObject, Debug)]
+impl From<&SampleId> for serde_json::Value {
+    fn from(value: &SampleId) -> Self {
+        match value {
+            SampleId::Int(i) => (*i).into(),
+            SampleId::Str(s) => s.as_str().into(),
+        }
+    }
+}
+
+#[derive(FromPyObject, Deserialize, Debug)]
+#[serde(untagged)]
 pub enum SampleInput {
     String(String),
     ChatMessageList(Vec<ChatMessage>),
 }
 
-#[derive(FromPyObject, Debug)]
+impl SampleInput {
+    /// Returns input as text.
+    ///
+    /// Chat message input is converted to the text content of its
+    /// messages, separated by blank lines.
+    pub fn text(&self) -> String {
+        match self {
+            Self::String(s) => s.clone(),
+            Self::ChatMessageList(l) => l
+                .iter()
+                .map(|msg| msg.base().content.text())
+                .filter(|s| !s.is_empty())
+                .join("\n\n"),
+        }
+    }
+}
+
+#[derive(FromPyObject, Deserialize, Debug)]
+#[serde(untagged)]
 pub enum Target {
     String(String),
     List(Vec<String>),
 }
 
+impl Default for Target {
+    fn default() -> Self {
+        Self::String(String::new())
+    }
+}
+
 impl Target {
     pub fn is_empty(&self) -> bool {
         match self {
@@ -827,7 +967,7 @@ impl Target {
     }
 }
 
-#[derive(Debug, FromPyObject)]
+#[derive(Debug, FromPyObject, Deserialize)]
 pub struct EvalSampleReductions {
     /// Name the of scorer
     pub scorer: String,
@@ -839,7 +979,7 @@ pub struct EvalSampleReductions {
     pub samples: Vec<EvalSampleScore>,
 }
 
-#[derive(Debug, FromPyObject)]
+#[derive(Debug, FromPyObject, Deserialize)]
 pub struct EvalSampleScore {
     /// Sample ID.
     pub sample_id: Option<SampleId>,
@@ -877,21 +1017,29 @@ pub fn list_logs_filter<'py, F>(
 where
     F: Into<LogFilter>,
 {
-    if let Ok(false) = std::fs::exists(log_dir) {
+    if !log_dir.to_str().is_some_and(s3::is_s3)
+        && let Ok(false) = std::fs::exists(log_dir)
+    {
         return Ok(Vec::new());
     }
     let deleted = match filter.into() {
         LogFilter::Deleted => true,
         LogFilter::None => false,
     };
-    let mut logs: Vec<EvalLogInfo> = py_call(
-        py,
-        "gage_inspect.log",
-        "list_logs",
-        (log_dir.expect_string(), deleted),
-    )?
-    .extract()
-    .unwrap();
+    let mut logs = match log_file::list_log_files(log_dir, deleted) {
+        Ok(logs) => logs,
+        Err(e) => {
+            log::debug!("Error listing logs in {}, using Python: {e}", log_dir.display());
+            py_call(
+                py,
+                "gage_inspect.log",
+                "list_logs",
+                (log_dir.expect_string(), deleted),
+            )?
+            .extract()
+            .unwrap()
+        }
+    };
     // Sort using name, which contains a leading create timestamp,
     // showing most recently created logs first
     logs.sort_by(|lhs, rhs| lhs.name.cmp(&rhs.name).reverse());
@@ -899,9 +1047,21 @@ where
 }
 
 pub fn read_log<'py>(py: Python<'py>, path: &str) -> Result<EvalLog> {
-    Ok(py_call(py, "gage_inspect.log", "read_eval_log", (path,))?.extract()?)
+    read_log_native_or_py(py, path, false)
 }
 
 pub fn read_log_header<'py>(py: Python<'py>, path: &str) -> Result<EvalLog> {
-    Ok(py_call(py, "gage_inspect.log", "read_eval_log", (path, true))?.extract()?)
+    read_log_native_or_py(py, path, true)
+}
+
+/// Reads a log using the native reader, falling back to Python for
+/// logs that aren't supported or that the native reader can't read.
+fn read_log_native_or_py<'py>(py: Python<'py>, path: &str, header_only: bool) -> Result<EvalLog> {
+    if log_file::is_supported(path) {
+        match log_file::read_log_file(path, header_only) {
+            Ok(log) => return Ok(log),
+            Err(e) => log::debug!("Error reading {path}, using Python: {e}"),
+        }
+    }
+    Ok(py_call(py, "gage_inspect.log", "read_eval_log", (path, header_only))?.extract()?)
 }
@@ -0,0 +1,1061 @@
+//! Native reader for Inspect log files.
+//!
+//! Supports the two Inspect log formats:
+//!
+//! - `.json` - a single JSON encoded `EvalLog`
+//! - `.eval` - a zip archive containing `header.json`, one file per
+//!   sample under `samples/`, and `reductions.json`
+//!
+//! Logs for evals that are still running don't have `header.json` -
+//! the header is read from `_journal/start.json` instead.
+//!
+//! Logs may be local files or objects in S3-compatible storage (see
+//! `s3`).
+//!
+//! Eval tags may be changed in place using `edit_log_tags`. Other log
+//! fields are read-only.
+
+use std::{
+    cmp::Ordering,
+    collections::HashSet,
+    fs::{self, File},
+    io::{BufReader, Cursor, Read, Seek, Write},
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, de::DeserializeOwned};
+use serde_json::Value;
+use zip::{ZipArchive, ZipWriter, result::ZipError, write::SimpleFileOptions};
+
+use crate::{
+    error::Error,
+    inspect::{
+        error::EvalError,
+        log::{
+            EvalLog, EvalLogInfo, EvalPlan, EvalResults, EvalSample, EvalSampleReductions,
+            EvalSpec, EvalStats, EvalStatus, SampleId,
+        },
+        log_grades::GRADES_SUFFIX,
+        log_notes::NOTES_SUFFIX,
+        s3::{self, S3Client, S3Url},
+    },
+    py::EpochMillis,
+    result::Result,
+};
+
+const FILE_SCHEME: &str = "file://";
+
+/// Suffixes of sidecar files (see `read_sidecar`).
+pub const SIDECAR_SUFFIXES: [&str; 2] = [NOTES_SUFFIX, GRADES_SUFFIX];
+
+/// `.eval` entries containing the eval spec.
+const EVAL_HEADER_ENTRIES: [&str; 2] = ["header.json", "_journal/start.json"];
+
+/// Log header fields.
+///
+/// Used to read `header.json` and `_journal/start.json` from `.eval`
+/// files and to read `.json` logs without samples.
+#[derive(Deserialize)]
+struct LogHeader {
+    version: u16,
+    #[serde(default = "started_status")]
+    status: EvalStatus,
+    eval: EvalSpec,
+    plan: EvalPlan,
+    results: Option<EvalResults>,
+    #[serde(default)]
+    stats: EvalStats,
+    error: Option<EvalError>,
+}
+
+fn started_status() -> EvalStatus {
+    EvalStatus::Started
+}
+
+impl LogHeader {
+    fn into_log(
+        self,
+        location: &str,
+        samples: Option<Vec<EvalSample>>,
+        reductions: Option<Vec<EvalSampleReductions>>,
+    ) -> EvalLog {
+        EvalLog {
+            version: self.version,
+            status: self.status,
+            eval: self.eval,
+            plan: self.plan,
+            results: self.results,
+            stats: self.stats,
+            error: self.error,
+            samples,
+            reductions,
+            location: location.into(),
+            completed_samples: None,
+        }
+    }
+}
+
+/// Returns the local path for a log name, if the log is local.
+///
+/// Log names are `file://` URIs for local logs. Plain paths are
+/// treated as local.
+pub fn local_path(name: &str) -> Option<&Path> {
+    match name.strip_prefix(FILE_SCHEME) {
+        Some(path) => Some(Path::new(path)),
+        None if !name.contains("://") => Some(Path::new(name)),
+        None => None,
+    }
+}
+
+/// Returns true if a log name is for a local log file.
+pub fn is_local(name: &str) -> bool {
+    local_path(name).is_some()
+}
+
+/// Returns true if a log can be read using `read_log_file`.
+pub fn is_supported(name: &str) -> bool {
+    is_local(name) || s3::is_s3(name)
+}
+
+/// Lists log files in a directory, including subdirectories.
+///
+/// If `deleted` is true, lists deleted logs (i.e. logs with a
+/// `.deleted` extension) rather than active logs. Logs are sorted by
+/// name.
+///
+/// `log_dir` may be an S3 URI, in which case logs are listed under the
+/// URI prefix.
+pub fn list_log_files(log_dir: &Path, deleted: bool) -> Result<Vec<EvalLogInfo>> {
+    if let Some(url) = log_dir.to_str().and_then(S3Url::parse) {
+        return list_s3_log_files(&url, deleted);
+    }
+    let log_dir = std::path::absolute(log_dir)?;
+    let mut paths = Vec::new();
+    find_log_files(&log_dir, deleted, &mut paths)?;
+    let mut logs = paths
+        .into_iter()
+        .filter_map(|path| log_info(&path))
+        .collect::<Vec<_>>();
+    logs.sort_by(|lhs, rhs| lhs.name.cmp(&rhs.name));
+    Ok(logs)
+}
+
+fn find_log_files(dir: &Path, deleted: bool, paths: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let file_name = entry.file_name();
+        let file_name = file_name.to_string_lossy();
+        if file_name.starts_with('.') {
+            continue;
+        }
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            find_log_files(&entry.path(), deleted, paths)?;
+        } else if file_type.is_file() && is_log_file_name(&file_name, deleted) {
+            paths.push(entry.path());
+        }
+    }
+    Ok(())
+}
+
+fn is_log_file_name(name: &str, deleted: bool) -> bool {
+    let name = match (name.strip_suffix(".deleted"), deleted) {
+        (Some(name), true) => name,
+        (None, false) => name,
+        _ => return false,
+    };
+    // Skip log listing files written by Inspect and sidecar files
+    name != "logs.json"
+        && !SIDECAR_SUFFIXES.iter().any(|suffix| name.ends_with(suffix))
+        && (name.ends_with(".eval") || name.ends_with(".json"))
+}
+
+fn list_s3_log_files(log_dir: &S3Url, deleted: bool) -> Result<Vec<EvalLogInfo>> {
+    let prefix = match log_dir.key.trim_end_matches('/') {
+        "" => String::new(),
+        key => format!("{key}/"),
+    };
+    let mut logs = S3Client::from_env()
+        .list(&log_dir.bucket, &prefix)?
+        .into_iter()
+        .filter(|obj| {
+            // Apply the same rules used for local directories
+            let rel_path = &obj.key[prefix.len()..];
+            !rel_path.split('/').any(|part| part.starts_with('.'))
+                && rel_path
+                    .rsplit('/')
+                    .next()
+                    .is_some_and(|file_name| is_log_file_name(file_name, deleted))
+        })
+        .filter_map(|obj| {
+            let mtime = obj
+                .last_modified
+                .map(|t| EpochMillis::from_epoch_millis(t.timestamp_millis()));
+            let mut log = make_log_info(log_dir.with_key(&obj.key).to_string(), mtime)?;
+            log.size = Some(obj.size);
+            log.etag = obj.etag;
+            Some(log)
+        })
+        .collect::<Vec<_>>();
+    logs.sort_by(|lhs, rhs| lhs.name.cmp(&rhs.name));
+    Ok(logs)
+}
+
+fn log_info(path: &Path) -> Option<EvalLogInfo> {
+    let name = format!("{FILE_SCHEME}{}", path.to_str()?);
+    let mtime = fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| EpochMillis::from_epoch_millis(d.as_millis() as i64));
+    make_log_info(name, mtime)
+}
+
+fn make_log_info(name: String, mtime: Option<EpochMillis>) -> Option<EvalLogInfo> {
+    let log_id = EvalLogInfo::log_id_for_name(&name)?.to_string();
+    let task = name
+        .rsplit('/')
+        .next()?
+        .split('_')
+        .nth(1)
+        .unwrap_or_default()
+        .to_string();
+    Some(EvalLogInfo {
+        name,
+        mtime,
+        task,
+        log_id,
+        size: None,
+        etag: None,
+    })
+}
+
+/// Reads a log file.
+///
+/// `name` is a `file://` URI, an `s3://` URI, or a path. If
+/// `header_only` is true, samples are not read.
+pub fn read_log_file(name: &str, header_only: bool) -> Result<EvalLog> {
+    let is_eval = is_eval_log(name)?;
+    if let Some(url) = S3Url::parse(name) {
+        let bytes = S3Client::from_env().get(&url)?;
+        return if is_eval {
+            read_eval(Cursor::new(bytes), name, header_only)
+        } else {
+            read_json(&bytes, name, header_only)
+        };
+    }
+    let path =
+        local_path(name).ok_or_else(|| Error::general(format!("{name} is not a log file")))?;
+    if is_eval {
+        read_eval(BufReader::new(File::open(path)?), name, header_only)
+    } else {
+        read_json(&fs::read(path)?, name, header_only)
+    }
+}
+
+/// Returns true if a log name is for an `.eval` log.
+///
+/// Fails if the log isn't an `.eval` or `.json` log.
+fn is_eval_log(name: &str) -> Result<bool> {
+    // Deleted logs are read using their original extension
+    let file_name = name.rsplit('/').next().unwrap_or(name);
+    match file_name
+        .strip_suffix(".deleted")
+        .unwrap_or(file_name)
+        .rsplit_once('.')
+    {
+        Some((_, "eval")) => Ok(true),
+        Some((_, "json")) => Ok(false),
+        _ => Err(Error::general(format!(
+            "{name} is not a supported log file (expected .eval or .json)"
+        ))),
+    }
+}
+
+/// Returns the contents of a log file.
+pub fn read_log_bytes(name: &str) -> Result<Vec<u8>> {
+    if let Some(url) = S3Url::parse(name) {
+        return S3Client::from_env().get(&url);
+    }
+    let path =
+        local_path(name).ok_or_else(|| Error::general(format!("{name} is not a log file")))?;
+    Ok(fs::read(path)?)
+}
+
+/// Writes a log file to a log directory.
+///
+/// `rel_path` is the log path relative to `log_dir`. Fails if the log
+/// file already exists. Returns the log name.
+pub fn write_log_bytes(log_dir: &Path, rel_path: &str, bytes: &[u8]) -> Result<String> {
+    if let Some(url) = log_dir_s3_url(log_dir, rel_path) {
+        let client = S3Client::from_env();
+        if client.exists(&url)? {
+            return Err(Error::general(format!("{url} already exists")));
+        }
+        client.put(&url, bytes)?;
+        return Ok(url.to_string());
+    }
+    let path = std::path::absolute(log_dir)?.join(rel_path);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::File::create_new(&path)
+        .and_then(|mut f| std::io::Write::write_all(&mut f, bytes))
+        .map_err(|e| Error::general(format!("Cannot write {}: {e}", path.display())))?;
+    Ok(format!("{FILE_SCHEME}{}", path.display()))
+}
+
+/// Tags to add and remove using `edit_log_tags`.
+#[derive(Debug, Default)]
+pub struct TagEdit {
+    pub add: Vec<String>,
+    pub remove: Vec<String>,
+}
+
+impl TagEdit {
+    /// Returns tags with the edit applied.
+    ///
+    /// Added tags are appended in order unless already present.
+    pub fn apply(&self, tags: &[String]) -> Vec<String> {
+        let mut tags = tags
+            .iter()
+            .filter(|tag| !self.remove.contains(tag))
+            .cloned()
+            .collect::<Vec<_>>();
+        for tag in &self.add {
+            if !tags.contains(tag) {
+                tags.push(tag.clone());
+            }
+        }
+        tags
+    }
+}
+
+/// Result of `edit_log_tags`.
+#[derive(Debug, PartialEq)]
+pub enum TagEditResult {
+    /// Tags were changed - contains the updated tags.
+    Changed(Vec<String>),
+
+    /// Tags are unchanged.
+    Unchanged,
+
+    /// Log isn't changed because the eval is running.
+    Running,
+}
+
+/// Adds and removes eval tags for a log.
+///
+/// The log file is rewritten with the updated tags. For `.eval` logs,
+/// the eval spec is updated in `header.json` and `_journal/start.json`
+/// and other entries are copied as is.
+///
+/// Logs for running evals are not changed as Inspect is still writing
+/// them.
+pub fn edit_log_tags(name: &str, edit: &TagEdit) -> Result<TagEditResult> {
+    let bytes = read_log_bytes(name)?;
+    let eval = is_eval_log(name)?;
+    if is_running_log(&bytes, name, eval)? {
+        return Ok(TagEditResult::Running);
+    }
+    let edited = if eval {
+        edit_eval_tags(&bytes, name, edit)?
+    } else {
+        edit_json_tags(&bytes, name, edit)?
+    };
+    let Some((bytes, tags)) = edited else {
+        return Ok(TagEditResult::Unchanged);
+    };
+    replace_log_bytes(name, &bytes)?;
+    Ok(TagEditResult::Changed(tags))
+}
+
+/// Returns true if a log is for a running eval.
+///
+/// `.eval` logs for running evals don't have a `header.json` entry.
+fn is_running_log(bytes: &[u8], location: &str, eval: bool) -> Result<bool> {
+    let header: Option<Value> = if eval {
+        let mut zip = ZipArchive::new(Cursor::new(bytes)).map_err(|e| zip_error(e, location))?;
+        read_zip_json(&mut zip, "header.json", location)?
+    } else {
+        Some(parse_json(bytes, location)?)
+    };
+    Ok(header.is_none_or(|header| header.get("status").and_then(Value::as_str) == Some("started")))
+}
+
+fn edit_json_tags(
+    bytes: &[u8],
+    location: &str,
+    edit: &TagEdit,
+) -> Result<Option<(Vec<u8>, Vec<String>)>> {
+    let mut log: Value = parse_json(bytes, location)?;
+    let Some(tags) = edit_spec_tags(&mut log, location, edit)? else {
+        return Ok(None);
+    };
+    let bytes = serde_json::to_vec_pretty(&log).map_err(|e| Error::general(e.to_string()))?;
+    Ok(Some((bytes, tags)))
+}
+
+fn edit_eval_tags(
+    bytes: &[u8],
+    location: &str,
+    edit: &TagEdit,
+) -> Result<Option<(Vec<u8>, Vec<String>)>> {
+    let mut zip = ZipArchive::new(Cursor::new(bytes)).map_err(|e| zip_error(e, location))?;
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+    let mut edited = Vec::new();
+    for i in 0..zip.len() {
+        let mut file = zip.by_index(i).map_err(|e| zip_error(e, location))?;
+        if !EVAL_HEADER_ENTRIES.contains(&file.name()) {
+            writer
+                .raw_copy_file(file)
+                .map_err(|e| zip_error(e, location))?;
+            continue;
+        }
+        let name = file.name().to_string();
+        let options = SimpleFileOptions::default().compression_method(file.compression());
+        let mut entry = Vec::with_capacity(file.size() as usize);
+        file.read_to_end(&mut entry)?;
+        let entry_location = format!("{location}/{name}");
+        let mut header: Value = parse_json(&entry, &entry_location)?;
+        if let Some(tags) = edit_spec_tags(&mut header, &entry_location, edit)? {
+            edited.push((name.clone(), tags));
+            entry = serde_json::to_vec(&header).map_err(|e| Error::general(e.to_string()))?;
+        }
+        writer
+            .start_file(name, options)
+            .map_err(|e| zip_error(e, location))?;
+        writer.write_all(&entry)?;
+    }
+    // Tags from header.json take precedence over the journal
+    let Some((_, tags)) = edited
+        .into_iter()
+        .min_by_key(|(name, _)| EVAL_HEADER_ENTRIES.iter().position(|entry| entry == name))
+    else {
+        return Ok(None);
+    };
+    let bytes = writer
+        .finish()
+        .map_err(|e| zip_error(e, location))?
+        .into_inner();
+    Ok(Some((bytes, tags)))
+}
+
+/// Applies a tag edit to the eval spec of a JSON encoded log header.
+///
+/// Returns the updated tags or None if the tags are unchanged.
+fn edit_spec_tags(
+    header: &mut Value,
+    location: &str,
+    edit: &TagEdit,
+) -> Result<Option<Vec<String>>> {
+    let eval = header
+        .get_mut("eval")
+        .and_then(Value::as_object_mut)
+        .ok_or_else(|| Error::general(format!("{location}: missing eval spec")))?;
+    let cur = match eval.get("tags") {
+        Some(Value::Array(tags)) => tags
+            .iter()
+            .filter_map(|tag| tag.as_str().map(String::from))
+            .collect(),
+        _ => Vec::new(),
+    };
+    let tags = edit.apply(&cur);
+    if tags == cur {
+        return Ok(None);
+    }
+    eval.insert("tags".into(), tags.clone().into());
+    Ok(Some(tags))
+}
+
+/// Replaces the contents of a log file.
+fn replace_log_bytes(name: &str, bytes: &[u8]) -> Result<()> {
+    if let Some(url) = S3Url::parse(name) {
+        return S3Client::from_env().put(&url, bytes);
+    }
+    let path =
+        local_path(name).ok_or_else(|| Error::general(format!("{name} is not a log file")))?;
+    replace_file(path, bytes)
+}
+
+/// Replaces the contents of a local file.
+///
+/// Writes to a temp file first to avoid leaving a partially written
+/// file.
+fn replace_file(path: &Path, bytes: &[u8]) -> Result<()> {
+    let mut tmp_path = path.as_os_str().to_owned();
+    tmp_path.push(format!(".{}.tmp", std::process::id()));
+    let result = fs::write(&tmp_path, bytes).and_then(|_| fs::rename(&tmp_path, path));
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp_path);
+    }
+    Ok(result?)
+}
+
+/// Reads a log as JSON values.
+///
+/// Returns the log header and samples. Used to write new logs from
+/// existing logs without losing fields that aren't read by `EvalLog`.
+pub fn read_log_values(name: &str) -> Result<(Value, Vec<Value>)> {
+    let bytes = read_log_bytes(name)?;
+    if !is_eval_log(name)? {
+        let mut log: Value = parse_json(&bytes, name)?;
+        let samples = match log.as_object_mut().and_then(|log| log.remove("samples")) {
+            Some(Value::Array(samples)) => samples,
+            _ => Vec::new(),
+        };
+        return Ok((log, samples));
+    }
+    let mut zip = ZipArchive::new(Cursor::new(bytes)).map_err(|e| zip_error(e, name))?;
+    let header = read_eval_header(&mut zip, name)?;
+    let sample_names = zip
+        .file_names()
+        .filter(|entry| is_sample_entry(entry))
+        .map(String::from)
+        .collect::<Vec<_>>();
+    let mut samples = Vec::with_capacity(sample_names.len());
+    for entry in sample_names {
+        samples.extend(read_zip_json(&mut zip, &entry, name)?);
+    }
+    Ok((header, samples))
+}
+
+/// Writes an `.eval` log to a log directory.
+///
+/// `header` is written to `header.json` and `samples` under `samples/`.
+/// `summaries` are written to `summaries.json`, which Inspect uses to
+/// list samples without reading them. Fails if the log file already
+/// exists. Returns the log name.
+pub fn write_eval_log(
+    log_dir: &Path,
+    rel_path: &str,
+    header: &Value,
+    samples: &[Value],
+    summaries: &[Value],
+) -> Result<String> {
+    let json_error = |e: serde_json::Error| Error::general(e.to_string());
+    let mut entries = vec![(
+        "header.json".to_string(),
+        serde_json::to_vec(header).map_err(json_error)?,
+    )];
+    for sample in samples {
+        let name = format!(
+            "samples/{}_epoch_{}.json",
+            sample_entry_id(sample.get("id")),
+            sample.get("epoch").and_then(Value::as_i64).unwrap_or(1)
+        );
+        entries.push((name, serde_json::to_vec(sample).map_err(json_error)?));
+    }
+    entries.push((
+        "summaries.json".to_string(),
+        serde_json::to_vec(summaries).map_err(json_error)?,
+    ));
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    for (name, bytes) in entries {
+        writer
+            .start_file(name, options)
+            .map_err(|e| zip_error(e, rel_path))?;
+        writer.write_all(&bytes)?;
+    }
+    let bytes = writer
+        .finish()
+        .map_err(|e| zip_error(e, rel_path))?
+        .into_inner();
+    write_log_bytes(log_dir, rel_path, &bytes)
+}
+
+fn sample_entry_id(id: Option<&Value>) -> String {
+    match id {
+        Some(Value::String(s)) => s.clone(),
+        Some(id) => id.to_string(),
+        None => "0".into(),
+    }
+}
+
+/// Reads a sidecar file for a log.
+///
+/// Sidecar files store Gage data for a log, such as reviewer notes and
+/// grades, next to the log file (e.g. `LOG.eval.notes.json`) so that
+/// the data stays with the log. `suffix` is one of `SIDECAR_SUFFIXES`. Returns
+/// None if the file doesn't exist.
+pub fn read_sidecar(name: &str, suffix: &str) -> Result<Option<Vec<u8>>> {
+    let name = format!("{name}{suffix}");
+    if let Some(url) = S3Url::parse(&name) {
+        return S3Client::from_env().get_opt(&url);
+    }
+    let path =
+        local_path(&name).ok_or_else(|| Error::general(format!("{name} is not a log file")))?;
+    match fs::read(path) {
+        Ok(bytes) => Ok(Some(bytes)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Writes a sidecar file for a log, replacing any existing file.
+pub fn write_sidecar(name: &str, suffix: &str, bytes: &[u8]) -> Result<()> {
+    let name = format!("{name}{suffix}");
+    if let Some(url) = S3Url::parse(&name) {
+        return S3Client::from_env().put(&url, bytes);
+    }
+    let path =
+        local_path(&name).ok_or_else(|| Error::general(format!("{name} is not a log file")))?;
+    replace_file(path, bytes)
+}
+
+/// Removes the sidecar files for a log.
+///
+/// `name` may be the name of a deleted log, in which case the sidecar
+/// files for the original log are removed.
+pub fn remove_sidecars(name: &str) -> Result<()> {
+    let name = name.strip_suffix(".deleted").unwrap_or(name);
+    for suffix in SIDECAR_SUFFIXES {
+        let name = format!("{name}{suffix}");
+        if let Some(url) = S3Url::parse(&name) {
+            // S3 doesn't fail when deleting missing objects
+            S3Client::from_env().delete(&url)?;
+            continue;
+        }
+        let path =
+            local_path(&name).ok_or_else(|| Error::general(format!("{name} is not a log file")))?;
+        match fs::remove_file(path) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+    Ok(())
+}
+
+/// Returns the S3 URL for a path in a log directory.
+///
+/// Returns None if `log_dir` isn't an S3 URI.
+fn log_dir_s3_url(log_dir: &Path, rel_path: &str) -> Option<S3Url> {
+    let url = log_dir.to_str().and_then(S3Url::parse)?;
+    Some(url.with_key(&match url.key.trim_end_matches('/') {
+        "" => rel_path.to_string(),
+        prefix => format!("{prefix}/{rel_path}"),
+    }))
+}
+
+/// Returns a log name relative to a log directory.
+///
+/// Returns the log file name if the log isn't in `log_dir`.
+pub fn relative_name<'a>(log_dir: &Path, name: &'a str) -> &'a str {
+    let prefix = match log_dir.to_str().and_then(S3Url::parse) {
+        Some(url) => url.to_string(),
+        None => std::path::absolute(log_dir)
+            .ok()
+            .and_then(|path| path.to_str().map(|path| format!("{FILE_SCHEME}{path}")))
+            .unwrap_or_default(),
+    };
+    match name.strip_prefix(prefix.trim_end_matches('/')) {
+        Some(rel) if rel.starts_with('/') => &rel[1..],
+        _ => name.rsplit('/').next().unwrap_or(name),
+    }
+}
+
+fn read_json(bytes: &[u8], location: &str, header_only: bool) -> Result<EvalLog> {
+    if header_only {
+        let header: LogHeader = parse_json(bytes, location)?;
+        Ok(header.into_log(location, None, None))
+    } else {
+        let mut log: EvalLog = parse_json(bytes, location)?;
+        log.location = location.into();
+        Ok(log)
+    }
+}
+
+fn read_eval<R: Read + Seek>(reader: R, location: &str, header_only: bool) -> Result<EvalLog> {
+    let mut zip = ZipArchive::new(reader).map_err(|e| zip_error(e, location))?;
+    let header: LogHeader = read_eval_header(&mut zip, location)?;
+    let sample_names = zip
+        .file_names()
+        .filter(|name| is_sample_entry(name))
+        .map(String::from)
+        .collect::<Vec<_>>();
+    // Running evals write samples as they complete
+    let completed_samples = (header.status == EvalStatus::Started).then_some(sample_names.len());
+    if header_only {
+        return Ok(EvalLog {
+            completed_samples,
+            ..header.into_log(location, None, None)
+        });
+    }
+    let mut samples = Vec::with_capacity(sample_names.len());
+    for name in sample_names {
+        if let Some(sample) = read_zip_json::<_, EvalSample>(&mut zip, &name, location)? {
+            samples.push(sample);
+        }
+    }
+    samples.sort_by(cmp_samples);
+    let reductions = read_zip_json(&mut zip, "reductions.json", location)?;
+    Ok(EvalLog {
+        completed_samples,
+        ..header.into_log(location, Some(samples), reductions)
+    })
+}
+
+/// Reads a log header and the samples that aren't in `read`.
+///
+/// `read` contains the keys of samples that were already read and is
+/// updated with the keys of samples returned. This is used to follow
+/// running evals without reading each sample more than once. `.eval`
+/// samples are keyed by their entry name. `.json` samples are keyed by
+/// sample Id and epoch.
+///
+/// Local `.eval` logs are read from the file so that only the zip
+/// directory, header, and new samples are read. S3 logs are downloaded
+/// in full.
+pub fn read_new_samples(
+    name: &str,
+    read: &mut HashSet<String>,
+) -> Result<(EvalLog, Vec<EvalSample>)> {
+    if !is_eval_log(name)? {
+        let mut log = read_log_file(name, false)?;
+        let samples = log
+            .samples
+            .take()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|sample| read.insert(format!("{}_epoch_{}", sample.id, sample.epoch)))
+            .collect();
+        return Ok((log, samples));
+    }
+    match local_path(name) {
+        Some(path) => read_new_eval_samples(BufReader::new(File::open(path)?), name, read),
+        None => read_new_eval_samples(Cursor::new(read_log_bytes(name)?), name, read),
+    }
+}
+
+fn read_new_eval_samples<R: Read + Seek>(
+    reader: R,
+    name: &str,
+    read: &mut HashSet<String>,
+) -> Result<(EvalLog, Vec<EvalSample>)> {
+    let mut zip = ZipArchive::new(reader).map_err(|e| zip_error(e, name))?;
+    let header: LogHeader = read_eval_header(&mut zip, name)?;
+    let new_names = zip
+        .file_names()
+        .filter(|entry| is_sample_entry(entry) && !read.contains(*entry))
+        .map(String::from)
+        .collect::<Vec<_>>();
+    let mut samples = Vec::with_capacity(new_names.len());
+    for entry in new_names {
+        if let Some(sample) = read_zip_json::<_, EvalSample>(&mut zip, &entry, name)? {
+            samples.push(sample);
+        }
+        read.insert(entry);
+    }
+    samples.sort_by(cmp_samples);
+    let completed_samples = (header.status == EvalStatus::Started).then_some(read.len());
+    Ok((
+        EvalLog {
+            completed_samples,
+            ..header.into_log(name, None, None)
+        },
+        samples,
+    ))
+}
+
+/// Reads the header of an `.eval` log.
+///
+/// Uses the journal start entry for running evals.
+fn read_eval_header<R: Read + Seek, T: DeserializeOwned>(
+    zip: &mut ZipArchive<R>,
+    location: &str,
+) -> Result<T> {
+    match read_zip_json(zip, "header.json", location)? {
+        Some(header) => Ok(header),
+        None => read_zip_json(zip, "_journal/start.json", location)?
+            .ok_or_else(|| Error::general(format!("{location}: missing log header"))),
+    }
+}
+
+fn is_sample_entry(name: &str) -> bool {
+    name.starts_with("samples/") && name.ends_with(".json")
+}
+
+/// Reads a JSON file from a zip archive.
+///
+/// Returns None if the archive doesn't contain the file.
+fn read_zip_json<R: Read + Seek, T: DeserializeOwned>(
+    zip: &mut ZipArchive<R>,
+    name: &str,
+    location: &str,
+) -> Result<Option<T>> {
+    let mut file = match zip.by_name(name) {
+        Ok(file) => file,
+        Err(ZipError::FileNotFound) => return Ok(None),
+        Err(e) => return Err(zip_error(e, location)),
+    };
+    let mut bytes = Vec::with_capacity(file.size() as usize);
+    file.read_to_end(&mut bytes)?;
+    parse_json(&bytes, &format!("{location}/{name}")).map(Some)
+}
+
+fn parse_json<T: DeserializeOwned>(bytes: &[u8], location: &str) -> Result<T> {
+    serde_json::from_slice(bytes).map_err(|e| Error::general(format!("{location}: {e}")))
+}
+
+fn zip_error(e: ZipError, location: &str) -> Error {
+    Error::general(format!("{location}: {e}"))
+}
+
+/// Orders samples by epoch and then by Id, consistent with Inspect.
+///
+/// Numeric Ids are compared as zero-padded strings.
+fn cmp_samples(lhs: &EvalSample, rhs: &EvalSample) -> Ordering {
+    lhs.epoch
+        .cmp(&rhs.epoch)
+        .then_with(|| sample_sort_key(&lhs.id).cmp(&sample_sort_key(&rhs.id)))
+}
+
+fn sample_sort_key(id: &SampleId) -> String {
+    match id {
+        SampleId::Int(i) => format!("{i:0>20}"),
+        SampleId::Str(s) => s.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashSet,
+        io::{Cursor, Write},
+        path::Path,
+    };
+
+    use zip::{ZipWriter, write::SimpleFileOptions};
+
+    use crate::{
+        inspect::log::{EvalStatus, SampleId},
+        util::TempDir,
+    };
+
+    use super::{
+        LogHeader, TagEdit, TagEditResult, edit_log_tags, edit_spec_tags, is_log_file_name,
+        is_running_log, local_path, read_eval, read_new_samples, sample_sort_key,
+    };
+
+    #[test]
+    fn test_log_file_names() {
+        assert!(is_log_file_name(
+            "2025-01-01T10-00-00+00-00_hello_A1.eval",
+            false
+        ));
+        assert!(is_log_file_name(
+            "2025-01-01T10-00-00+00-00_hello_A1.json",
+            false
+        ));
+        assert!(!is_log_file_name(
+            "2025-01-01T10-00-00+00-00_hello_A1.eval",
+            true
+        ));
+        assert!(is_log_file_name(
+            "2025-01-01T10-00-00+00-00_hello_A1.eval.deleted",
+            true
+        ));
+        assert!(!is_log_file_name(
+            "2025-01-01T10-00-00+00-00_hello_A1.eval.deleted",
+            false
+        ));
+        assert!(!is_log_file_name("logs.json", false));
+        assert!(!is_log_file_name("README.md", false));
+
+        assert_eq!(
+            local_path("file:///logs/a.eval"),
+            Some(Path::new("/logs/a.eval"))
+        );
+        assert_eq!(local_path("logs/a.eval"), Some(Path::new("logs/a.eval")));
+        assert_eq!(local_path("s3://bucket/a.eval"), None);
+    }
+
+    #[test]
+    fn test_sample_sort_key() {
+        let mut ids = [
+            SampleId::Int(10),
+            SampleId::Str("b".into()),
+            SampleId::Int(2),
+            SampleId::Str("a".into()),
+        ]
+        .map(|id| sample_sort_key(&id));
+        ids.sort();
+        assert_eq!(
+            ids,
+            ["00000000000000000002", "00000000000000000010", "a", "b"]
+        );
+    }
+
+    #[test]
+    fn test_read_start_header() {
+        let start = r#"{
+            "version": 2,
+            "eval": {
+                "eval_id": "E1", "run_id": "R1", "created": "2025-01-01T10:00:00+00:00",
+                "task": "hello", "task_id": "T1", "dataset": {}, "model": "mockllm/model"
+            },
+            "plan": {"name": "plan", "steps": []}
+        }"#;
+        let header: LogHeader = serde_json::from_str(start).unwrap();
+        let log = header.into_log("file:///logs/a.eval", None, None);
+        assert_eq!(log.status, EvalStatus::Started);
+        assert_eq!(log.eval.task, "hello");
+        assert_eq!(log.eval.task_version.to_string(), "0");
+        assert!(log.stats.started_at.is_none());
+        assert!(log.samples.is_none());
+    }
+
+    #[test]
+    fn test_read_running_eval() {
+        let start = r#"{
+            "version": 2,
+            "eval": {
+                "eval_id": "E1", "run_id": "R1", "created": "2025-01-01T10:00:00+00:00",
+                "task": "hello", "task_id": "T1", "model": "mockllm/model",
+                "dataset": {"samples": 2, "sample_ids": [1, 2]}, "config": {"epochs": 2}
+            },
+            "plan": {"name": "plan", "steps": []}
+        }"#;
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, bytes) in [
+            ("_journal/start.json", start),
+            ("samples/1_epoch_1.json", "{}"),
+        ] {
+            zip.start_file(name, SimpleFileOptions::default()).unwrap();
+            zip.write_all(bytes.as_bytes()).unwrap();
+        }
+        let bytes = zip.finish().unwrap().into_inner();
+        let log = read_eval(Cursor::new(bytes), "file:///logs/a.eval", true).unwrap();
+        assert_eq!(log.status, EvalStatus::Started);
+        assert_eq!(log.completed_samples, Some(1));
+        assert_eq!(log.eval.total_samples(), Some(4));
+    }
+
+    #[test]
+    fn test_read_new_samples() {
+        let start = r#"{
+            "version": 2,
+            "eval": {
+                "eval_id": "E1", "run_id": "R1", "created": "2025-01-01T10:00:00+00:00",
+                "task": "hello", "task_id": "T1", "model": "mockllm/model",
+                "dataset": {"samples": 2, "sample_ids": [1, 2]}
+            },
+            "plan": {"name": "plan", "steps": []}
+        }"#;
+        let sample = |id: usize| {
+            format!(
+                r#"{{"id": {id}, "epoch": 1, "input": "hi", "output": {{}},
+                    "scores": {{"match": {{"value": "C"}}}}}}"#
+            )
+        };
+        let tmp = TempDir::create("gage-progress-test").unwrap();
+        let path = tmp.path().join("2025-01-01T10-00-00+00-00_hello_A1.eval");
+        let write_eval = |entries: &[(&str, String)]| {
+            let mut zip = ZipWriter::new(std::fs::File::create(&path).unwrap());
+            for (name, bytes) in entries {
+                zip.start_file(*name, SimpleFileOptions::default()).unwrap();
+                zip.write_all(bytes.as_bytes()).unwrap();
+            }
+            zip.finish().unwrap();
+        };
+        let name = path.to_string_lossy().to_string();
+        let mut read = HashSet::new();
+
+        write_eval(&[
+            ("_journal/start.json", start.into()),
+            ("samples/1_epoch_1.json", sample(1)),
+        ]);
+        let (log, samples) = read_new_samples(&name, &mut read).unwrap();
+        assert_eq!(log.completed_samples, Some(1));
+        assert_eq!(samples.len(), 1);
+        assert_eq!(samples[0].is_correct(), Some(true));
+
+        // Samples already read are skipped
+        write_eval(&[
+            ("_journal/start.json", start.into()),
+            ("samples/1_epoch_1.json", sample(1)),
+            ("samples/2_epoch_1.json", sample(2)),
+        ]);
+        let (log, samples) = read_new_samples(&name, &mut read).unwrap();
+        assert_eq!(log.completed_samples, Some(2));
+        assert_eq!(samples.len(), 1);
+        assert_eq!(samples[0].id.to_string(), "2");
+    }
+
+    #[test]
+    fn test_edit_tags() {
+        let edit = TagEdit {
+            add: vec!["baseline".into(), "type:eval".into()],
+            remove: vec!["wip".into()],
+        };
+        let tags = |tags: &[&str]| tags.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            edit.apply(&tags(&["type:eval", "wip"])),
+            tags(&["type:eval", "baseline"])
+        );
+        assert_eq!(edit.apply(&[]), tags(&["baseline", "type:eval"]));
+
+        let mut header = serde_json::json!({"eval": {"task": "hello", "tags": null}});
+        assert_eq!(
+            edit_spec_tags(&mut header, "a.json", &edit).unwrap(),
+            Some(tags(&["baseline", "type:eval"]))
+        );
+        assert_eq!(
+            header["eval"]["tags"],
+            serde_json::json!(["baseline", "type:eval"])
+        );
+        assert_eq!(edit_spec_tags(&mut header, "a.json", &edit).unwrap(), None);
+    }
+
+    #[test]
+    fn test_tag_running_log() {
+        let eval_bytes = |entries: &[(&str, &str)]| {
+            let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+            for (name, bytes) in entries {
+                zip.start_file(*name, SimpleFileOptions::default()).unwrap();
+                zip.write_all(bytes.as_bytes()).unwrap();
+            }
+            zip.finish().unwrap().into_inner()
+        };
+        let start = r#"{"eval": {"task": "hello"}}"#;
+        let running = eval_bytes(&[("_journal/start.json", start)]);
+        assert!(is_running_log(&running, "a.eval", true).unwrap());
+        let started = eval_bytes(&[
+            ("_journal/start.json", start),
+            (
+                "header.json",
+                r#"{"status": "started", "eval": {"task": "hello"}}"#,
+            ),
+        ]);
+        assert!(is_running_log(&started, "a.eval", true).unwrap());
+        let success = eval_bytes(&[
+            ("_journal/start.json", start),
+            (
+                "header.json",
+                r#"{"status": "success", "eval": {"task": "hello"}}"#,
+            ),
+        ]);
+        assert!(!is_running_log(&success, "a.eval", true).unwrap());
+        assert!(is_running_log(br#"{"status": "started"}"#, "a.json", false).unwrap());
+        assert!(!is_running_log(br#"{"status": "error"}"#, "a.json", false).unwrap());
+
+        // Running logs aren't changed
+        let tmp = TempDir::create("gage-tag-test").unwrap();
+        let path = tmp.path().join("2025-01-01T10-00-00+00-00_hello_T1.eval");
+        std::fs::write(&path, &running).unwrap();
+        let name = path.to_string_lossy();
+        let edit = TagEdit {
+            add: vec!["baseline".into()],
+            remove: Vec::new(),
+        };
+        assert_eq!(edit_log_tags(&name, &edit).unwrap(), TagEditResult::Running);
+        assert_eq!(std::fs::read(&path).unwrap(), running);
+        std::fs::write(&path, &success).unwrap();
+        assert_eq!(
+            edit_log_tags(&name, &edit).unwrap(),
+            TagEditResult::Changed(vec!["baseline".into()])
+        );
+    }
+}
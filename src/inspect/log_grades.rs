@@ -0,0 +1,347 @@
+//! Human grades for log samples.
+//!
+//! Reviewers grade samples as correct, incorrect, or unsure to
+//! override automated scoring. Grades for a log are stored in a
+//! sidecar file next to the log (see `GRADES_SUFFIX`), keyed by sample
+//! Id and epoch.
+//!
+//! Each grade records the scorer result for the sample at the time it
+//! was graded. This is used to calculate agreement between reviewers
+//! and scorers without reading log samples.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    env,
+    error::Error,
+    inspect::{log::EvalSample, log_file},
+    result::Result,
+};
+
+/// Suffix of the grades file for a log (e.g. `LOG.eval.grades.json`).
+pub const GRADES_SUFFIX: &str = ".grades.json";
+
+const GRADES_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum HumanGrade {
+    Correct,
+    Incorrect,
+    Unsure,
+}
+
+impl HumanGrade {
+    /// Returns true or false for correct and incorrect grades and None
+    /// for unsure.
+    pub fn is_correct(&self) -> Option<bool> {
+        match self {
+            Self::Correct => Some(true),
+            Self::Incorrect => Some(false),
+            Self::Unsure => None,
+        }
+    }
+}
+
+impl fmt::Display for HumanGrade {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Correct => "correct",
+            Self::Incorrect => "incorrect",
+            Self::Unsure => "unsure",
+        })
+    }
+}
+
+/// Identifies a graded sample.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct GradeKey {
+    pub sample_id: String,
+    pub epoch: i64,
+}
+
+impl GradeKey {
+    pub fn new(sample: &EvalSample) -> Self {
+        Self {
+            sample_id: sample.id.to_string(),
+            epoch: sample.epoch,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Grade {
+    #[serde(flatten)]
+    pub key: GradeKey,
+
+    /// Reviewer grade.
+    pub grade: HumanGrade,
+
+    /// Reason for the grade.
+    pub reason: Option<String>,
+
+    /// Scorer result when the sample was graded (see
+    /// `EvalSample::is_correct`).
+    pub scorer: Option<bool>,
+
+    /// User that last updated the grade.
+    pub author: Option<String>,
+
+    /// Time the grade was last updated (RFC 3339).
+    pub updated: String,
+}
+
+/// Summary of human grades for a log.
+#[derive(Clone, Default, Debug)]
+pub struct GradeSummary {
+    /// Number of samples graded correct.
+    pub correct: usize,
+
+    /// Number of samples graded incorrect.
+    pub incorrect: usize,
+
+    /// Number of samples graded unsure.
+    pub unsure: usize,
+
+    /// Number of correct and incorrect grades with a scorer result.
+    pub compared: usize,
+
+    /// Number of compared grades that agree with the scorer result.
+    pub agreed: usize,
+}
+
+impl GradeSummary {
+    fn add(&mut self, grade: &Grade) {
+        match grade.grade {
+            HumanGrade::Correct => self.correct += 1,
+            HumanGrade::Incorrect => self.incorrect += 1,
+            HumanGrade::Unsure => self.unsure += 1,
+        }
+        if let (Some(human), Some(scorer)) = (grade.grade.is_correct(), grade.scorer) {
+            self.compared += 1;
+            if human == scorer {
+                self.agreed += 1;
+            }
+        }
+    }
+
+    /// Returns the number of graded samples.
+    pub fn graded(&self) -> usize {
+        self.correct + self.incorrect + self.unsure
+    }
+
+    /// Returns the portion of correct and incorrect grades that are
+    /// correct. Unsure grades are not counted.
+    pub fn human_accuracy(&self) -> Option<f64> {
+        match self.correct + self.incorrect {
+            0 => None,
+            n => Some(self.correct as f64 / n as f64),
+        }
+    }
+
+    /// Returns the portion of compared grades that agree with the
+    /// scorer.
+    pub fn agreement(&self) -> Option<f64> {
+        match self.compared {
+            0 => None,
+            n => Some(self.agreed as f64 / n as f64),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct GradesFile {
+    version: u32,
+    grades: Vec<Grade>,
+}
+
+#[derive(Serialize)]
+struct GradesFileRef<'a> {
+    version: u32,
+    grades: &'a [Grade],
+}
+
+/// Human grades for samples in a log.
+///
+/// Use `set` and `remove` to change grades. Changes are saved
+/// immediately.
+pub struct LogGrades {
+    log: String,
+    grades: Vec<Grade>,
+}
+
+impl LogGrades {
+    /// Opens the grades for a log.
+    ///
+    /// `log` is the log name. A missing grades file is treated as
+    /// empty.
+    pub fn open(log: &str) -> Result<Self> {
+        Ok(Self {
+            log: log.into(),
+            grades: read_grades(log)?,
+        })
+    }
+
+    /// Returns the grade for a sample.
+    pub fn get(&self, key: &GradeKey) -> Option<&Grade> {
+        self.grades.iter().find(|grade| grade.key == *key)
+    }
+
+    /// Returns a summary of grades for the log.
+    pub fn summary(&self) -> GradeSummary {
+        let mut summary = GradeSummary::default();
+        for grade in &self.grades {
+            summary.add(grade);
+        }
+        summary
+    }
+
+    /// Grades a sample and saves the grades.
+    ///
+    /// `scorer` is the scorer result for the sample. An empty reason
+    /// is saved as None.
+    pub fn set(
+        &mut self,
+        key: GradeKey,
+        grade: HumanGrade,
+        reason: &str,
+        scorer: Option<bool>,
+    ) -> Result<()> {
+        let reason = reason.trim();
+        let grade = Grade {
+            key,
+            grade,
+            reason: (!reason.is_empty()).then(|| reason.into()),
+            scorer,
+            author: env::get("USER").or_else(|| env::get("USERNAME")),
+            updated: chrono::Utc::now().to_rfc3339(),
+        };
+        self.update(&grade.key.clone(), Some(grade))
+    }
+
+    /// Removes the grade for a sample and saves the grades.
+    pub fn remove(&mut self, key: &GradeKey) -> Result<()> {
+        self.update(key, None)
+    }
+
+    /// Replaces the grade for `key` and saves the grades.
+    ///
+    /// Grades are read again before saving to include changes made
+    /// since they were opened.
+    fn update(&mut self, key: &GradeKey, grade: Option<Grade>) -> Result<()> {
+        let mut grades = read_grades(&self.log)?;
+        grades.retain(|grade| grade.key != *key);
+        grades.extend(grade);
+        grades.sort_by(|a, b| sort_key(a).cmp(&sort_key(b)));
+        let bytes = serde_json::to_vec_pretty(&GradesFileRef {
+            version: GRADES_VERSION,
+            grades: &grades,
+        })
+        .map_err(|e| Error::general(e.to_string()))?;
+        log_file::write_sidecar(&self.log, GRADES_SUFFIX, &bytes)?;
+        self.grades = grades;
+        Ok(())
+    }
+}
+
+/// Returns a summary of grades for a log.
+///
+/// Used where grades are optional, such as log tables. Errors reading
+/// grades are logged and an empty summary is returned.
+pub fn grade_summary(log: &str) -> GradeSummary {
+    match LogGrades::open(log) {
+        Ok(grades) => grades.summary(),
+        Err(err) => {
+            log::error!("Reading grades for {log}: {err:?}");
+            GradeSummary::default()
+        }
+    }
+}
+
+fn read_grades(log: &str) -> Result<Vec<Grade>> {
+    let Some(bytes) = log_file::read_sidecar(log, GRADES_SUFFIX)? else {
+        return Ok(Vec::new());
+    };
+    let grades = serde_json::from_slice::<GradesFile>(&bytes).map_err(|e| {
+        Error::general(format!("Error reading grades in {log}{GRADES_SUFFIX}: {e}"))
+    })?;
+    if grades.version != GRADES_VERSION {
+        return Err(Error::general(format!(
+            "Unsupported grades version in {log}{GRADES_SUFFIX}: {}",
+            grades.version
+        )));
+    }
+    Ok(grades.grades)
+}
+
+/// Returns a key to sort grades by epoch and sample Id.
+fn sort_key(grade: &Grade) -> (i64, &str) {
+    (grade.key.epoch, &grade.key.sample_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::util::TempDir;
+
+    use super::{GradeKey, HumanGrade, LogGrades};
+
+    #[test]
+    fn test_grade_summary() {
+        let tmp = TempDir::create("gage-grades-test").unwrap();
+        let log_dir = tmp.path();
+        let log = log_dir.join("a.eval").to_string_lossy().to_string();
+        let key = |id: &str| GradeKey {
+            sample_id: id.into(),
+            epoch: 1,
+        };
+
+        let mut grades = LogGrades::open(&log).unwrap();
+        assert_eq!(grades.summary().graded(), 0);
+        assert_eq!(grades.summary().human_accuracy(), None);
+        grades
+            .set(key("1"), HumanGrade::Correct, "", Some(true))
+            .unwrap();
+        grades
+            .set(
+                key("2"),
+                HumanGrade::Correct,
+                "Equivalent answer",
+                Some(false),
+            )
+            .unwrap();
+        grades
+            .set(key("3"), HumanGrade::Incorrect, "", Some(false))
+            .unwrap();
+        grades
+            .set(key("4"), HumanGrade::Unsure, "Ambiguous target", Some(true))
+            .unwrap();
+        grades
+            .set(key("5"), HumanGrade::Incorrect, "", None)
+            .unwrap();
+
+        // Grades are saved
+        let mut grades = LogGrades::open(&log).unwrap();
+        assert!(log_dir.join("a.eval.grades.json").exists());
+        let summary = grades.summary();
+        assert_eq!(summary.graded(), 5);
+        assert_eq!(summary.human_accuracy(), Some(0.5));
+        assert_eq!(summary.agreement(), Some(2.0 / 3.0));
+        assert_eq!(grades.get(&key("1")).unwrap().reason, None);
+        assert_eq!(
+            grades.get(&key("2")).unwrap().reason.as_deref(),
+            Some("Equivalent answer")
+        );
+
+        // Regrading replaces a grade
+        grades
+            .set(key("2"), HumanGrade::Incorrect, "", Some(false))
+            .unwrap();
+        grades.remove(&key("5")).unwrap();
+        let summary = grades.summary();
+        assert_eq!(summary.graded(), 4);
+        assert_eq!(summary.human_accuracy(), Some(1.0 / 3.0));
+        assert_eq!(summary.agreement(), Some(1.0));
+    }
+}
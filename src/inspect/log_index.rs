@@ -0,0 +1,349 @@
+//! Log header index.
+//!
+//! Reading the header of every log is slow for log directories with
+//! many logs. The index stores a summary of each log header in the log
+//! directory (see `INDEX_PATH`), keyed by log file name. An entry is
+//! used as long as the log file modification time and size are
+//! unchanged. Otherwise the header is read again and the entry is
+//! updated.
+//!
+//! S3 log directories are indexed using the last modified time, size,
+//! and ETag of each object as listed, so that unchanged logs aren't
+//! downloaded. The index for an S3 log directory is stored in the
+//! bucket alongside the logs.
+
+use std::{
+    collections::{BTreeMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+    time::UNIX_EPOCH,
+};
+
+use pyo3::Python;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    inspect::{
+        log::{EvalLog, EvalLogInfo, EvalStatus, read_log_header},
+        log_file,
+        log_grades::GradeSummary,
+        s3::{S3Client, S3Url},
+    },
+    py::EpochMillis,
+    result::Result,
+};
+
+/// Index location, relative to the log directory.
+pub const INDEX_PATH: &str = ".gage/log-index";
+
+const INDEX_VERSION: u32 = 1;
+
+/// Summary of a log header.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct LogSummary {
+    /// Location that the log was read from.
+    #[serde(skip)]
+    pub location: String,
+
+    pub task: String,
+    pub task_id: String,
+    pub eval_id: String,
+    pub run_id: String,
+    pub run_type: Option<String>,
+    pub status: EvalStatus,
+    pub model: String,
+    pub dataset: Option<String>,
+    pub samples: Option<usize>,
+    pub created: EpochMillis,
+    pub accuracy: Option<f64>,
+    pub stderr: Option<f64>,
+    pub tags: Vec<String>,
+    pub error: Option<String>,
+
+    /// Completed and total samples for running evals.
+    #[serde(default)]
+    pub progress: Option<(usize, usize)>,
+
+    /// Human grades for the log (see `LogGrades`).
+    ///
+    /// Grades are not read from the log header and are not indexed.
+    #[serde(skip)]
+    pub grades: GradeSummary,
+}
+
+impl LogSummary {
+    pub fn new(header: &EvalLog) -> Self {
+        Self {
+            location: header.location.clone(),
+            task: header.eval.task.clone(),
+            task_id: header.eval.task_id.clone(),
+            eval_id: header.eval.eval_id.clone(),
+            run_id: header.eval.run_id.clone(),
+            run_type: header.eval.run_type(),
+            status: header.status,
+            model: header.eval.model.clone(),
+            dataset: header.eval.dataset.name.clone(),
+            samples: header.eval.dataset.evaluated_count(),
+            created: header.eval.created.clone(),
+            accuracy: header.results.as_ref().and_then(|r| r.first_accuracy()),
+            stderr: header.results.as_ref().and_then(|r| r.first_stderr()),
+            tags: header.eval.tags.clone().unwrap_or_default(),
+            error: header.error.as_ref().map(|e| e.message.clone()),
+            progress: match header.status {
+                EvalStatus::Started => header
+                    .eval
+                    .total_samples()
+                    .map(|total| (header.completed_samples.unwrap_or(0), total)),
+                _ => None,
+            },
+            grades: GradeSummary::default(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct IndexFile {
+    version: u32,
+    logs: BTreeMap<String, IndexEntry>,
+}
+
+#[derive(Serialize)]
+struct IndexFileRef<'a> {
+    version: u32,
+    logs: &'a BTreeMap<String, IndexEntry>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct IndexEntry {
+    mtime: u64,
+    size: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    etag: Option<String>,
+    summary: LogSummary,
+}
+
+/// Values used to check if a log changed since it was indexed.
+#[derive(PartialEq)]
+struct Stamp {
+    mtime: u64,
+    size: u64,
+    etag: Option<String>,
+}
+
+/// Log header index for a log directory.
+///
+/// Use `summary` to get a log summary and `save` to write index
+/// changes.
+pub struct LogIndex {
+    log_dir: PathBuf,
+    s3_dir: Option<S3Url>,
+    logs: BTreeMap<String, IndexEntry>,
+    changed: bool,
+}
+
+impl LogIndex {
+    /// Opens the index for a log directory.
+    ///
+    /// A missing or unreadable index is treated as empty.
+    pub fn open(log_dir: &Path) -> Self {
+        let s3_dir = log_dir.to_str().and_then(S3Url::parse);
+        let log_dir = match s3_dir {
+            Some(_) => log_dir.into(),
+            None => std::path::absolute(log_dir).unwrap_or_else(|_| log_dir.into()),
+        };
+        let bytes = match &s3_dir {
+            Some(s3_dir) => S3Client::from_env().get(&s3_index_url(s3_dir)).ok(),
+            None => fs::read(log_dir.join(INDEX_PATH)).ok(),
+        };
+        let logs = match bytes {
+            Some(bytes) => match serde_json::from_slice::<IndexFile>(&bytes) {
+                Ok(index) if index.version == INDEX_VERSION => index.logs,
+                Ok(_) => BTreeMap::new(),
+                Err(e) => {
+                    log::debug!("Error reading log index in {}: {e}", log_dir.display());
+                    BTreeMap::new()
+                }
+            },
+            None => BTreeMap::new(),
+        };
+        Self {
+            log_dir,
+            s3_dir,
+            logs,
+            changed: false,
+        }
+    }
+
+    /// Returns the summary for a log.
+    ///
+    /// Reads the log header if the log isn't indexed or has changed
+    /// since it was indexed.
+    pub fn summary(&mut self, py: Python<'_>, log: &EvalLogInfo) -> Result<LogSummary> {
+        let Some((key, stamp)) = self.stamp(log)? else {
+            return Ok(LogSummary::new(&read_log_header(py, &log.name)?));
+        };
+        if let Some(entry) = self.logs.get(&key)
+            && entry.mtime == stamp.mtime
+            && entry.size == stamp.size
+            && entry.etag == stamp.etag
+        {
+            return Ok(LogSummary {
+                location: log.name.clone(),
+                ..entry.summary.clone()
+            });
+        }
+        let summary = LogSummary {
+            location: log.name.clone(),
+            ..LogSummary::new(&read_log_header(py, &log.name)?)
+        };
+        self.logs.insert(
+            key,
+            IndexEntry {
+                mtime: stamp.mtime,
+                size: stamp.size,
+                etag: stamp.etag,
+                summary: summary.clone(),
+            },
+        );
+        self.changed = true;
+        Ok(summary)
+    }
+
+    /// Returns the index key and stamp for a log.
+    ///
+    /// Returns None if the log can't be indexed. S3 logs are stamped
+    /// using their listed object attributes, which aren't available
+    /// for logs listed by Inspect.
+    fn stamp(&self, log: &EvalLogInfo) -> Result<Option<(String, Stamp)>> {
+        if let Some(path) = log_file::local_path(&log.name) {
+            let (mtime, size) = file_stamp(path)?;
+            return Ok(Some((
+                self.key(path),
+                Stamp {
+                    mtime,
+                    size,
+                    etag: None,
+                },
+            )));
+        }
+        let (Some(s3_dir), Some(size)) = (&self.s3_dir, log.size) else {
+            return Ok(None);
+        };
+        let prefix = s3_dir.with_key(&s3_prefix(s3_dir)).to_string();
+        let key = log.name.strip_prefix(&prefix).unwrap_or(&log.name).into();
+        let mtime = log
+            .mtime
+            .as_ref()
+            .and_then(|t| t.as_datetime().timestamp_nanos_opt())
+            .unwrap_or_default() as u64;
+        Ok(Some((
+            key,
+            Stamp {
+                mtime,
+                size,
+                etag: log.etag.clone(),
+            },
+        )))
+    }
+
+    /// Writes the index if it changed.
+    ///
+    /// Entries for logs that no longer exist are removed. Errors are
+    /// logged rather than returned as the index is only used to speed
+    /// up log reads.
+    pub fn save(&mut self) {
+        if !self.changed {
+            return;
+        }
+        self.remove_missing();
+        match (self.write(), &self.s3_dir) {
+            (Ok(()), _) => {}
+            // S3 credentials are often read-only
+            (Err(e), Some(_)) => {
+                log::debug!("Error writing log index in {}: {e}", self.log_dir.display())
+            }
+            (Err(e), None) => {
+                log::warn!("Error writing log index in {}: {e}", self.log_dir.display())
+            }
+        }
+        self.changed = false;
+    }
+
+    /// Removes entries for logs that no longer exist.
+    fn remove_missing(&mut self) {
+        let Some(s3_dir) = &self.s3_dir else {
+            let log_dir = &self.log_dir;
+            self.logs.retain(|key, _| log_dir.join(key).exists());
+            return;
+        };
+        // Use a single listing rather than checking each object
+        let prefix = s3_prefix(s3_dir);
+        match S3Client::from_env().list(&s3_dir.bucket, &prefix) {
+            Ok(objects) => {
+                let keys = objects
+                    .into_iter()
+                    .map(|obj| obj.key[prefix.len()..].to_string())
+                    .collect::<HashSet<_>>();
+                self.logs.retain(|key, _| keys.contains(key));
+            }
+            Err(e) => log::debug!("Error listing {s3_dir}: {e}"),
+        }
+    }
+
+    fn write(&self) -> Result<()> {
+        let json = serde_json::to_vec(&IndexFileRef {
+            version: INDEX_VERSION,
+            logs: &self.logs,
+        })
+        .map_err(std::io::Error::other)?;
+        if let Some(s3_dir) = &self.s3_dir {
+            return S3Client::from_env().put(&s3_index_url(s3_dir), &json);
+        }
+        let path = self.log_dir.join(INDEX_PATH);
+        fs::create_dir_all(path.parent().unwrap())?;
+        // Write to a temp file and rename to avoid partial reads - the
+        // temp file is unique to this process so that concurrent
+        // writers don't clobber each other's temp files
+        let tmp = path.with_extension(format!("{}.tmp", std::process::id()));
+        let result = fs::write(&tmp, json).and_then(|_| fs::rename(&tmp, path));
+        if result.is_err() {
+            let _ = fs::remove_file(&tmp);
+        }
+        Ok(result?)
+    }
+
+    /// Returns the index key for a log path.
+    ///
+    /// Keys are relative to the log directory when possible.
+    fn key(&self, path: &Path) -> String {
+        path.strip_prefix(&self.log_dir)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .into()
+    }
+}
+
+/// Returns the key prefix for logs in an S3 log directory.
+fn s3_prefix(s3_dir: &S3Url) -> String {
+    match s3_dir.key.trim_end_matches('/') {
+        "" => String::new(),
+        key => format!("{key}/"),
+    }
+}
+
+/// Returns the index location for an S3 log directory.
+fn s3_index_url(s3_dir: &S3Url) -> S3Url {
+    s3_dir.with_key(&format!("{}{INDEX_PATH}", s3_prefix(s3_dir)))
+}
+
+/// Returns modification time (nanoseconds since epoch) and size for a
+/// file.
+fn file_stamp(path: &Path) -> Result<(u64, u64)> {
+    let meta = fs::metadata(path)?;
+    let mtime = meta
+        .modified()?
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or_default();
+    Ok((mtime, meta.len()))
+}
@@ -0,0 +1,323 @@
+//! Merges logs into a single log.
+//!
+//! Used to write one log for a batch of task runs, where each run
+//! writes its own log. The merged log uses the eval spec and plan of
+//! the first log. Samples are numbered in log order and results are
+//! calculated from sample scores.
+//!
+//! Only metrics that can be calculated from sample scores are kept
+//! (`accuracy`, `mean`, and `stderr`). Other metrics are removed from
+//! the merged log.
+
+use std::path::Path;
+
+use serde_json::{Map, Value, json};
+
+use crate::{error::Error, inspect::log_file, result::Result};
+
+/// Summary fields copied from samples.
+const SUMMARY_FIELDS: [&str; 11] = [
+    "id",
+    "epoch",
+    "input",
+    "target",
+    "metadata",
+    "scores",
+    "model_usage",
+    "total_time",
+    "working_time",
+    "uuid",
+    "limit",
+];
+
+/// Merges logs and writes the merged log to `log_dir`.
+///
+/// `dataset` is used as the dataset name for the merged log. The merged
+/// log file name is that of the first log. Returns the merged log name.
+pub fn merge_logs(logs: &[String], dataset: &str, log_dir: &Path) -> Result<String> {
+    let first = logs
+        .first()
+        .ok_or_else(|| Error::general("no logs to merge"))?;
+    let mut headers = Vec::with_capacity(logs.len());
+    let mut samples = Vec::new();
+    for name in logs {
+        let (header, log_samples) = log_file::read_log_values(name)?;
+        headers.push(header);
+        samples.extend(log_samples);
+    }
+    for (i, sample) in samples.iter_mut().enumerate() {
+        if let Some(sample) = sample.as_object_mut() {
+            sample.insert("id".into(), json!(i + 1));
+            sample.insert("epoch".into(), json!(1));
+        }
+    }
+    let header = merge_headers(&headers, &samples, dataset);
+    let summaries = samples.iter().map(sample_summary).collect::<Vec<_>>();
+    let file_name = first.rsplit('/').next().unwrap_or(first);
+    let file_name = match file_name.rsplit_once('.') {
+        Some((stem, _)) => format!("{stem}.eval"),
+        None => format!("{file_name}.eval"),
+    };
+    log_file::write_eval_log(log_dir, &file_name, &header, &samples, &summaries)
+}
+
+fn merge_headers(headers: &[Value], samples: &[Value], dataset: &str) -> Value {
+    let mut header = headers[0].clone();
+
+    // Status is that of the first log that didn't succeed, if any
+    if let Some(failed) = headers
+        .iter()
+        .find(|header| header["status"].as_str() != Some("success"))
+    {
+        header["status"] = failed["status"].clone();
+        header["error"] = failed["error"].clone();
+    }
+
+    // Dataset
+    header["eval"]["dataset"] = json!({
+        "name": dataset,
+        "location": dataset,
+        "samples": samples.len(),
+        "sample_ids": (1..=samples.len()).collect::<Vec<_>>(),
+        "shuffled": false,
+    });
+
+    // Results
+    if let Some(results) = header["results"].as_object_mut() {
+        results.insert("total_samples".into(), json!(samples.len()));
+        results.insert(
+            "completed_samples".into(),
+            json!(
+                samples
+                    .iter()
+                    .filter(|sample| sample["error"].is_null())
+                    .count()
+            ),
+        );
+        if let Some(Value::Array(scores)) = results.get_mut("scores") {
+            for score in scores {
+                merge_score(score, samples);
+            }
+        }
+    }
+
+    // Stats
+    let last = &headers[headers.len() - 1];
+    header["stats"]["completed_at"] = last["stats"]["completed_at"].clone();
+    header["stats"]["model_usage"] = merge_usage(
+        headers
+            .iter()
+            .filter_map(|header| header["stats"]["model_usage"].as_object()),
+    );
+
+    header
+}
+
+/// Calculates score metrics from sample scores.
+fn merge_score(score: &mut Value, samples: &[Value]) {
+    let name = score["name"].as_str().unwrap_or_default().to_string();
+    let values = samples
+        .iter()
+        .filter_map(|sample| score_to_float(&sample["scores"][&name]["value"]))
+        .collect::<Vec<_>>();
+    score["scored_samples"] = json!(values.len());
+    score["unscored_samples"] = json!(samples.len() - values.len());
+    let Some(metrics) = score["metrics"].as_object_mut() else {
+        return;
+    };
+    metrics.retain(|name, metric| {
+        let value = match name.as_str() {
+            "accuracy" | "mean" => mean(&values),
+            "stderr" => stderr(&values),
+            _ => return false,
+        };
+        metric["value"] = value.map(Value::from).unwrap_or(Value::Null);
+        true
+    });
+}
+
+/// Converts a score value to a float, consistent with Inspect's
+/// `value_to_float`.
+fn score_to_float(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => n.as_f64(),
+        Value::Bool(b) => Some(if *b { 1.0 } else { 0.0 }),
+        Value::String(s) => match s.to_lowercase().as_str() {
+            "c" | "yes" | "true" => Some(1.0),
+            "i" | "n" | "no" | "false" => Some(0.0),
+            "p" => Some(0.5),
+            s => s.parse().ok(),
+        },
+        _ => None,
+    }
+}
+
+fn mean(values: &[f64]) -> Option<f64> {
+    match values.len() {
+        0 => None,
+        n => Some(values.iter().sum::<f64>() / n as f64),
+    }
+}
+
+/// Returns the standard error of the mean.
+fn stderr(values: &[f64]) -> Option<f64> {
+    let mean = mean(values)?;
+    let n = values.len();
+    if n == 1 {
+        return Some(0.0);
+    }
+    let var = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1) as f64;
+    Some((var / n as f64).sqrt())
+}
+
+/// Sums model usage by model.
+fn merge_usage<'a>(usage: impl Iterator<Item = &'a Map<String, Value>>) -> Value {
+    let mut merged = Map::new();
+    for (model, model_usage) in usage.flatten() {
+        let Some(model_usage) = model_usage.as_object() else {
+            continue;
+        };
+        let merged = merged
+            .entry(model.clone())
+            .or_insert_with(|| json!({}))
+            .as_object_mut()
+            .expect("object");
+        for (field, val) in model_usage {
+            let Some(val) = val.as_u64() else {
+                continue;
+            };
+            let total = merged.get(field).and_then(Value::as_u64).unwrap_or(0) + val;
+            merged.insert(field.clone(), json!(total));
+        }
+    }
+    Value::Object(merged)
+}
+
+/// Returns an Inspect sample summary for a sample.
+fn sample_summary(sample: &Value) -> Value {
+    let mut summary = Map::new();
+    for field in SUMMARY_FIELDS {
+        if let Some(val) = sample.get(field) {
+            summary.insert(field.into(), val.clone());
+        }
+    }
+    summary.insert("error".into(), sample["error"]["message"].clone());
+    summary.insert(
+        "message_count".into(),
+        json!(sample["messages"].as_array().map(Vec::len)),
+    );
+    summary.insert("completed".into(), json!(true));
+    Value::Object(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::{
+        inspect::{log::EvalStatus, log_file::read_log_file},
+        util::TempDir,
+    };
+
+    use super::{merge_logs, merge_score, merge_usage};
+
+    #[test]
+    fn test_merge_logs() {
+        let tmp = TempDir::create("gage-merge-test").unwrap();
+        let logs = ["C", "I"]
+            .into_iter()
+            .enumerate()
+            .map(|(i, value)| {
+                let log = json!({
+                    "version": 2,
+                    "status": "success",
+                    "eval": {
+                        "eval_id": format!("E{i}"), "run_id": "R1",
+                        "created": "2025-01-01T10:00:00+00:00",
+                        "task": "hello", "task_id": format!("T{i}"), "dataset": {},
+                        "model": "mockllm/model"
+                    },
+                    "plan": {"name": "plan", "steps": []},
+                    "results": {
+                        "total_samples": 1,
+                        "completed_samples": 1,
+                        "scores": [{
+                            "name": "match", "scorer": "match",
+                            "metrics": {"accuracy": {"name": "accuracy", "value": 1.0}}
+                        }]
+                    },
+                    "stats": {
+                        "model_usage": {"mockllm/model": {"input_tokens": 10, "output_tokens": 5}}
+                    },
+                    "samples": [{
+                        "id": 1, "epoch": 1, "input": format!("input {i}"), "output": {},
+                        "scores": {"match": {"value": value}}
+                    }]
+                });
+                let path = tmp
+                    .path()
+                    .join(format!("2025-01-01T10-00-0{i}+00-00_hello_A{i}.json"));
+                std::fs::write(&path, log.to_string()).unwrap();
+                path.to_string_lossy().to_string()
+            })
+            .collect::<Vec<_>>();
+
+        let merged = merge_logs(&logs, "inputs.jsonl", tmp.path()).unwrap();
+        assert!(merged.ends_with("2025-01-01T10-00-00+00-00_hello_A0.eval"));
+
+        // Merged log is readable
+        let log = read_log_file(&merged, false).unwrap();
+        assert_eq!(log.status, EvalStatus::Success);
+        assert_eq!(log.eval.dataset.name.as_deref(), Some("inputs.jsonl"));
+        let results = log.results.as_ref().unwrap();
+        assert_eq!(results.total_samples, 2);
+        assert_eq!(results.completed_samples, 2);
+        assert_eq!(results.first_accuracy(), Some(0.5));
+        assert_eq!(log.stats.model_usage["mockllm/model"].input_tokens, 20);
+        let samples = log.samples.as_ref().unwrap();
+        assert_eq!(
+            samples
+                .iter()
+                .map(|sample| sample.id.to_string())
+                .collect::<Vec<_>>(),
+            ["1", "2"]
+        );
+        assert_eq!(samples[1].is_correct(), Some(false));
+    }
+
+    #[test]
+    fn test_merge_score() {
+        let samples = ["C", "I", "C", "C"]
+            .map(|value| json!({"scores": {"match": {"value": value}}}))
+            .into_iter()
+            .chain([json!({"scores": {}})])
+            .collect::<Vec<_>>();
+        let mut score = json!({
+            "name": "match",
+            "metrics": {
+                "accuracy": {"name": "accuracy", "value": 1.0},
+                "stderr": {"name": "stderr", "value": 0.0},
+                "f1": {"name": "f1", "value": 1.0}
+            }
+        });
+        merge_score(&mut score, &samples);
+        assert_eq!(score["scored_samples"], 4);
+        assert_eq!(score["unscored_samples"], 1);
+        assert_eq!(score["metrics"]["accuracy"]["value"], 0.75);
+        assert_eq!(score["metrics"]["stderr"]["value"], 0.25);
+        assert!(score["metrics"].get("f1").is_none());
+    }
+
+    #[test]
+    fn test_merge_usage() {
+        let usage = [
+            json!({"m": {"input_tokens": 10, "output_tokens": 5, "total_tokens": 15}}),
+            json!({"m": {"input_tokens": 1, "output_tokens": 2, "total_tokens": 3,
+                         "reasoning_tokens": null}}),
+        ];
+        assert_eq!(
+            merge_usage(usage.iter().filter_map(|usage| usage.as_object())),
+            json!({"m": {"input_tokens": 11, "output_tokens": 7, "total_tokens": 18}})
+        );
+    }
+}
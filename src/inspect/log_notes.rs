@@ -0,0 +1,198 @@
+//! Reviewer notes for logs.
+//!
+//! Notes are free-text comments attached to a log or to a log sample.
+//! Notes for a log are stored in a sidecar file next to the log (see
+//! `NOTES_SUFFIX`), keyed by sample Id and epoch for sample notes.
+//! Notes are not stored in log files and are unaffected by changes to
+//! them.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    env,
+    error::Error,
+    inspect::{log::EvalSample, log_file},
+    result::Result,
+};
+
+/// Suffix of the notes file for a log (e.g. `LOG.eval.notes.json`).
+pub const NOTES_SUFFIX: &str = ".notes.json";
+
+const NOTES_VERSION: u32 = 1;
+
+/// Identifies the log or sample that a note is for.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct NoteKey {
+    /// Sample Id, or None for a log note.
+    pub sample_id: Option<String>,
+
+    /// Sample epoch, or None for a log note.
+    pub epoch: Option<i64>,
+}
+
+impl NoteKey {
+    pub fn log() -> Self {
+        Self {
+            sample_id: None,
+            epoch: None,
+        }
+    }
+
+    pub fn sample(sample: &EvalSample) -> Self {
+        Self {
+            sample_id: Some(sample.id.to_string()),
+            epoch: Some(sample.epoch),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Note {
+    #[serde(flatten)]
+    pub key: NoteKey,
+
+    /// Note text.
+    pub text: String,
+
+    /// User that last updated the note.
+    pub author: Option<String>,
+
+    /// Time the note was last updated (RFC 3339).
+    pub updated: String,
+}
+
+#[derive(Deserialize)]
+struct NotesFile {
+    version: u32,
+    notes: Vec<Note>,
+}
+
+#[derive(Serialize)]
+struct NotesFileRef<'a> {
+    version: u32,
+    notes: &'a [Note],
+}
+
+/// Notes for a log.
+///
+/// Use `set` to add, change, or remove a note. Changes are saved
+/// immediately.
+pub struct LogNotes {
+    log: String,
+    notes: Vec<Note>,
+}
+
+impl LogNotes {
+    /// Opens the notes for a log.
+    ///
+    /// `log` is the log name. A missing notes file is treated as empty.
+    pub fn open(log: &str) -> Result<Self> {
+        Ok(Self {
+            log: log.into(),
+            notes: read_notes(log)?,
+        })
+    }
+
+    /// Returns the note for a log or sample.
+    pub fn get(&self, key: &NoteKey) -> Option<&Note> {
+        self.notes.iter().find(|note| note.key == *key)
+    }
+
+    /// Returns all notes, with the log note first followed by sample
+    /// notes.
+    pub fn iter(&self) -> impl Iterator<Item = &Note> {
+        self.notes.iter()
+    }
+
+    /// Sets the note for a log or sample and saves the notes.
+    ///
+    /// Notes are read again before saving to include changes made
+    /// since they were opened. Empty text removes the note.
+    pub fn set(&mut self, key: NoteKey, text: &str) -> Result<()> {
+        let mut notes = read_notes(&self.log)?;
+        notes.retain(|note| note.key != key);
+        let text = text.trim();
+        if !text.is_empty() {
+            notes.push(Note {
+                key,
+                text: text.into(),
+                author: env::get("USER").or_else(|| env::get("USERNAME")),
+                updated: chrono::Utc::now().to_rfc3339(),
+            });
+        }
+        sort_notes(&mut notes);
+        let bytes = serde_json::to_vec_pretty(&NotesFileRef {
+            version: NOTES_VERSION,
+            notes: &notes,
+        })
+        .map_err(|e| Error::general(e.to_string()))?;
+        log_file::write_sidecar(&self.log, NOTES_SUFFIX, &bytes)?;
+        self.notes = notes;
+        Ok(())
+    }
+}
+
+fn read_notes(log: &str) -> Result<Vec<Note>> {
+    let Some(bytes) = log_file::read_sidecar(log, NOTES_SUFFIX)? else {
+        return Ok(Vec::new());
+    };
+    let notes = serde_json::from_slice::<NotesFile>(&bytes)
+        .map_err(|e| Error::general(format!("Error reading notes in {log}{NOTES_SUFFIX}: {e}")))?;
+    if notes.version != NOTES_VERSION {
+        return Err(Error::general(format!(
+            "Unsupported notes version in {log}{NOTES_SUFFIX}: {}",
+            notes.version
+        )));
+    }
+    Ok(notes.notes)
+}
+
+/// Sorts notes with the log note first followed by sample notes in
+/// epoch and sample Id order.
+fn sort_notes(notes: &mut [Note]) {
+    notes.sort_by(|a, b| {
+        let key = |note: &Note| (note.key.epoch, note.key.sample_id.clone());
+        key(a).cmp(&key(b))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::util::TempDir;
+
+    use super::{LogNotes, NoteKey};
+
+    #[test]
+    fn test_set_notes() {
+        let tmp = TempDir::create("gage-notes-test").unwrap();
+        let log_dir = tmp.path();
+        let log = log_dir.join("a.eval").to_string_lossy().to_string();
+        let sample_key = |id: &str| NoteKey {
+            sample_id: Some(id.into()),
+            epoch: Some(1),
+        };
+
+        let mut notes = LogNotes::open(&log).unwrap();
+        assert!(notes.get(&NoteKey::log()).is_none());
+        notes.set(sample_key("2"), "Wrong target\n").unwrap();
+        notes.set(NoteKey::log(), "Needs review").unwrap();
+        notes.set(sample_key("1"), "Looks good").unwrap();
+
+        // Notes are saved next to the log
+        assert!(log_dir.join("a.eval.notes.json").exists());
+        let notes = LogNotes::open(&log).unwrap();
+        assert_eq!(
+            notes
+                .iter()
+                .map(|note| note.text.as_str())
+                .collect::<Vec<_>>(),
+            ["Needs review", "Looks good", "Wrong target"]
+        );
+
+        // Empty text removes a note
+        let mut notes = LogNotes::open(&log).unwrap();
+        notes.set(sample_key("1"), " ").unwrap();
+        assert!(notes.get(&sample_key("1")).is_none());
+        assert_eq!(notes.get(&sample_key("2")).unwrap().text, "Wrong target");
+    }
+}
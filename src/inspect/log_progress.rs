@@ -0,0 +1,118 @@
+//! Progress of running evals.
+//!
+//! Running evals write each sample to their `.eval` log as it
+//! completes. Progress is calculated from these samples. Each sample is
+//! parsed once - updates parse only the samples written since the last
+//! update (see `log_file::read_new_samples`). Local logs are read from
+//! the file so updates don't read samples that were already counted. S3
+//! logs are downloaded in full for each update.
+//!
+//! `.json` logs are written when an eval finishes. Progress for these
+//! logs is only available once the eval is complete.
+
+use std::collections::HashSet;
+
+use crate::{
+    inspect::{
+        log::{EvalSample, EvalStatus},
+        log_file,
+    },
+    result::Result,
+};
+
+/// Progress of an eval.
+#[derive(Debug)]
+pub struct EvalProgress {
+    /// Log the progress is read from.
+    pub location: String,
+
+    pub task: String,
+    pub model: String,
+    pub status: EvalStatus,
+
+    /// Number of completed samples, including samples with errors.
+    pub completed: usize,
+
+    /// Total samples for the eval, if known.
+    pub total: Option<usize>,
+
+    /// Number of completed samples with a correct or incorrect score.
+    pub scored: usize,
+
+    /// Number of scored samples that are correct.
+    pub correct: usize,
+
+    /// Number of samples halted by an error.
+    pub errors: usize,
+
+    /// Total model tokens used by completed samples.
+    pub tokens: usize,
+
+    read: HashSet<String>,
+}
+
+impl EvalProgress {
+    /// Returns progress for a log, read using `update`.
+    pub fn new(location: &str) -> Self {
+        Self {
+            location: location.into(),
+            task: String::new(),
+            model: String::new(),
+            status: EvalStatus::Started,
+            completed: 0,
+            total: None,
+            scored: 0,
+            correct: 0,
+            errors: 0,
+            tokens: 0,
+            read: HashSet::new(),
+        }
+    }
+
+    /// Updates progress with samples completed since the last update.
+    pub fn update(&mut self) -> Result<()> {
+        let (log, samples) = log_file::read_new_samples(&self.location, &mut self.read)?;
+        self.total = log
+            .results
+            .map(|results| results.total_samples)
+            .or_else(|| log.eval.total_samples());
+        self.task = log.eval.task_display_name.unwrap_or(log.eval.task);
+        self.model = log.eval.model;
+        self.status = log.status;
+        for sample in &samples {
+            self.add(sample);
+        }
+        Ok(())
+    }
+
+    fn add(&mut self, sample: &EvalSample) {
+        self.completed += 1;
+        if sample.error.is_some() {
+            self.errors += 1;
+        }
+        if let Some(correct) = sample.is_correct() {
+            self.scored += 1;
+            if correct {
+                self.correct += 1;
+            }
+        }
+        self.tokens += sample
+            .model_usage
+            .values()
+            .map(|usage| usage.total_tokens)
+            .sum::<usize>();
+    }
+
+    /// Returns the portion of scored samples that are correct.
+    pub fn accuracy(&self) -> Option<f64> {
+        match self.scored {
+            0 => None,
+            n => Some(self.correct as f64 / n as f64),
+        }
+    }
+
+    /// Returns true if the eval is no longer running.
+    pub fn is_done(&self) -> bool {
+        self.status != EvalStatus::Started
+    }
+}
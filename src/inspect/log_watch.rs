@@ -0,0 +1,72 @@
+//! Log directory watcher.
+//!
+//! Changes are detected by polling the log directory listing and
+//! comparing log names and modification times. Polling is used rather
+//! than file system notifications so that S3 log directories can be
+//! watched the same way as local directories.
+
+use std::{
+    path::{Path, PathBuf},
+    thread,
+    time::Duration,
+};
+
+use crate::{inspect::log_file, py::EpochMillis};
+
+/// Interval between log directory checks.
+pub const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+type Stamp = Vec<(String, Option<EpochMillis>)>;
+
+/// Watches a log directory for added, removed, and modified logs.
+pub struct LogDirWatch {
+    log_dir: PathBuf,
+    stamp: Option<Stamp>,
+}
+
+impl LogDirWatch {
+    pub fn new(log_dir: &Path) -> Self {
+        let log_dir = log_dir.to_path_buf();
+        let stamp = read_stamp(&log_dir);
+        Self { log_dir, stamp }
+    }
+
+    /// Returns true if logs changed since the last check.
+    ///
+    /// Logs are considered unchanged if they can't be listed.
+    pub fn changed(&mut self) -> bool {
+        let Some(stamp) = read_stamp(&self.log_dir) else {
+            return false;
+        };
+        if self.stamp.as_ref() == Some(&stamp) {
+            return false;
+        }
+        self.stamp = Some(stamp);
+        true
+    }
+
+    /// Blocks until logs change.
+    pub fn wait(&mut self) {
+        loop {
+            thread::sleep(POLL_INTERVAL);
+            if self.changed() {
+                return;
+            }
+        }
+    }
+}
+
+/// Returns log names and modification times for a log directory.
+///
+/// Returns None if logs can't be listed. Errors are logged rather than
+/// returned as they're typically transient (e.g. a network error for
+/// an S3 log directory).
+fn read_stamp(log_dir: &Path) -> Option<Stamp> {
+    match log_file::list_log_files(log_dir, false) {
+        Ok(logs) => Some(logs.into_iter().map(|log| (log.name, log.mtime)).collect()),
+        Err(e) => {
+            log::debug!("Error listing logs in {}: {e}", log_dir.display());
+            None
+        }
+    }
+}
@@ -6,7 +6,15 @@ pub mod dataset;
 pub mod event;
 pub mod json;
 pub mod log;
+pub mod log_file;
+pub mod log_grades;
+pub mod log_index;
+pub mod log_merge;
+pub mod log_notes;
+pub mod log_progress;
+pub mod log_watch;
 pub mod model;
+pub mod s3;
 pub mod scorer;
 pub mod task;
 pub mod tool;
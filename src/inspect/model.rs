@@ -6,12 +6,15 @@ use pyo3::{
     Borrowed, FromPyObject, PyAny, PyErr, PyResult, exceptions::PyTypeError, types::PyAnyMethods,
 };
 
+use serde::{Deserialize, Deserializer};
+
 use crate::{
     inspect::{Metadata, json::JsonValue},
     py::Any,
 };
 
-#[derive(Debug)]
+#[derive(Deserialize, Debug)]
+#[serde(tag = "role", rename_all = "lowercase")]
 pub enum ChatMessage {
     System(ChatMessageBase),
     User(ChatMessageUser),
@@ -55,7 +58,7 @@ impl ChatMessage {
     }
 }
 
-#[derive(FromPyObject, Debug)]
+#[derive(FromPyObject, Deserialize, Debug)]
 pub struct ChatMessageBase {
     /// Unique identifer for message.
     pub id: Option<String>,
@@ -70,8 +73,9 @@ pub struct ChatMessageBase {
     pub metadata: Option<Metadata>,
 }
 
-#[derive(Debug)]
+#[derive(Deserialize, Debug)]
 pub struct ChatMessageUser {
+    #[serde(flatten)]
     pub base: ChatMessageBase,
 
     /// ID(s) of tool call(s) this message has the content payload for.
@@ -89,8 +93,9 @@ impl<'a, 'py> FromPyObject<'a, 'py> for ChatMessageUser {
 }
 
 /// Assistant chat message.
-#[derive(Debug)]
+#[derive(Deserialize, Debug)]
 pub struct ChatMessageAssistant {
+    #[serde(flatten)]
     pub base: ChatMessageBase,
 
     /// Tool calls made by the model.
@@ -111,7 +116,7 @@ impl<'a, 'py> FromPyObject<'a, 'py> for ChatMessageAssistant {
     }
 }
 
-#[derive(FromPyObject, Debug)]
+#[derive(FromPyObject, Deserialize, Debug)]
 pub struct ToolCall {
     /// Unique identifier for tool call.
     pub id: String,
@@ -120,6 +125,7 @@ pub struct ToolCall {
     pub function: String,
 
     /// Arguments to function.
+    #[serde(default)]
     pub arguments: HashMap<String, Any>,
 
     /// Error which occurred parsing tool call.
@@ -130,11 +136,12 @@ pub struct ToolCall {
 
     /// Type of tool call.
     #[pyo3(attribute("type"))]
+    #[serde(rename = "type", default)]
     pub call_type: String,
 }
 
 /// Content to include in tool call view.
-#[derive(FromPyObject, Debug)]
+#[derive(FromPyObject, Deserialize, Debug)]
 pub struct ToolCallContent {
     /// Optional (plain text) title for tool call content.
     pub title: Option<String>,
@@ -148,8 +155,9 @@ pub struct ToolCallContent {
     pub content: String,
 }
 
-#[derive(Debug)]
+#[derive(Deserialize, Debug)]
 pub struct ChatMessageTool {
+    #[serde(flatten)]
     pub base: ChatMessageBase,
 
     /// ID of tool call.
@@ -175,7 +183,7 @@ impl<'a, 'py> FromPyObject<'a, 'py> for ChatMessageTool {
 }
 
 /// Error raised by a tool call.
-#[derive(FromPyObject, Debug)]
+#[derive(FromPyObject, Deserialize, Debug)]
 pub struct ToolCallError {
     /// Error type.
     ///
@@ -194,13 +202,15 @@ pub struct ToolCallError {
     ///     "output_limit",
     /// ]
     #[pyo3(attribute("type"))]
+    #[serde(rename = "type")]
     pub error_type: String,
 
     /// Error message.
     pub message: String,
 }
 
-#[derive(FromPyObject, Debug)]
+#[derive(FromPyObject, Deserialize, Debug)]
+#[serde(untagged)]
 pub enum ChatMessageContent {
     String(String),
     ContentList(Vec<Content>),
@@ -213,9 +223,28 @@ impl ChatMessageContent {
             Self::ContentList(l) => l.is_empty(),
         }
     }
+
+    /// Returns text content.
+    ///
+    /// Text blocks in a content list are separated by blank lines.
+    /// Non-text content is omitted.
+    pub fn text(&self) -> String {
+        match self {
+            Self::String(s) => s.clone(),
+            Self::ContentList(l) => l
+                .iter()
+                .filter_map(|content| match content {
+                    Content::Text(v) => Some(v.text.as_str()),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+                .join("\n\n"),
+        }
+    }
 }
 
-#[derive(Debug)]
+#[derive(Deserialize, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
 pub enum Content {
     Text(ContentText),
     Reasoning(ContentReasoning),
@@ -261,7 +290,7 @@ impl Content {
     }
 }
 
-#[derive(FromPyObject, Debug)]
+#[derive(FromPyObject, Deserialize, Debug)]
 pub struct ContentText {
     /// Text content.
     pub text: String,
@@ -273,7 +302,7 @@ pub struct ContentText {
     // """Citations supporting the text block."""
 }
 
-#[derive(FromPyObject, Debug)]
+#[derive(FromPyObject, Deserialize, Debug)]
 pub struct ContentReasoning {
     /// Reasoning content.
     pub reasoning: String,
@@ -287,19 +316,21 @@ pub struct ContentReasoning {
 
     /// Indicates that the explicit content of this reasoning block has
     /// been redacted.
+    #[serde(default)]
     pub redacted: bool,
 }
 
-#[derive(FromPyObject, Debug)]
+#[derive(FromPyObject, Deserialize, Debug)]
 pub struct ContentImage {
     /// Either a URL of the image or the base64 encoded image data.
     pub image: String,
 
     /// Specifies the detail level of the image.
+    #[serde(default)]
     pub detail: String,
 }
 
-#[derive(FromPyObject, Debug)]
+#[derive(FromPyObject, Deserialize, Debug)]
 pub struct ContentAudio {
     /// Audio file path or base64 encoded data URL.
     pub audio: String,
@@ -308,7 +339,7 @@ pub struct ContentAudio {
     pub format: String,
 }
 
-#[derive(FromPyObject, Debug)]
+#[derive(FromPyObject, Deserialize, Debug)]
 pub struct ContentVideo {
     /// Video file path or base64 encoded data URL.
     pub video: String,
@@ -317,13 +348,13 @@ pub struct ContentVideo {
     pub format: String,
 }
 
-#[derive(FromPyObject, Debug)]
+#[derive(FromPyObject, Deserialize, Debug)]
 pub struct ContentData {
     /// Model provider specific payload - required for internal content.
     pub data: HashMap<String, JsonValue>,
 }
 
-#[derive(FromPyObject, Debug)]
+#[derive(FromPyObject, Deserialize, Debug)]
 pub struct ContentToolUse {
     /// The type of the tool call.
     pub tool_type: String,
@@ -347,15 +378,17 @@ pub struct ContentToolUse {
     pub error: Option<String>,
 }
 
-#[derive(FromPyObject, Debug)]
+#[derive(FromPyObject, Deserialize, Debug)]
 pub struct ContentDocument {
     /// Document file path or base64 encoded data URL.
     pub document: String,
 
     /// Document filename (automatically determined from 'document' if not specified).
+    #[serde(default)]
     pub filename: String,
 
     /// Document mime type (automatically determined from 'document' if not specified).
+    #[serde(default)]
     pub mime_type: String,
 }
 
@@ -383,25 +416,68 @@ pub struct ModelOutput {
     pub error: Option<String>,
 }
 
-#[derive(FromPyObject, Debug)]
+/// Deserializes model output.
+///
+/// Inspect derives completion from the first choice and doesn't
+/// always store it. In that case completion is read from the choice
+/// message.
+impl<'de> Deserialize<'de> for ModelOutput {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        struct Fields {
+            #[serde(default)]
+            model: String,
+            #[serde(default)]
+            choices: Vec<ChatCompletionChoice>,
+            completion: Option<String>,
+            usage: Option<ModelUsage>,
+            time: Option<f64>,
+            metadata: Option<Metadata>,
+            error: Option<String>,
+        }
+        let fields = Fields::deserialize(deserializer)?;
+        let completion = fields.completion.unwrap_or_else(|| {
+            fields
+                .choices
+                .first()
+                .map(|choice| choice.message.base.content.text())
+                .unwrap_or_default()
+        });
+        Ok(Self {
+            model: fields.model,
+            choices: fields.choices,
+            completion,
+            usage: fields.usage,
+            time: fields.time,
+            metadata: fields.metadata,
+            error: fields.error,
+        })
+    }
+}
+
+#[derive(FromPyObject, Deserialize, Debug)]
 pub struct ChatCompletionChoice {
     /// Assistant message.
     pub message: ChatMessageAssistant,
 
     /// Reason that the model stopped generating.
+    #[serde(default)]
     pub stop_reason: StopReason,
     //
     // logprobs: Logprobs | None = Field(default=None)
     // """Logprobs."""
 }
 
-#[derive(Debug)]
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "snake_case")]
 pub enum StopReason {
     Stop,
     MaxTokens,
     ModelLength,
     ToolCalls,
+    #[serde(alias = "ContentFilter")]
     ContentFilter,
+    #[default]
     Unknown,
 }
 
@@ -436,7 +512,8 @@ impl Display for StopReason {
     }
 }
 
-#[derive(FromPyObject, Debug)]
+#[derive(FromPyObject, Deserialize, Debug, Default)]
+#[serde(default)]
 pub struct ModelUsage {
     // Total input tokens used.
     pub input_tokens: usize,
@@ -3,11 +3,12 @@
 use std::{collections::HashMap, fmt::Display};
 
 use pyo3::FromPyObject;
+use serde::{Deserialize, Serialize};
 
 use crate::inspect::Metadata;
 
 /// Score generated by a scorer.
-#[derive(FromPyObject, Debug)]
+#[derive(FromPyObject, Deserialize, Debug)]
 pub struct Score {
     /// The score value.
     pub value: Value,
@@ -22,14 +23,16 @@ pub struct Score {
     pub metadata: Option<Metadata>,
 }
 
-#[derive(FromPyObject, Clone, Debug)]
+#[derive(FromPyObject, Serialize, Deserialize, Clone, Debug)]
+#[serde(untagged)]
 pub enum Value {
     Scalar(Scalar),
     Sequence(Vec<Scalar>),
     Map(HashMap<String, Scalar>),
 }
 
-#[derive(FromPyObject, Clone, Debug)]
+#[derive(FromPyObject, Serialize, Deserialize, Clone, Debug)]
+#[serde(untagged)]
 pub enum Scalar {
     String(String),
     Int(i64),
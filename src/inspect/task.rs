@@ -1,4 +1,8 @@
-use pyo3::{FromPyObject, Python, types::PyAnyMethods};
+use pyo3::{
+    FromPyObject, Py, PyAny, PyResult, Python,
+    call::PyCallArgs,
+    types::{PyAnyMethods, PyModule},
+};
 
 use crate::{
     error::Error,
@@ -35,6 +39,7 @@ pub fn list_tasks<'py>(py: Python<'py>, path: &Vec<String>) -> Result<Vec<TaskIn
         .unwrap())
 }
 
+/// Evaluates tasks and returns the locations of the eval logs.
 #[allow(clippy::too_many_arguments)]
 pub fn eval_tasks<'py>(
     py: Python<'py>,
@@ -49,8 +54,8 @@ pub fn eval_tasks<'py>(
     epochs: Option<usize>,
     max_tasks: usize,
     log_dir: String,
-) -> Result<()> {
-    py_call(
+) -> Result<Vec<String>> {
+    let logs = py_call(
         py,
         "gage_inspect.task",
         "eval_tasks",
@@ -59,7 +64,10 @@ pub fn eval_tasks<'py>(
             log_dir,
         ),
     )?;
-    Ok(())
+    Ok(logs
+        .try_iter()?
+        .map(|log| log?.getattr("location")?.extract::<String>())
+        .collect::<PyResult<_>>()?)
 }
 
 mod gage_inspect {
@@ -97,12 +105,68 @@ pub fn run_task<'py>(
     log_dir: Option<String>,
     tags: Vec<String>,
 ) -> Result<TaskResult> {
-    let result = py_call(
+    call_run_task(py, (task, input, task_args, model, target, log_dir, tags))
+}
+
+/// Task loaded by Inspect.
+///
+/// Used to run a task more than once without loading it for each run
+/// (see `run_loaded_task`).
+pub struct LoadedTask {
+    task: Py<PyAny>,
+
+    /// Task arguments used to load the task.
+    pub task_args: Vec<String>,
+}
+
+/// Loads a task with task arguments.
+pub fn load_task<'py>(py: Python<'py>, task: String, task_args: Vec<String>) -> Result<LoadedTask> {
+    if !PyModule::import(py, "gage_inspect.task")?.hasattr("load_task")? {
+        return Err(Error::general(
+            "Installed gage_inspect doesn't support loading tasks\n\
+            \n\
+            Upgrade gage-inspect to run tasks interactively.",
+        ));
+    }
+    let task = py_call(
         py,
         "gage_inspect.task",
-        "run_task",
-        (task, input, task_args, model, target, log_dir, tags),
-    );
+        "load_task",
+        (task, task_args.clone()),
+    )?;
+    Ok(LoadedTask {
+        task: task.unbind(),
+        task_args,
+    })
+}
+
+/// Runs a task loaded using `load_task`.
+pub fn run_loaded_task<'py>(
+    py: Python<'py>,
+    task: &LoadedTask,
+    input: String,
+    model: Option<String>,
+    target: Option<String>,
+    log_dir: Option<String>,
+    tags: Vec<String>,
+) -> Result<TaskResult> {
+    // Task args are applied when the task is loaded
+    call_run_task(
+        py,
+        (
+            task.task.bind(py),
+            input,
+            Vec::<String>::new(),
+            model,
+            target,
+            log_dir,
+            tags,
+        ),
+    )
+}
+
+fn call_run_task<'py>(py: Python<'py>, args: impl PyCallArgs<'py>) -> Result<TaskResult> {
+    let result = py_call(py, "gage_inspect.task", "run_task", args);
     log::debug!("{result:?}");
     match result {
         Ok(result) => Ok(result.extract()?),
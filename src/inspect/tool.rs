@@ -3,16 +3,18 @@
 use std::collections::HashMap;
 
 use pyo3::FromPyObject;
+use serde::Deserialize;
 
 use crate::{inspect::json::JSONSchema, py::Any};
 
 /// Specification of a tool (JSON Schema compatible)
-#[derive(FromPyObject, Debug)]
+#[derive(FromPyObject, Deserialize, Debug)]
 pub struct ToolInfo {
     /// Name of tool.
     pub name: String,
 
     /// Short description of tool.
+    #[serde(default)]
     pub description: String,
 
     /// JSON Schema of tool parameters object.
@@ -23,16 +25,19 @@ pub struct ToolInfo {
 }
 
 /// Description of tool parameters object in JSON Schema format.
-#[derive(FromPyObject, Debug)]
+#[derive(FromPyObject, Deserialize, Debug)]
 pub struct ToolParams {
     /// Tool function parameters.
+    #[serde(default)]
     pub properties: HashMap<String, ToolParam>,
 
     /// List of required fields.
+    #[serde(default)]
     pub required: Vec<String>,
 
     /// Are additional object properties allowed? (always `False`)
     #[pyo3(attribute("additionalProperties"))]
+    #[serde(rename = "additionalProperties", default)]
     pub additional_properties: bool,
 }
 
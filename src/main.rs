@@ -1,6 +1,6 @@
 use std::{io, path::PathBuf};
 
-use clap::{Parser, Subcommand, command};
+use clap::{Parser, Subcommand};
 
 mod commands;
 mod config;
@@ -10,6 +10,7 @@ mod env;
 mod error;
 mod inspect;
 mod logger;
+mod output;
 mod profile;
 mod py;
 mod result;
@@ -51,15 +52,13 @@ enum Cmd {
     Delete(cmd::log::delete::Args),
 
     /// Manage task endpoints
-    #[command(hide = true)] // TODO
     Endpoint(cmd::endpoint::Args),
 
     /// Run an evaluation
     Eval(cmd::task::eval::Args),
 
     /// Initialize a project
-    #[command(hide = true)] // TODO
-    Init,
+    Init(cmd::init::Args),
 
     /// List eval logs
     List(cmd::log::list::Args),
@@ -80,7 +79,6 @@ enum Cmd {
     Status(cmd::status::Args),
 
     /// Start a task endpoint
-    #[command(hide = true)]
     Serve(cmd::endpoint::start::Args),
 
     /// Manage tasks
@@ -104,18 +102,18 @@ fn main() -> Exit {
     // Dispatch command
     handle_result(match args.cmd {
         Cmd::Dataset(args) => cmd::dataset::main(args),
-        Cmd::Endpoint(args) => cmd::endpoint::main(args),
-        Cmd::Eval(args) => cmd::task::eval::main(args),
-        Cmd::Init => cmd::init::main(),
+        Cmd::Endpoint(args) => cmd::endpoint::main(args, &config),
+        Cmd::Eval(args) => cmd::task::eval::main(args, &config),
+        Cmd::Init(args) => cmd::init::main(args),
         Cmd::List(args) => cmd::log::list::main(args),
-        Cmd::Log(args) => cmd::log::main(args),
+        Cmd::Log(args) => cmd::log::main(args, &config),
         Cmd::Profile(args) => cmd::profile::main(args, &config),
         Cmd::Review(args) => cmd::log::review::main(args),
         Cmd::Delete(args) => cmd::log::delete::main(args),
         Cmd::Status(args) => cmd::status::main(args, &config),
         Cmd::Run(args) => cmd::task::run::main(args),
-        Cmd::Serve(args) => cmd::endpoint::start::main(args),
-        Cmd::Task(args) => cmd::task::main(args),
+        Cmd::Serve(args) => cmd::endpoint::start::serve_main(args, &config),
+        Cmd::Task(args) => cmd::task::main(args, &config),
     })
 }
 
@@ -0,0 +1,142 @@
+use std::io::{self, Write};
+
+use clap::{Args as ArgsTrait, ValueEnum};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{error::Error, result::Result};
+
+/// Output format used by list and info commands.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Default)]
+pub enum Format {
+    #[default]
+    Table,
+    Json,
+    Csv,
+}
+
+/// Format options for list and info commands.
+///
+/// Use `#[command(flatten)]` to add to command args.
+#[derive(ArgsTrait, Debug)]
+pub struct FormatArgs {
+    /// Show output as JSON (same as '--format json')
+    #[arg(long)]
+    json: bool,
+
+    /// Output format
+    #[arg(long, value_enum, value_name = "FORMAT", conflicts_with = "json")]
+    format: Option<Format>,
+}
+
+impl FormatArgs {
+    pub fn format(&self) -> Format {
+        if self.json {
+            Format::Json
+        } else {
+            self.format.unwrap_or_default()
+        }
+    }
+
+    pub fn is_table(&self) -> bool {
+        self.format() == Format::Table
+    }
+}
+
+/// Prints a list of records as JSON or CSV.
+///
+/// JSON is printed as an array of objects. CSV is printed with a
+/// header row using the record field names. Nested values (lists and
+/// objects) are printed as JSON in CSV cells.
+pub fn print_records<T: Serialize>(format: Format, records: &[T]) -> Result<()> {
+    let values = records
+        .iter()
+        .map(to_value)
+        .collect::<Result<Vec<_>>>()?;
+    match format {
+        Format::Json => print_json(&Value::Array(values)),
+        Format::Csv => print_csv(&values),
+        Format::Table => panic!("table format must be handled by caller"),
+    }
+}
+
+/// Prints a single record as JSON or CSV.
+///
+/// JSON is printed as an object. CSV is printed as a header row and a
+/// single value row.
+pub fn print_record<T: Serialize>(format: Format, record: &T) -> Result<()> {
+    let value = to_value(record)?;
+    match format {
+        Format::Json => print_json(&value),
+        Format::Csv => print_csv(&[value]),
+        Format::Table => panic!("table format must be handled by caller"),
+    }
+}
+
+fn to_value<T: Serialize>(record: &T) -> Result<Value> {
+    serde_json::to_value(record).map_err(|e| Error::general(e.to_string()))
+}
+
+fn print_json(value: &Value) -> Result<()> {
+    let json = serde_json::to_string_pretty(value).map_err(|e| Error::general(e.to_string()))?;
+    ignore_broken_pipe(writeln!(io::stdout().lock(), "{json}"))
+}
+
+fn print_csv(values: &[Value]) -> Result<()> {
+    let mut header = Vec::<&String>::new();
+    for value in values {
+        if let Value::Object(obj) = value {
+            for key in obj.keys() {
+                if !header.contains(&key) {
+                    header.push(key);
+                }
+            }
+        }
+    }
+    let mut w = csv::Writer::from_writer(Vec::new());
+    let csv_err = |e: csv::Error| Error::general(e.to_string());
+    w.write_record(&header).map_err(csv_err)?;
+    for value in values {
+        w.write_record(
+            header
+                .iter()
+                .map(|key| value.get(key.as_str()).map(csv_cell).unwrap_or_default()),
+        )
+        .map_err(csv_err)?;
+    }
+    let buf = w.into_inner().map_err(|e| Error::general(e.to_string()))?;
+    ignore_broken_pipe(io::stdout().lock().write_all(&buf))
+}
+
+/// Treats a broken pipe (e.g. output piped to 'head') as success.
+pub fn ignore_broken_pipe(result: io::Result<()>) -> Result<()> {
+    match result {
+        Err(e) if e.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+        result => Ok(result?),
+    }
+}
+
+/// Returns a CSV cell value for a JSON value.
+pub fn csv_cell(value: &Value) -> String {
+    match value {
+        Value::Null => "".into(),
+        Value::String(s) => s.clone(),
+        _ => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::csv_cell;
+
+    #[test]
+    fn test_csv_cell() {
+        assert_eq!(csv_cell(&json!(null)), "");
+        assert_eq!(csv_cell(&json!("a, b")), "a, b");
+        assert_eq!(csv_cell(&json!(1.5)), "1.5");
+        assert_eq!(csv_cell(&json!(true)), "true");
+        assert_eq!(csv_cell(&json!(["a", "b"])), r#"["a","b"]"#);
+    }
+}
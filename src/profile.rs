@@ -33,18 +33,38 @@ fn apply_profile_impl(config: &Config, with_secrets: bool) -> Result<()> {
         if let Some(log_dir) = profile.log_dir.as_deref()
             && std::env::var("INSPECT_LOG_DIR").is_err()
         {
-            let log_dir = config
-                .path
-                .parent()
-                .unwrap()
-                .join(log_dir)
-                .to_str()
-                .unwrap()
-                .to_string();
+            // Log dirs with a scheme (e.g. s3://) are used as is
+            let log_dir = if log_dir.contains("://") {
+                log_dir.to_string()
+            } else {
+                config
+                    .path
+                    .parent()
+                    .unwrap()
+                    .join(log_dir)
+                    .to_str()
+                    .unwrap()
+                    .to_string()
+            };
             log::debug!("INSPECT_LOG_DIR={log_dir}");
             unsafe { std::env::set_var("INSPECT_LOG_DIR", log_dir) };
         }
 
+        // S3 - applied as AWS env vars so they're also used by Inspect
+        if let Some(s3) = profile.s3.as_ref() {
+            for (name, val) in [
+                ("AWS_ENDPOINT_URL_S3", s3.endpoint_url.as_deref()),
+                ("AWS_REGION", s3.region.as_deref()),
+            ] {
+                if let Some(val) = val
+                    && std::env::var(name).is_err()
+                {
+                    log::debug!("{name}={val}");
+                    unsafe { std::env::set_var(name, val) };
+                }
+            }
+        }
+
         // Env
         for (name, val) in &profile.env {
             if std::env::var(name).is_err() {
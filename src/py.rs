@@ -6,6 +6,7 @@ use std::{
 
 use chrono::{DateTime, FixedOffset, Local, ParseResult, Utc};
 use chrono_humanize::HumanTime;
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
 use pyo3::{
     Borrowed, Bound, FromPyObject, PyAny, PyErr, PyResult, Python,
     call::PyCallArgs,
@@ -22,6 +23,13 @@ pub enum Any {
     Other(String),
 }
 
+/// Default corresponds to Python `None`.
+impl Default for Any {
+    fn default() -> Self {
+        Self::Other("None".into())
+    }
+}
+
 impl<'a, 'py> FromPyObject<'a, 'py> for Any {
     type Error = PyErr;
     fn extract(ob: Borrowed<'a, 'py, PyAny>) -> PyResult<Self> {
@@ -37,6 +45,33 @@ impl<'a, 'py> FromPyObject<'a, 'py> for Any {
     }
 }
 
+/// Deserializes JSON values using the same variants as Python
+/// extraction. Values that aren't strings or numbers are stored as JSON.
+impl<'de> Deserialize<'de> for Any {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(match serde_json::Value::deserialize(deserializer)? {
+            serde_json::Value::String(s) => Self::Str(s),
+            serde_json::Value::Number(n) => match n.as_i64() {
+                Some(i) => Self::Int(i),
+                None => Self::Float(n.as_f64().unwrap_or(f64::NAN)),
+            },
+            serde_json::Value::Null => Self::default(),
+            other => Self::Other(other.to_string()),
+        })
+    }
+}
+
+impl From<&Any> for serde_json::Value {
+    fn from(value: &Any) -> Self {
+        match value {
+            Any::Str(s) => s.as_str().into(),
+            Any::Int(i) => (*i).into(),
+            Any::Float(n) => (*n).into(),
+            Any::Other(o) => o.as_str().into(),
+        }
+    }
+}
+
 impl Display for Any {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -70,9 +105,37 @@ impl EpochMillis {
         self.to_human_since(&Utc::now())
     }
 
+    pub fn as_datetime(&self) -> &DateTime<Utc> {
+        &self.0
+    }
+
+    pub fn to_rfc3339(&self) -> String {
+        self.0.to_rfc3339()
+    }
+
     pub fn to_iso_8601_local(&self) -> String {
         self.0.with_timezone(Local::now().offset()).to_rfc3339()
     }
+
+    /// Extracts an optional time, where empty strings are None.
+    pub fn extract_opt(ob: &Bound<'_, PyAny>) -> PyResult<Option<Self>> {
+        if ob.is_none() || ob.extract::<String>().is_ok_and(|s| s.is_empty()) {
+            Ok(None)
+        } else {
+            Ok(Some(ob.extract()?))
+        }
+    }
+
+    /// Deserializes an optional time, where empty strings are None.
+    pub fn deserialize_opt<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Self>, D::Error> {
+        match serde_json::Value::deserialize(deserializer)? {
+            serde_json::Value::Null => Ok(None),
+            serde_json::Value::String(s) if s.is_empty() => Ok(None),
+            value => Self::deserialize(value).map(Some).map_err(de::Error::custom),
+        }
+    }
 }
 
 impl FromPyObject<'_, '_> for EpochMillis {
@@ -99,6 +162,30 @@ impl FromPyObject<'_, '_> for EpochMillis {
     }
 }
 
+/// Deserializes epoch millis (number) or ISO 8601 (string) values.
+impl<'de> Deserialize<'de> for EpochMillis {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match serde_json::Value::deserialize(deserializer)? {
+            serde_json::Value::Number(n) => n
+                .as_f64()
+                .map(|ms| Self::from_epoch_millis(ms as i64))
+                .ok_or_else(|| de::Error::custom(format!("invalid epoch millis {n}"))),
+            serde_json::Value::String(iso) => Self::from_python_iso(&iso)
+                .map_err(|_| de::Error::custom(format!("invalid datetime ISO string '{iso}'"))),
+            other => Err(de::Error::custom(format!(
+                "{other} cannot be converted to EpochMillis"
+            ))),
+        }
+    }
+}
+
+/// Serializes as an RFC 3339 string.
+impl Serialize for EpochMillis {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_rfc3339())
+    }
+}
+
 static INIT_SYNC: std::sync::Once = std::sync::Once::new();
 
 pub fn init() {
@@ -15,7 +15,10 @@ impl Termination for Exit {
             Self::Ok => ().report(),
             Self::Err(err) => {
                 eprint!("{err}");
-                ExitCode::FAILURE
+                match err {
+                    Error::Code(code, _) => ExitCode::from(code),
+                    _ => ExitCode::FAILURE,
+                }
             }
         }
     }
@@ -56,7 +56,7 @@ use cursive::{Cursive, views::LayerPosition};
 use crate::{
     logger,
     result::Result,
-    review::{app::view::AppView, theme},
+    review::{app::view::AppView, screens::logs::LogsScreen, theme},
 };
 
 mod help;
@@ -80,6 +80,9 @@ pub fn run(log_dir: &Path, dev_mode: bool) -> Result<()> {
         // App view
         siv.add_fullscreen_layer(AppView::new(log_dir, dev_mode));
 
+        // Refresh logs as they change
+        LogsScreen::watch(&mut siv, log_dir);
+
         // Run app - blocks until cursive quit
         siv.run();
     });
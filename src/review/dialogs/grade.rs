@@ -0,0 +1,94 @@
+use std::sync::Arc;
+
+use cursive::{
+    Cursive, View,
+    align::HAlign,
+    event::{Event, EventResult, Key},
+    theme::Effect,
+    utils::markup::StyledString,
+    view::{Nameable, Resizable, ViewWrapper},
+    views::{EditView, LinearLayout, TextView},
+    wrap_impl,
+};
+
+use crate::{cursive::views::Dialog, handle_wrapped_dialog_event};
+
+const REASON_NAME: &str = "grade_reason";
+
+type SaveCb = Arc<dyn Fn(&mut Cursive, &str) + Send + Sync>;
+
+pub type RemoveCb = Box<dyn Fn(&mut Cursive) + Send + Sync>;
+
+pub struct GradeDialog {
+    inner: Dialog,
+}
+
+impl GradeDialog {
+    /// Returns a dialog to enter an optional reason for a grade.
+    ///
+    /// If `on_remove` is specified, the dialog shows a button to
+    /// remove an existing grade.
+    pub fn new<S, F>(title: S, reason: &str, on_save: F, on_remove: Option<RemoveCb>) -> Self
+    where
+        S: Into<String>,
+        F: Fn(&mut Cursive, &str) + Send + Sync + 'static,
+    {
+        let on_save: SaveCb = Arc::new(on_save);
+        let submit_cb = on_save.clone();
+        let mut inner = Dialog::new()
+            .title(title.into())
+            .content(
+                LinearLayout::vertical()
+                    .child(TextView::new(StyledString::styled(
+                        "Reason (optional)",
+                        Effect::Dim,
+                    )))
+                    .child(
+                        EditView::new()
+                            .content(reason)
+                            .on_submit(move |siv, reason| Self::save(siv, &submit_cb, reason))
+                            .with_name(REASON_NAME)
+                            .min_width(60),
+                    ),
+            )
+            .padding_lrtb(2, 2, 1, 0)
+            .h_align(HAlign::Center)
+            .button("Save", move |siv| {
+                let reason = siv
+                    .call_on_name(REASON_NAME, |view: &mut EditView| {
+                        view.get_content().to_string()
+                    })
+                    .unwrap_or_default();
+                Self::save(siv, &on_save, &reason)
+            });
+        if let Some(on_remove) = on_remove {
+            inner = inner.button("Remove grade", move |siv| {
+                siv.pop_layer();
+                on_remove(siv);
+            });
+        }
+        Self {
+            inner: inner.dismiss_button("Cancel"),
+        }
+    }
+
+    fn save(siv: &mut Cursive, on_save: &SaveCb, reason: &str) {
+        siv.pop_layer();
+        on_save(siv, reason);
+    }
+
+    fn close(siv: &mut Cursive) {
+        siv.pop_layer();
+    }
+}
+
+impl ViewWrapper for GradeDialog {
+    wrap_impl!(self.inner: Dialog);
+
+    fn wrap_on_event(&mut self, event: Event) -> EventResult {
+        match event {
+            Event::Key(Key::Esc) => EventResult::with_cb(Self::close),
+            _ => handle_wrapped_dialog_event!(self, event),
+        }
+    }
+}
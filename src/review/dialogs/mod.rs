@@ -1,5 +1,7 @@
 pub mod confirm;
+pub mod grade;
 pub mod help;
+pub mod note;
 pub mod notify;
 pub mod status;
 
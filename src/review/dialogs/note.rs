@@ -0,0 +1,85 @@
+use std::sync::Arc;
+
+use cursive::{
+    Cursive, View,
+    align::HAlign,
+    event::{Event, EventResult, Key},
+    theme::Effect,
+    utils::markup::StyledString,
+    view::{Nameable, Resizable, ViewWrapper},
+    views::{LinearLayout, TextArea, TextView},
+    wrap_impl,
+};
+
+use crate::{cursive::views::Dialog, handle_wrapped_dialog_event};
+
+const TEXT_NAME: &str = "note_text";
+
+type SaveCb = Arc<dyn Fn(&mut Cursive, &str) + Send + Sync>;
+
+pub struct NoteDialog {
+    on_save: SaveCb,
+    inner: Dialog,
+}
+
+impl NoteDialog {
+    pub fn new<S, F>(title: S, text: &str, on_save: F) -> Self
+    where
+        S: Into<String>,
+        F: Fn(&mut Cursive, &str) + Send + Sync + 'static,
+    {
+        let on_save: SaveCb = Arc::new(on_save);
+        let save_cb = on_save.clone();
+        let inner = Dialog::new()
+            .title(title.into())
+            .content(
+                LinearLayout::vertical()
+                    .child(
+                        TextArea::new()
+                            .content(text)
+                            .with_name(TEXT_NAME)
+                            .min_size((60, 8)),
+                    )
+                    .child(TextView::new(StyledString::styled(
+                        "Ctrl-D to save, Esc to cancel. Save an empty note to remove it.",
+                        Effect::Dim,
+                    ))),
+            )
+            .padding_lrtb(2, 2, 1, 0)
+            .h_align(HAlign::Center)
+            .button("Save", move |siv| Self::save(siv, &save_cb))
+            .dismiss_button("Cancel");
+
+        Self { on_save, inner }
+    }
+
+    fn save(siv: &mut Cursive, on_save: &SaveCb) {
+        let text = siv
+            .call_on_name(TEXT_NAME, |view: &mut TextArea| {
+                view.get_content().to_string()
+            })
+            .unwrap_or_default();
+        siv.pop_layer();
+        on_save(siv, &text);
+    }
+
+    fn close(siv: &mut Cursive) {
+        siv.pop_layer();
+    }
+}
+
+impl ViewWrapper for NoteDialog {
+    wrap_impl!(self.inner: Dialog);
+
+    fn wrap_on_event(&mut self, event: Event) -> EventResult {
+        match event {
+            // Ctrl-D saves the note
+            Event::CtrlChar('d') => {
+                let on_save = self.on_save.clone();
+                EventResult::with_cb(move |siv| Self::save(siv, &on_save))
+            }
+            Event::Key(Key::Esc) => EventResult::with_cb(Self::close),
+            _ => handle_wrapped_dialog_event!(self, event),
+        }
+    }
+}
@@ -8,7 +8,10 @@ use cursive::{
 };
 use itertools::{Itertools, intersperse};
 
-use crate::inspect::log::{EvalLog, EvalStatus};
+use crate::inspect::{
+    log::{EvalLog, EvalStatus},
+    log_index::LogSummary,
+};
 
 pub trait StyledEvalLog {
     fn styled_status(&self) -> StyledString;
@@ -20,14 +23,7 @@ pub trait StyledEvalLog {
 
 impl StyledEvalLog for EvalLog {
     fn styled_status(&self) -> StyledString {
-        StyledString::styled(
-            self.status.to_string(),
-            match self.status {
-                EvalStatus::Success => Style::from(Effect::Simple),
-                EvalStatus::Error => Style::from(BaseColor::Red.light()).combine(Effect::Dim),
-                _ => Style::from(Effect::Dim),
-            },
-        )
+        styled_status(self.status)
     }
 
     fn styled_task(&self) -> StyledString {
@@ -43,15 +39,50 @@ impl StyledEvalLog for EvalLog {
     }
 
     fn styled_score(&self) -> StyledString {
-        self.results
-            .as_ref()
-            .and_then(|r| r.first_accuracy())
-            .map(|acc| format!("{acc:0.4}"))
-            .unwrap_or_default()
-            .into()
+        styled_score(self.results.as_ref().and_then(|r| r.first_accuracy()))
+    }
+}
+
+impl StyledEvalLog for LogSummary {
+    fn styled_status(&self) -> StyledString {
+        styled_status(self.status)
+    }
+
+    fn styled_task(&self) -> StyledString {
+        StyledString::styled(&self.task, BaseColor::Yellow.light())
+    }
+
+    fn styled_created(&self) -> StyledString {
+        StyledString::styled(self.created.to_human(), Effect::Dim)
+    }
+
+    fn styled_run_type(&self) -> StyledString {
+        StyledString::styled(self.run_type.as_deref().unwrap_or_default(), Effect::Dim)
+    }
+
+    fn styled_score(&self) -> StyledString {
+        styled_score(self.accuracy)
     }
 }
 
+fn styled_status(status: EvalStatus) -> StyledString {
+    StyledString::styled(
+        status.to_string(),
+        match status {
+            EvalStatus::Success => Style::from(Effect::Simple),
+            EvalStatus::Error => Style::from(BaseColor::Red.light()).combine(Effect::Dim),
+            _ => Style::from(Effect::Dim),
+        },
+    )
+}
+
+pub fn styled_score(accuracy: Option<f64>) -> StyledString {
+    accuracy
+        .map(|acc| format!("{acc:0.4}"))
+        .unwrap_or_default()
+        .into()
+}
+
 pub fn dim_ital(s: &str) -> StyledString {
     StyledString::styled(
         s,
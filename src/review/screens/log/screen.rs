@@ -14,19 +14,34 @@ use pyo3::Python;
 
 use crate::{
     cursive::{view::Padding, views::ScreensView},
-    inspect::log::{EvalLog, read_log},
+    inspect::{
+        log::{EvalLog, read_log},
+        log_grades::{GradeKey, HumanGrade, LogGrades},
+        log_notes::{LogNotes, NoteKey},
+    },
     py,
+    result::Result,
     review::{
         App, AppScreen,
         components::{footer::Footer, header::Header},
-        dialogs::help::HelpDialog,
-        screens::log::{advanced::AdvancedView, simplified::SampleView},
+        dialogs::{
+            grade::{GradeDialog, RemoveCb},
+            help::HelpDialog,
+            note::NoteDialog,
+            notify::NotifyDialog,
+        },
+        screens::log::{
+            advanced::AdvancedView,
+            simplified::{SampleReview, SampleView},
+        },
         theme,
     },
 };
 
 pub struct LogScreen {
     log: Option<Arc<EvalLog>>,
+    notes: Option<LogNotes>,
+    grades: Option<LogGrades>,
     active_sample: Option<usize>,
     sample_view: ScreenId,
     advanced_view: ScreenId,
@@ -65,6 +80,8 @@ impl LogScreen {
 
         Self {
             log: None,
+            notes: None,
+            grades: None,
             active_sample: None,
             sample_view,
             advanced_view,
@@ -86,7 +103,23 @@ impl LogScreen {
                 } else {
                     None
                 };
-                self.sample_view().set_log_sample(&log, active_sample, true);
+                self.notes = match LogNotes::open(location) {
+                    Ok(notes) => Some(notes),
+                    Err(err) => {
+                        log::error!("Reading notes: {err:?}");
+                        None
+                    }
+                };
+                self.grades = match LogGrades::open(location) {
+                    Ok(grades) => Some(grades),
+                    Err(err) => {
+                        log::error!("Reading grades: {err:?}");
+                        None
+                    }
+                };
+                let review = self.sample_review(&log, active_sample);
+                self.sample_view()
+                    .set_log_sample(&log, active_sample, true, &review);
                 self.advanced_view().set_log_sample(&log, active_sample);
                 self.active_sample = active_sample;
                 self.log = Some(log);
@@ -136,7 +169,9 @@ impl LogScreen {
             let sample_count = log.samples.as_ref().map(|s| s.len()).unwrap_or(0);
             if sample < sample_count {
                 let log = Arc::clone(log);
-                self.sample_view().set_log_sample(&log, Some(sample), false);
+                let review = self.sample_review(&log, Some(sample));
+                self.sample_view()
+                    .set_log_sample(&log, Some(sample), false, &review);
                 self.advanced_view().set_log_sample(&log, Some(sample));
                 self.active_sample = Some(sample);
                 self.refresh_footer();
@@ -146,6 +181,108 @@ impl LogScreen {
         false
     }
 
+    fn sample_review(&self, log: &EvalLog, active_sample: Option<usize>) -> SampleReview {
+        let sample = log
+            .samples
+            .as_ref()
+            .and_then(|samples| active_sample.and_then(|i| samples.get(i)));
+        let notes = self.notes.as_ref();
+        SampleReview {
+            log_note: notes.and_then(|notes| notes.get(&NoteKey::log())).cloned(),
+            sample_note: notes
+                .zip(sample)
+                .and_then(|(notes, sample)| notes.get(&NoteKey::sample(sample)))
+                .cloned(),
+            grade: self
+                .grades
+                .as_ref()
+                .zip(sample)
+                .and_then(|(grades, sample)| grades.get(&GradeKey::new(sample)))
+                .cloned(),
+        }
+    }
+
+    /// Refreshes the sample view for changes to notes or grades.
+    fn refresh_sample_review(&mut self) {
+        if let Some(log) = self.log.as_ref() {
+            let log = Arc::clone(log);
+            let active_sample = self.active_sample;
+            let review = self.sample_review(&log, active_sample);
+            self.sample_view()
+                .set_log_sample(&log, active_sample, false, &review);
+        }
+    }
+
+    /// Returns the key and current text for a note.
+    ///
+    /// If `sample` is true, returns the note for the active sample,
+    /// otherwise returns the note for the log.
+    fn note(&self, sample: bool) -> Option<(NoteKey, String)> {
+        let log = self.log.as_ref()?;
+        let key = if sample {
+            let sample = log.samples.as_ref()?.get(self.active_sample?)?;
+            NoteKey::sample(sample)
+        } else {
+            NoteKey::log()
+        };
+        let text = self
+            .notes
+            .as_ref()
+            .and_then(|notes| notes.get(&key))
+            .map(|note| note.text.clone())
+            .unwrap_or_default();
+        Some((key, text))
+    }
+
+    fn save_note(&mut self, key: NoteKey, text: &str) -> Result<()> {
+        let notes = match (self.notes.as_mut(), self.log.as_ref()) {
+            (Some(notes), _) => notes,
+            (None, Some(log)) => self.notes.insert(LogNotes::open(&log.location)?),
+            (None, None) => return Ok(()),
+        };
+        notes.set(key, text)?;
+        self.refresh_sample_review();
+        Ok(())
+    }
+
+    /// Returns the grade key, scorer result, and current grade reason
+    /// for the active sample.
+    ///
+    /// Reason is None if the sample isn't graded.
+    fn sample_grade(&self) -> Option<(GradeKey, Option<bool>, Option<String>)> {
+        let log = self.log.as_ref()?;
+        let sample = log.samples.as_ref()?.get(self.active_sample?)?;
+        let key = GradeKey::new(sample);
+        let reason = self
+            .grades
+            .as_ref()
+            .and_then(|grades| grades.get(&key))
+            .map(|grade| grade.reason.clone().unwrap_or_default());
+        Some((key, sample.is_correct(), reason))
+    }
+
+    /// Saves a grade for a sample.
+    ///
+    /// `grade` is the human grade, reason, and scorer result. If
+    /// `grade` is None, removes the sample grade.
+    fn save_grade(
+        &mut self,
+        key: GradeKey,
+        grade: Option<(HumanGrade, String, Option<bool>)>,
+    ) -> Result<()> {
+        let grades = match (self.grades.as_mut(), self.log.as_ref()) {
+            (Some(grades), _) => grades,
+            (None, Some(log)) => self.grades.insert(LogGrades::open(&log.location)?),
+            (None, None) => return Ok(()),
+        };
+        match grade {
+            Some((grade, reason, scorer)) => grades.set(key, grade, &reason, scorer)?,
+            None => grades.remove(&key)?,
+        }
+        self.refresh_sample_review();
+        Ok(())
+    }
+
     fn header(&mut self) -> ViewRef<Header> {
         self.inner.find_name("header").unwrap()
     }
@@ -285,6 +422,17 @@ impl ViewWrapper for LogScreen {
                 EventResult::consumed()
             }
 
+            // Edit sample note
+            Event::Char('n') => self.on_edit_note(true),
+
+            // Edit log note
+            Event::Char('N') => self.on_edit_note(false),
+
+            // Grade sample
+            Event::Char('c') => self.on_grade(HumanGrade::Correct),
+            Event::Char('i') => self.on_grade(HumanGrade::Incorrect),
+            Event::Char('u') => self.on_grade(HumanGrade::Unsure),
+
             // Show debug console
             Event::Char('`') => {
                 EventResult::with_cb_once(|siv| App::push_screen(siv, AppScreen::Console))
@@ -322,6 +470,69 @@ impl ViewWrapper for LogScreen {
 
 // Event handlers
 impl LogScreen {
+    fn on_edit_note(&mut self, sample: bool) -> EventResult {
+        if self.active_screen() != self.sample_view {
+            return EventResult::Ignored;
+        }
+        let Some((key, text)) = self.note(sample) else {
+            return EventResult::consumed();
+        };
+        let title = match key.sample_id.as_ref() {
+            Some(sample_id) => format!("Note - Sample {sample_id}"),
+            None => "Note - Log".into(),
+        };
+        EventResult::with_cb_once(move |siv| {
+            siv.add_layer(NoteDialog::new(title, &text, move |siv, text| {
+                let key = key.clone();
+                let text = text.to_string();
+                let result = App::with_screen(siv, AppScreen::Log, move |screen: &mut Self| {
+                    screen.save_note(key.clone(), &text)
+                });
+                if let Some(Err(err)) = result {
+                    siv.add_layer(NotifyDialog::new(format!("Error saving note: {err}")));
+                }
+            }));
+        })
+    }
+
+    fn on_grade(&mut self, grade: HumanGrade) -> EventResult {
+        if self.active_screen() != self.sample_view {
+            return EventResult::Ignored;
+        }
+        let Some((key, scorer, reason)) = self.sample_grade() else {
+            return EventResult::consumed();
+        };
+        let title = format!("Grade {grade} - Sample {}", key.sample_id);
+        let on_remove = reason.is_some().then(|| {
+            let key = key.clone();
+            Box::new(move |siv: &mut Cursive| Self::save_grade_cb(siv, key.clone(), None))
+                as RemoveCb
+        });
+        EventResult::with_cb_once(move |siv| {
+            siv.add_layer(GradeDialog::new(
+                title,
+                reason.as_deref().unwrap_or_default(),
+                move |siv, reason| {
+                    Self::save_grade_cb(siv, key.clone(), Some((grade, reason.into(), scorer)))
+                },
+                on_remove,
+            ));
+        })
+    }
+
+    fn save_grade_cb(
+        siv: &mut Cursive,
+        key: GradeKey,
+        grade: Option<(HumanGrade, String, Option<bool>)>,
+    ) {
+        let result = App::with_screen(siv, AppScreen::Log, move |screen: &mut Self| {
+            screen.save_grade(key.clone(), grade.clone())
+        });
+        if let Some(Err(err)) = result {
+            siv.add_layer(NotifyDialog::new(format!("Error saving grade: {err}")));
+        }
+    }
+
     fn on_sample_view_help(siv: &mut Cursive) {
         let help = vec![
             (
@@ -331,6 +542,21 @@ impl LogScreen {
                     ("Down, Up", "Highlight next and previous sections".into()),
                 ],
             ),
+            (
+                Some("Grade"),
+                vec![
+                    ("c", "Grade sample correct".into()),
+                    ("i", "Grade sample incorrect".into()),
+                    ("u", "Grade sample unsure".into()),
+                ],
+            ),
+            (
+                Some("Notes"),
+                vec![
+                    ("n", "Edit sample note".into()),
+                    ("N", "Edit log note".into()),
+                ],
+            ),
             (
                 Some("View"),
                 vec![
@@ -21,7 +21,8 @@ use crate::{
         screens::{
             common::dim_ital,
             log::simplified::{
-                ChatMessageView, ErrorsView, InputView, OutputView, SampleScores, TargetView,
+                ChatMessageView, ErrorsView, InputView, OutputView, ReviewView, SampleReview,
+                SampleScores, TargetView,
             },
         },
     },
@@ -52,6 +53,7 @@ impl Body {
         log: &EvalLog,
         active_sample: Option<usize>,
         reset_active_panel: bool,
+        review: &SampleReview,
     ) {
         if reset_active_panel {
             *self.active_panel.lock().unwrap() = BodyPanel::Input;
@@ -167,6 +169,15 @@ impl Body {
             );
         }
 
+        // Review
+        if !review.is_empty() {
+            self.add_child(
+                ToggleView::new("Review", Panel::new(ReviewView::new(review)).pad_b(1))
+                    .expanded(true)
+                    .pad_x(1),
+            );
+        }
+
         // Messages
         let mut messages_layout = PageLayout::new();
         for msg in sample.messages.iter() {
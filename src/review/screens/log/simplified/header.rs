@@ -8,7 +8,10 @@ use cursive::{
 
 use crate::{
     cursive::view::Padding,
-    inspect::log::{EvalLog, EvalSample},
+    inspect::{
+        log::{EvalLog, EvalSample},
+        log_grades::{Grade, HumanGrade},
+    },
     review::{components::value::ValueView, screens::common::StyledEvalLog},
     util::first_line,
 };
@@ -138,12 +141,13 @@ impl SampleHeader {
                     .child(TextView::empty().with_name("sample"))
                     .full_width(),
             )
+            .child(TextView::empty().with_name("grade").pad_r(3))
             .child(TextView::empty().with_name("score").pad_r(1));
 
         Self { inner }
     }
 
-    pub fn set_sample(&mut self, sample: &EvalSample) {
+    pub fn set_sample(&mut self, sample: &EvalSample, grade: Option<&Grade>) {
         self.sample().set_content(StyledString::styled(
             sample.id.to_string(),
             BaseColor::Yellow.dark(),
@@ -159,6 +163,19 @@ impl SampleHeader {
             other => other.into(),
         };
         self.score().set_content(score);
+        self.grade().set_content(match grade {
+            Some(grade) => StyledString::concatenate([
+                StyledString::styled("Human ", Effect::Dim),
+                match grade.grade {
+                    HumanGrade::Correct => StyledString::styled("Correct", BaseColor::Cyan.light()),
+                    HumanGrade::Incorrect => {
+                        StyledString::styled("Incorrect", BaseColor::Red.dark())
+                    }
+                    HumanGrade::Unsure => StyledString::styled("Unsure", BaseColor::Yellow.dark()),
+                },
+            ]),
+            None => StyledString::new(),
+        });
     }
 
     fn sample(&mut self) -> ViewRef<TextView> {
@@ -168,6 +185,10 @@ impl SampleHeader {
     fn score(&mut self) -> ViewRef<TextView> {
         self.find_name("score").unwrap()
     }
+
+    fn grade(&mut self) -> ViewRef<TextView> {
+        self.find_name("grade").unwrap()
+    }
 }
 
 impl ViewWrapper for SampleHeader {
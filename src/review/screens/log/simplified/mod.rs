@@ -4,6 +4,7 @@ mod errors;
 mod header;
 mod input;
 mod output;
+mod review;
 mod scores;
 mod target;
 mod view;
@@ -14,6 +15,7 @@ pub use errors::ErrorsView;
 pub use header::LogHeader;
 pub use input::InputView;
 pub use output::OutputView;
+pub use review::{ReviewView, SampleReview};
 pub use scores::SampleScores;
 pub use target::TargetView;
 pub use view::SampleView;
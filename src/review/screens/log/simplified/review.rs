@@ -0,0 +1,79 @@
+use cursive::{
+    theme::Effect, utils::markup::StyledString, view::ViewWrapper, views::TextView, wrap_impl,
+};
+
+use crate::{
+    inspect::{log_grades::Grade, log_notes::Note},
+    py::EpochMillis,
+};
+
+/// Reviewer notes and grade for a sample.
+#[derive(Default)]
+pub struct SampleReview {
+    pub log_note: Option<Note>,
+    pub sample_note: Option<Note>,
+    pub grade: Option<Grade>,
+}
+
+impl SampleReview {
+    pub fn is_empty(&self) -> bool {
+        self.log_note.is_none() && self.sample_note.is_none() && self.grade.is_none()
+    }
+}
+
+pub struct ReviewView {
+    inner: TextView,
+}
+
+impl ReviewView {
+    pub fn new(review: &SampleReview) -> Self {
+        let mut content = StyledString::new();
+        if let Some(grade) = review.grade.as_ref() {
+            content.append(Self::fmt_caption(
+                &format!("Graded {}", grade.grade),
+                grade.author.as_deref(),
+                &grade.updated,
+            ));
+            if let Some(reason) = grade.reason.as_ref() {
+                content.append_plain("\n");
+                content.append_plain(reason);
+            }
+        }
+        for (label, note) in [
+            ("Sample note", &review.sample_note),
+            ("Log note", &review.log_note),
+        ] {
+            let Some(note) = note else {
+                continue;
+            };
+            if !content.is_empty() {
+                content.append_plain("\n\n");
+            }
+            content.append(Self::fmt_caption(
+                label,
+                note.author.as_deref(),
+                &note.updated,
+            ));
+            content.append_plain("\n");
+            content.append_plain(&note.text);
+        }
+        Self {
+            inner: TextView::new(content),
+        }
+    }
+
+    fn fmt_caption(label: &str, author: Option<&str>, updated: &str) -> StyledString {
+        let updated = chrono::DateTime::parse_from_rfc3339(updated)
+            .map(|dt| EpochMillis::from_epoch_millis(dt.timestamp_millis()).to_human())
+            .unwrap_or_default();
+        let caption = match author {
+            Some(author) => format!("{label} by {author} {updated}"),
+            None => format!("{label} {updated}"),
+        };
+        StyledString::styled(caption.trim_end(), Effect::Dim)
+    }
+}
+
+impl ViewWrapper for ReviewView {
+    wrap_impl!(self.inner: TextView);
+}
@@ -13,7 +13,7 @@ use crate::{
     inspect::log::{EvalLog, EvalStatus},
     review::{
         components::banner::Banner,
-        screens::log::simplified::{Body, LogHeader, header::SampleHeader},
+        screens::log::simplified::{Body, LogHeader, SampleReview, header::SampleHeader},
     },
 };
 
@@ -64,6 +64,7 @@ impl SampleView {
         log: &EvalLog,
         active_sample: Option<usize>,
         reset_active_panel: bool,
+        review: &SampleReview,
     ) {
         // If log status isn't success, show banner qualifying results
         if log.status != EvalStatus::Success {
@@ -85,7 +86,8 @@ impl SampleView {
             .as_ref()
             .and_then(|samples| active_sample.and_then(|i| samples.get(i)))
         {
-            self.sample_header().set_sample(sample);
+            self.sample_header()
+                .set_sample(sample, review.grade.as_ref());
             self.sample_header_container().set_visible(true);
         } else {
             self.sample_header_container().set_visible(false);
@@ -93,7 +95,7 @@ impl SampleView {
 
         // Sample body
         self.body_mut()
-            .set_sample(log, active_sample, reset_active_panel);
+            .set_sample(log, active_sample, reset_active_panel, review);
     }
 
     fn banner_mut(&mut self) -> &mut Banner {
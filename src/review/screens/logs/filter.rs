@@ -24,6 +24,9 @@ use crate::{
     },
 };
 
+/// Keys used to filter by tag, in order of log tags.
+const TAG_KEYS: [&str; 9] = ["1", "2", "3", "4", "5", "6", "7", "8", "9"];
+
 pub struct FilterDialog {
     filter: Filter,
     inner: PaddedView<Dialog>,
@@ -40,9 +43,20 @@ impl FilterDialog {
                     .filter_map(|col| Self::col_key(&filter, col))
                     .collect_vec(),
             ),
+            (
+                None,
+                TAG_KEYS
+                    .iter()
+                    .zip(&filter.tags)
+                    .map(|(key, tag)| (*key, Self::fmt_col_key_desc("Tag", tag)))
+                    .collect_vec(),
+            ),
             (None, vec![("c", "Clear filter".into())]),
             (None, vec![("Esc", "Cancel".into())]),
-        ];
+        ]
+        .into_iter()
+        .filter(|(_, keys)| !keys.is_empty())
+        .collect_vec();
         let inner = Dialog::around(HelpView::new(keys))
             .padding_lrtb(4, 4, 1, 1)
             .title("Filter")
@@ -90,7 +104,21 @@ impl FilterDialog {
     }
 
     fn on_apply(&self, col: Col) -> EventResult {
-        let filter = Filter::from_col(&self.filter, col);
+        Self::apply_filter(Filter::from_col(&self.filter, col))
+    }
+
+    fn on_apply_tag(&self, key: char) -> EventResult {
+        match TAG_KEYS
+            .iter()
+            .position(|k| k.starts_with(key))
+            .and_then(|i| self.filter.tags.get(i))
+        {
+            Some(tag) => Self::apply_filter(Filter::from_tag(tag)),
+            None => EventResult::Ignored,
+        }
+    }
+
+    fn apply_filter(filter: Filter) -> EventResult {
         EventResult::with_cb(move |siv| {
             let filter = filter.clone();
             App::with_screen(siv, AppScreen::Logs, move |screen: &mut LogsScreen| {
@@ -119,6 +147,7 @@ impl ViewWrapper for FilterDialog {
             Event::Char('s') => self.on_apply(Col::Status),
             Event::Char('m') => self.on_apply(Col::Model),
             Event::Char('d') => self.on_apply(Col::Dataset),
+            Event::Char(c @ '1'..='9') => self.on_apply_tag(c),
             Event::Char('c') => self.on_clear(),
             Event::Char('q') | Event::Char('Q') => EventResult::with_cb(Self::close),
             Event::Key(Key::Esc) => EventResult::with_cb(Self::close),
@@ -1,4 +1,4 @@
-use std::path::Path;
+use std::{path::Path, thread};
 
 use cursive::{
     Cursive, View,
@@ -10,16 +10,19 @@ use cursive::{
 };
 use itertools::intersperse;
 
-use crate::review::{
-    app::{App, AppScreen},
-    components::{footer::Footer, header::Header, table::Sort},
-    dialogs::{help::HelpDialog, notify::NotifyDialog, status::StatusDialog},
-    screens::logs::{
-        filter::FilterDialog,
-        sort::SortDialog,
-        view::{Col, Filter, LogsView},
+use crate::{
+    inspect::log_watch::LogDirWatch,
+    review::{
+        app::{App, AppScreen},
+        components::{footer::Footer, header::Header, table::Sort},
+        dialogs::{help::HelpDialog, notify::NotifyDialog, status::StatusDialog},
+        screens::logs::{
+            filter::FilterDialog,
+            sort::SortDialog,
+            view::{Col, Filter, LogsView},
+        },
+        theme,
     },
-    theme,
 };
 
 pub struct LogsScreen {
@@ -55,6 +58,28 @@ impl LogsScreen {
         }
     }
 
+    /// Refreshes logs when logs in the log directory change.
+    ///
+    /// Logs are watched in a background thread until Cursive quits.
+    pub fn watch(siv: &mut Cursive, log_dir: &Path) {
+        let cb = siv.cb_sink().clone();
+        let log_dir = log_dir.to_path_buf();
+        thread::spawn(move || {
+            let mut watch = LogDirWatch::new(&log_dir);
+            loop {
+                watch.wait();
+                let sent = cb.send(Box::new(|siv| {
+                    App::with_screen(siv, AppScreen::Logs, |screen: &mut Self| {
+                        screen.logs_mut().refresh_items();
+                    });
+                }));
+                if sent.is_err() {
+                    break;
+                }
+            }
+        });
+    }
+
     fn fmt_log_dir(log_dir: &Path) -> StyledString {
         let cwd = std::env::current_dir().unwrap();
         let path = log_dir.strip_prefix(cwd).unwrap_or(log_dir);
@@ -138,6 +163,12 @@ impl LogsScreen {
         maybe_part!("Status", status);
         maybe_part!("Model", model);
         maybe_part!("Dataset", dataset);
+        for tag in &filter.tags {
+            parts.push(StyledString::concatenate([
+                StyledString::styled("Tag ", theme::Style::footer_caption()),
+                StyledString::styled(tag, theme::Style::footer_highlight()),
+            ]));
+        }
         StyledString::concatenate([StyledString::concatenate(intersperse(
             parts,
             StyledString::styled(" | ", theme::Style::footer_sep()),
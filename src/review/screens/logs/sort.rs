@@ -50,6 +50,8 @@ impl SortDialog {
                     ("m", col!(Col::Model, "Model")),
                     ("d", col!(Col::Dataset, "Dataset")),
                     ("r", col!(Col::Score, "Score")),
+                    ("h", col!(Col::Human, "Human score")),
+                    ("a", col!(Col::Agreement, "Agreement")),
                     ("n", col!(Col::Dataset, "Number of samples")),
                 ],
             ),
@@ -99,6 +101,8 @@ impl ViewWrapper for SortDialog {
             Event::Char('d') => Self::on_sort(Col::Dataset),
             Event::Char('n') => Self::on_sort(Col::Samples),
             Event::Char('r') => Self::on_sort(Col::Score),
+            Event::Char('h') => Self::on_sort(Col::Human),
+            Event::Char('a') => Self::on_sort(Col::Agreement),
             Event::Char('q') | Event::Char('Q') => EventResult::with_cb(Self::close),
             Event::Key(Key::Esc) => EventResult::with_cb(Self::close),
             _ => handle_wrapped_dialog_event!(self, event),
@@ -17,13 +17,20 @@ use pyo3::Python;
 
 use crate::{
     cursive::views::ScreensView,
-    inspect::log::{EvalLog, list_logs, read_log_header},
+    inspect::{
+        log::list_logs,
+        log_grades::grade_summary,
+        log_index::{LogIndex, LogSummary},
+    },
     py,
     result::Result,
     review::{
         App, AppScreen,
         components::table::{DefaultSortDir, Sort, SortDir, TableColExt, TableView},
-        screens::{common::StyledEvalLog, log::LogScreen},
+        screens::{
+            common::{StyledEvalLog, styled_score},
+            log::LogScreen,
+        },
     },
     util::fit_path_name,
 };
@@ -37,6 +44,8 @@ pub enum Col {
     Model,
     Dataset,
     Score,
+    Human,
+    Agreement,
     Samples,
     Created,
 }
@@ -48,6 +57,7 @@ pub struct Filter {
     pub status: Option<String>,
     pub model: Option<String>,
     pub dataset: Option<String>,
+    pub tags: Vec<String>,
 }
 
 impl Filter {
@@ -61,29 +71,32 @@ impl Filter {
             Col::Model => filter.model = value.model.clone(),
             Col::Dataset => filter.dataset = value.dataset.clone(),
             Col::Score => {}
+            Col::Human => {}
+            Col::Agreement => {}
             Col::Samples => {}
             Col::Created => {}
         };
         filter
     }
 
-    pub fn filter(&self, item: &EvalLog) -> bool {
+    pub fn from_tag(tag: &str) -> Self {
+        Self {
+            tags: vec![tag.into()],
+            ..Default::default()
+        }
+    }
+
+    pub fn filter(&self, item: &LogSummary) -> bool {
         // AND the specified field criteria
         if let Some(task) = self.task.as_ref()
-            && item.eval.task != *task
+            && item.task != *task
         {
             return false;
         }
-        if let Some(run_type) = self.run_type.as_ref() {
-            let eval_run_type = item
-                .eval
-                .run_type()
-                .as_deref()
-                .unwrap_or_default()
-                .to_string();
-            if eval_run_type != *run_type {
-                return false;
-            }
+        if let Some(run_type) = self.run_type.as_ref()
+            && item.run_type.as_deref().unwrap_or_default() != run_type
+        {
+            return false;
         }
         if let Some(status) = self.status.as_ref()
             && item.status.to_string() != *status
@@ -91,21 +104,17 @@ impl Filter {
             return false;
         }
         if let Some(model) = self.model.as_ref()
-            && item.eval.model != *model
+            && item.model != *model
         {
             return false;
         }
-        if let Some(dataset) = self.dataset.as_ref() {
-            let dataset_name = item
-                .eval
-                .dataset
-                .name
-                .as_deref()
-                .unwrap_or_default()
-                .to_string();
-            if dataset_name != *dataset {
-                return false;
-            }
+        if let Some(dataset) = self.dataset.as_ref()
+            && item.dataset.as_deref().unwrap_or_default() != dataset
+        {
+            return false;
+        }
+        if !self.tags.iter().all(|tag| item.tags.contains(tag)) {
+            return false;
         }
         true
     }
@@ -122,85 +131,69 @@ impl Filter {
         merge!(status);
         merge!(model);
         merge!(dataset);
+        for tag in &other.tags {
+            if !self.tags.contains(tag) {
+                self.tags.push(tag.clone());
+            }
+        }
     }
 }
 
-impl From<&EvalLog> for Filter {
-    fn from(value: &EvalLog) -> Self {
+impl From<&LogSummary> for Filter {
+    fn from(value: &LogSummary) -> Self {
         Self {
-            task: Some(value.eval.task.clone()),
-            run_type: Some(
-                value
-                    .eval
-                    .run_type()
-                    .as_deref()
-                    .unwrap_or_default()
-                    .to_string(),
-            ),
+            task: Some(value.task.clone()),
+            run_type: Some(value.run_type.clone().unwrap_or_default()),
             status: Some(value.status.to_string()),
-            model: Some(value.eval.model.clone()),
-            dataset: Some(
-                value
-                    .eval
-                    .dataset
-                    .name
-                    .as_deref()
-                    .unwrap_or_default()
-                    .to_string(),
-            ),
+            model: Some(value.model.clone()),
+            dataset: Some(value.dataset.clone().unwrap_or_default()),
+            // Run type is filtered using 'type:' tags
+            tags: value
+                .tags
+                .iter()
+                .filter(|tag| !tag.starts_with("type:"))
+                .cloned()
+                .collect(),
         }
     }
 }
 
-type LogsTable = TableView<EvalLog, Col>;
+type LogsTable = TableView<LogSummary, Col>;
 
-impl TableColExt<EvalLog> for Col {
-    fn fmt(&self, log: &EvalLog) -> impl Into<StyledString> {
+impl TableColExt<LogSummary> for Col {
+    fn fmt(&self, log: &LogSummary) -> impl Into<StyledString> {
         match self {
             Self::Id => StyledString::styled(fmt_log_id(log), Effect::Dim),
             Self::Task => log.styled_task(),
             Self::Type => log.styled_run_type(),
             Self::Status => log.styled_status(),
-            Self::Model => fit_path_name(log.eval.model.as_str(), 20).into(),
-            Self::Dataset => {
-                fit_path_name(log.eval.dataset.name.as_deref().unwrap_or_default(), 20).into()
-            }
+            Self::Model => fit_path_name(log.model.as_str(), 20).into(),
+            Self::Dataset => fit_path_name(log.dataset.as_deref().unwrap_or_default(), 20).into(),
             Self::Score => log.styled_score(),
-            Self::Samples => log
-                .eval
-                .dataset
-                .evaluated_count()
-                .map(|n| n.to_string())
-                .unwrap_or_default()
-                .into(),
+            Self::Human => styled_score(log.grades.human_accuracy()),
+            Self::Agreement => styled_score(log.grades.agreement()),
+            Self::Samples => match log.progress {
+                Some((completed, total)) => format!("{completed}/{total}"),
+                None => log.samples.map(|n| n.to_string()).unwrap_or_default(),
+            }
+            .into(),
             Self::Created => log.styled_created(),
         }
     }
 
-    fn cmp(&self, lhs: &EvalLog, rhs: &EvalLog) -> Ordering {
+    fn cmp(&self, lhs: &LogSummary, rhs: &LogSummary) -> Ordering {
         match self {
-            Self::Id => lhs.eval.task_id.cmp(&rhs.eval.task_id),
-            Self::Task => lhs.eval.task.cmp(&rhs.eval.task),
-            Self::Type => lhs.eval.run_type().into_iter().cmp(rhs.eval.run_type()),
+            Self::Id => lhs.task_id.cmp(&rhs.task_id),
+            Self::Task => lhs.task.cmp(&rhs.task),
+            Self::Type => lhs.run_type.cmp(&rhs.run_type),
             Self::Status => lhs.status.cmp(&rhs.status),
-            Self::Model => lhs.eval.model.cmp(&rhs.eval.model),
-            Self::Dataset => lhs.eval.dataset.name.cmp(&rhs.eval.dataset.name),
-            Self::Score => lhs
-                .results
-                .as_ref()
-                .and_then(|r| r.first_accuracy().map(|f| (f * 1000.) as i64))
-                .into_iter()
-                .cmp(
-                    rhs.results
-                        .as_ref()
-                        .and_then(|r| r.first_accuracy().map(|f| (f * 1000.) as i64)),
-                ),
-            Self::Samples => lhs
-                .eval
-                .dataset
-                .evaluated_count()
-                .cmp(&rhs.eval.dataset.evaluated_count()),
-            Self::Created => lhs.eval.created.cmp(&rhs.eval.created),
+            Self::Model => lhs.model.cmp(&rhs.model),
+            Self::Dataset => lhs.dataset.cmp(&rhs.dataset),
+            Self::Score => cmp_score(lhs.accuracy, rhs.accuracy),
+            Self::Human => cmp_score(lhs.grades.human_accuracy(), rhs.grades.human_accuracy()),
+            Self::Agreement => cmp_score(lhs.grades.agreement(), rhs.grades.agreement()),
+            Self::Samples => lhs.samples.cmp(&rhs.samples),
+            Self::Created => lhs.created.cmp(&rhs.created),
         }
     }
 }
@@ -209,6 +202,8 @@ impl DefaultSortDir for Col {
     fn default_sort(&self) -> SortDir {
         match self {
             Self::Score => SortDir::Desc,
+            Self::Human => SortDir::Desc,
+            Self::Agreement => SortDir::Desc,
             Self::Samples => SortDir::Desc,
             Self::Created => SortDir::Desc,
             _ => SortDir::Asc,
@@ -216,8 +211,13 @@ impl DefaultSortDir for Col {
     }
 }
 
-fn fmt_log_id(log: &EvalLog) -> &str {
-    let id = &log.eval.task_id;
+fn cmp_score(lhs: Option<f64>, rhs: Option<f64>) -> Ordering {
+    lhs.map(|f| (f * 1000.) as i64)
+        .cmp(&rhs.map(|f| (f * 1000.) as i64))
+}
+
+fn fmt_log_id(log: &LogSummary) -> &str {
+    let id = &log.task_id;
     match id.split_at_checked(4) {
         Some((s, _)) => s,
         None => id,
@@ -244,11 +244,13 @@ impl LogsView {
                 .col(Col::Model, "Model")
                 .col(Col::Dataset, "Dataset")
                 .col(Col::Score, "Score")
+                .col(Col::Human, "Human")
+                .col(Col::Agreement, "Agree")
                 .col(Col::Samples, "Samples")
                 .col(Col::Created, "Created")
                 .sort(Sort::desc(Col::Created))
                 .empty_msg(Self::default_empty_msg())
-                .on_select(|log: &EvalLog| {
+                .on_select(|log: &LogSummary| {
                     let location = log.location.clone();
                     EventResult::with_cb(move |siv| {
                         let location = location.clone();
@@ -304,8 +306,8 @@ impl LogsView {
                     items
                 };
                 let table = self.table_mut();
-                if let Some(active_id) = table.get_active().map(|item| item.eval.task_id.clone()) {
-                    table.set_items_with_active(items, |item| item.eval.task_id == active_id);
+                if let Some(active_id) = table.get_active().map(|item| item.task_id.clone()) {
+                    table.set_items_with_active(items, |item| item.task_id == active_id);
                 } else {
                     table.set_items(items);
                 }
@@ -314,19 +316,25 @@ impl LogsView {
         }
     }
 
-    fn items(log_dir: &Path) -> Result<Vec<EvalLog>> {
+    fn items(log_dir: &Path) -> Result<Vec<LogSummary>> {
         py::init();
         Python::attach(|py| {
-            Ok(list_logs(py, log_dir)?
+            let mut index = LogIndex::open(log_dir);
+            let items = list_logs(py, log_dir)?
                 .into_iter()
-                .filter_map(|log_info| match read_log_header(py, &log_info.name) {
-                    Ok(log) => Some(log),
+                .filter_map(|log_info| match index.summary(py, &log_info) {
+                    Ok(log) => Some(LogSummary {
+                        grades: grade_summary(&log_info.name),
+                        ..log
+                    }),
                     Err(err) => {
                         log::error!("Reading {}: {:?}", log_info.name, err);
                         None
                     }
                 })
-                .collect_vec())
+                .collect_vec();
+            index.save();
+            Ok(items)
         })
     }
 
@@ -348,11 +356,11 @@ impl LogsView {
         self.table().get_sort()
     }
 
-    pub fn get_active(&self) -> Option<&EvalLog> {
+    pub fn get_active(&self) -> Option<&LogSummary> {
         self.table().get_active()
     }
 
-    fn table(&self) -> &TableView<EvalLog, Col> {
+    fn table(&self) -> &TableView<LogSummary, Col> {
         self.inner
             .get_screen(self.table_screen)
             .unwrap()
@@ -2,16 +2,19 @@ use cliclack::ThemeState;
 use console::{Emoji, Style};
 use tabled::settings::Color;
 
-struct CliTheme;
+pub struct CliTheme;
 
 // Copied from cliclack Theme
 const S_BAR_END: Emoji = Emoji("└", "—");
-const S_BAR: Emoji = Emoji("│", "|");
+pub const S_BAR: Emoji = Emoji("│", "|");
+
+/// Progress spinner frames.
+pub const SPINNER_CHARS: &str = "⠋⠙⠚⠒⠂⠂⠒⠲⠴⠦⠖⠒⠐⠐⠒⠓⠋";
 
 impl cliclack::Theme for CliTheme {
     /// Customize the progress spinner.
     fn spinner_chars(&self) -> String {
-        "⠋⠙⠚⠒⠂⠂⠒⠲⠴⠦⠖⠒⠐⠐⠒⠓⠋".to_string()
+        SPINNER_CHARS.to_string()
     }
 
     /// Customize footer.
@@ -1,8 +1,9 @@
 use core::fmt;
 use std::{
     borrow::Cow,
-    env, io,
+    env, fs, io,
     path::{MAIN_SEPARATOR, Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
 };
 
 use tabled::{
@@ -202,6 +203,45 @@ impl PathExt for Path {
     }
 }
 
+/// Temporary directory that's removed when dropped.
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+    /// Creates a directory in the system temp dir.
+    ///
+    /// The directory name is `prefix` followed by the process ID and a
+    /// counter, so that each directory is unique.
+    pub fn create(prefix: &str) -> io::Result<Self> {
+        static COUNT: AtomicUsize = AtomicUsize::new(0);
+        let path = env::temp_dir().join(format!(
+            "{prefix}-{}-{}",
+            std::process::id(),
+            COUNT.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::create_dir_all(&path)?;
+        Ok(Self(path))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.0
+    }
+
+    /// Keeps the directory rather than removing it when dropped.
+    ///
+    /// Returns the directory path.
+    pub fn keep(mut self) -> PathBuf {
+        std::mem::take(&mut self.0)
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        if !self.0.as_os_str().is_empty() {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::util::fit_path_name;